@@ -0,0 +1,52 @@
+//! 路由匹配基准测试：前缀树匹配器与线性扫描对比
+//!
+//! 运行：`cargo bench --bench matcher`
+
+use divan::Bencher;
+use uni_routing::matcher::RouteMatcher;
+use uni_routing::routing::{find_route, HttpMethod, RouteInfo};
+
+fn main() {
+    divan::main();
+}
+
+/// 生成 `resources * 4` 条典型的 REST 路由
+fn routes(resources: usize) -> Vec<RouteInfo> {
+    (0..resources)
+        .flat_map(|index| {
+            [
+                (HttpMethod::GET, format!("/api/resource{}", index)),
+                (HttpMethod::POST, format!("/api/resource{}", index)),
+                (HttpMethod::GET, format!("/api/resource{}/{{id:u64}}", index)),
+                (HttpMethod::GET, format!("/api/resource{}/{{id}}/files/{{*path}}", index)),
+            ]
+        })
+        .map(|(method, path)| RouteInfo {
+            path,
+            method,
+            ..Default::default()
+        })
+        .collect()
+}
+
+const SIZES: &[usize] = &[250, 1000, 2500];
+
+#[divan::bench(args = SIZES)]
+fn radix_tree(bencher: Bencher, resources: usize) {
+    let matcher = RouteMatcher::new(routes(resources)).unwrap();
+    let path = format!("/api/resource{}/42/files/a/b.txt", resources - 1);
+    bencher.bench(|| matcher.find(divan::black_box("GET"), divan::black_box(&path)).unwrap().params.len());
+}
+
+#[divan::bench(args = SIZES)]
+fn linear_scan(bencher: Bencher, resources: usize) {
+    let routes = routes(resources);
+    let path = format!("/api/resource{}/42/files/a/b.txt", resources - 1);
+    bencher.bench(|| find_route(&routes, divan::black_box("GET"), divan::black_box(&path)).is_some());
+}
+
+#[divan::bench(args = SIZES)]
+fn compile(bencher: Bencher, resources: usize) {
+    let routes = routes(resources);
+    bencher.bench(|| RouteMatcher::new(routes.clone()).unwrap());
+}
@@ -2,17 +2,24 @@
 
 use actix_web::{web, App, HttpServer, HttpResponse, Result as ActixResult, middleware::Logger};
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use std::sync::Arc;
+use uni_routing::frameworks::actix::{openapi_resource, routes_resource, UniMiddleware};
+use uni_routing::introspect::{self, Format};
+use uni_routing::openapi::OpenApiBuilder;
+use uni_routing::auth::JwtToken;
+use uni_routing::middleware::{CorsMiddleware, LoggingMiddleware, MiddlewareChain, MiddlewareFactory};
 use uni_routing_macros::uni_routing;
 
 
-#[derive(Serialize, Deserialize, Clone)]
+#[derive(Serialize, Deserialize, Clone, ToSchema)]
 struct User {
     id: u64,
     name: String,
     email: String,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 struct CreateUserRequest {
     name: String,
     email: String,
@@ -135,7 +142,7 @@ async fn swagger_ui() -> ActixResult<HttpResponse> {
 <html>
 <head>
     <title>Uni Routing API Documentation</title>
-    <link rel="stylesheet" type="text/css" href="https://unpkg.com/swagger-ui-dist@4/swagger-ui.css" />
+    <link rel="stylesheet" type="text/css" href="https://unpkg.com/swagger-ui-dist@5/swagger-ui.css" />
     <style>
         html { box-sizing: border-box; overflow: -moz-scrollbars-vertical; overflow-y: scroll; }
         *, *:before, *:after { box-sizing: inherit; }
@@ -144,8 +151,8 @@ async fn swagger_ui() -> ActixResult<HttpResponse> {
 </head>
 <body>
     <div id="swagger-ui"></div>
-    <script src="https://unpkg.com/swagger-ui-dist@4/swagger-ui-bundle.js"></script>
-    <script src="https://unpkg.com/swagger-ui-dist@4/swagger-ui-standalone-preset.js"></script>
+    <script src="https://unpkg.com/swagger-ui-dist@5/swagger-ui-bundle.js"></script>
+    <script src="https://unpkg.com/swagger-ui-dist@5/swagger-ui-standalone-preset.js"></script>
     <script>
         window.onload = function() {
             const ui = SwaggerUIBundle({
@@ -172,84 +179,6 @@ async fn swagger_ui() -> ActixResult<HttpResponse> {
         .body(html))
 }
 
-// OpenAPI规范端点（从uni_routing宏生成）
-async fn openapi_spec() -> ActixResult<HttpResponse> {
-    // 从宏生成的注册函数中收集路由信息
-    let routes = vec![
-        __register_route_health_check(),
-        __register_route_get_users(),
-        __register_route_create_user(),
-        __register_route_get_user_by_id(),
-        __register_route_update_user(),
-        __register_route_delete_user(),
-    ];
-    
-    let mut paths = serde_json::Map::new();
-    for route in routes {
-        let path_item = create_path_item(&route);
-        paths.insert(route.path, serde_json::Value::Object(path_item));
-    }
-
-    let spec = serde_json::json!({
-        "openapi": "3.0.0",
-        "info": {
-            "title": "Uni Routing API",
-            "version": "1.0.0",
-            "description": "API documentation automatically generated from uni_routing macros"
-        },
-        "paths": serde_json::Value::Object(paths),
-        "components": {
-            "securitySchemes": {
-                "bearerAuth": {
-                    "type": "http",
-                    "scheme": "bearer",
-                    "bearerFormat": "JWT"
-                }
-            }
-        }
-    });
-    
-    Ok(HttpResponse::Ok().json(spec))
-}
-
-// 创建路径项
-fn create_path_item(route: &uni_routing::routing::RouteInfo) -> serde_json::Map<String, serde_json::Value> {
-    let mut path_item = serde_json::Map::new();
-    let method_str = match route.method {
-        uni_routing::routing::HttpMethod::GET => "get",
-        uni_routing::routing::HttpMethod::POST => "post",
-        uni_routing::routing::HttpMethod::PUT => "put",
-        uni_routing::routing::HttpMethod::DELETE => "delete",
-        uni_routing::routing::HttpMethod::PATCH => "patch",
-    };
-
-    let mut operation = serde_json::Map::new();
-    operation.insert("summary".to_string(), serde_json::Value::String(
-        route.description.clone().unwrap_or_else(|| "API endpoint".to_string())
-    ));
-
-    if route.description.is_some() {
-        operation.insert("description".to_string(), serde_json::Value::String(
-            route.description.clone().unwrap()
-        ));
-    }
-
-    // 添加认证要求
-    if route.auth_policy.is_some() {
-        operation.insert("security".to_string(), serde_json::json!([{"bearerAuth": []}]));
-    }
-
-    // 添加响应
-    let mut responses = serde_json::Map::new();
-    responses.insert("200".to_string(), serde_json::json!({
-        "description": "Successful response"
-    }));
-    operation.insert("responses".to_string(), serde_json::Value::Object(responses));
-
-    path_item.insert(method_str.to_string(), serde_json::Value::Object(operation));
-    path_item
-}
-
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
     env_logger::init();
@@ -268,23 +197,12 @@ async fn main() -> std::io::Result<()> {
     ];
     
     println!("📋 Registered routes (from uni_routing macro):");
-    for route in &routes {
-        let auth_info = if route.auth_policy.is_some() {
-            "🔒 (requires auth)"
-        } else {
-            "🔓 (public)"
-        };
-        println!("  {} {} {} - {}", 
-            route.method, 
-            route.path, 
-            auth_info,
-            route.description.as_deref().unwrap_or("No description")
-        );
-    }
+    println!("{}", introspect::render(&routes, Format::Table));
     println!();
     println!("🌐 Server starting on http://localhost:8080");
     println!("📖 Swagger UI: http://localhost:8080/swagger");
     println!("📄 OpenAPI Spec: http://localhost:8080/swagger/openapi.json");
+    println!("🗺️  Route table: http://localhost:8080/_routes?format=markdown");
     println!();
     println!("🧪 Try these commands:");
     println!("  curl -X GET http://localhost:8080/api/health");
@@ -297,10 +215,32 @@ async fn main() -> std::io::Result<()> {
     println!("📝 Note: All routes are defined using #[uni_routing] macro and automatically registered!");
     println!("   This demonstrates true automatic route registration, not just metadata!");
     
+    // OpenAPI文档（从uni_routing宏生成），与 Axum、Rocket 示例使用相同的构建器
+    let openapi = OpenApiBuilder::new("Uni Routing API", "1.0.0")
+        .description("API documentation automatically generated from uni_routing macros")
+        .server("http://localhost:8080", Some("Local development server"))
+        .routes(routes.clone())
+        .build();
+
+    // 管理路由输出的路由表
+    let admin_routes = routes.clone();
+
+    // 统一中间件链，与 Axum、Rocket 示例使用相同的配置
+    // 路由声明的认证策略由中间件链统一执行。演示用途：任何 Bearer 令牌都视为有效，实际项目中应在解码函数中校验JWT
+    let middleware_chain = Arc::new(
+        MiddlewareChain::new()
+            .add_middleware(Arc::new(LoggingMiddleware::default()))
+            .add_middleware(Arc::new(CorsMiddleware::default().with_routes(routes.clone())))
+            .with_token_decoder(Arc::new(|_token: &str| Some(JwtToken { payload: Default::default() })))
+            .with_routes(routes, &MiddlewareFactory::default())
+            .expect("invalid route middleware"),
+    );
+
     // 使用Actix-web原生路由注册，但所有路由信息都来自uni_routing宏
-    HttpServer::new(|| {
+    HttpServer::new(move || {
         App::new()
             .wrap(Logger::default())
+            .wrap(UniMiddleware::from_arc(middleware_chain.clone()))
             // 健康检查端点 - 来自uni_routing宏
             .service(
                 web::resource("/api/health")
@@ -324,10 +264,9 @@ async fn main() -> std::io::Result<()> {
                 web::resource("/swagger")
                     .route(web::get().to(swagger_ui))
             )
-            .service(
-                web::resource("/swagger/openapi.json")
-                    .route(web::get().to(openapi_spec))
-            )
+            .service(openapi_resource("/swagger/openapi.json", &openapi))
+            // 路由表管理端点
+            .service(routes_resource("/_routes", &admin_routes))
     })
     .bind("127.0.0.1:8080")?
     .run()
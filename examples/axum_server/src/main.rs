@@ -1,31 +1,34 @@
 //! Axum服务器示例 - 真正使用 uni_routing 宏进行自动路由注册和文档生成
 
 use axum::{
-    extract::{Path, State},
+    extract::Path,
     http::StatusCode,
     response::Json,
-    routing::{get, post, put, delete},
+    routing::get,
     Router,
 };
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 use std::sync::Arc;
 use uni_routing_macros::uni_routing;
-use uni_routing::middleware::{MiddlewareChain, AuthMiddleware, LoggingMiddleware, CorsMiddleware};
-use uni_routing::auth::AuthPolicy;
+use uni_routing::frameworks::axum::{openapi_route, UniMiddlewareLayer};
+use uni_routing::openapi::OpenApiBuilder;
+use uni_routing::auth::JwtToken;
+use uni_routing::middleware::{MiddlewareChain, LoggingMiddleware, CorsMiddleware, MiddlewareFactory};
 
 #[derive(Serialize, Deserialize, Clone)]
 struct AppState {
     // 应用状态可以在这里添加
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, ToSchema)]
 struct User {
     id: u64,
     name: String,
     email: String,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 struct CreateUserRequest {
     name: String,
     email: String,
@@ -141,11 +144,11 @@ async fn swagger_ui() -> Result<String, StatusCode> {
 <html>
 <head>
     <title>Uni Routing API Documentation</title>
-    <link rel="stylesheet" type="text/css" href="https://unpkg.com/swagger-ui-dist@4/swagger-ui.css" />
+    <link rel="stylesheet" type="text/css" href="https://unpkg.com/swagger-ui-dist@5/swagger-ui.css" />
 </head>
 <body>
     <div id="swagger-ui"></div>
-    <script src="https://unpkg.com/swagger-ui-dist@4/swagger-ui-bundle.js"></script>
+    <script src="https://unpkg.com/swagger-ui-dist@5/swagger-ui-bundle.js"></script>
     <script>
         SwaggerUIBundle({
             url: '/swagger/openapi.json',
@@ -159,84 +162,6 @@ async fn swagger_ui() -> Result<String, StatusCode> {
     Ok(html.to_string())
 }
 
-// OpenAPI 规范端点（从uni_routing宏生成）
-async fn openapi_spec() -> Result<Json<serde_json::Value>, StatusCode> {
-    // 从宏生成的注册函数中收集路由信息
-    let routes = vec![
-        __register_route_health_check(),
-        __register_route_get_users(),
-        __register_route_create_user(),
-        __register_route_get_user_by_id(),
-        __register_route_update_user(),
-        __register_route_delete_user(),
-    ];
-    
-    let mut paths = serde_json::Map::new();
-    for route in routes {
-        let path_item = create_path_item(&route);
-        paths.insert(route.path, serde_json::Value::Object(path_item));
-    }
-
-    let spec = serde_json::json!({
-        "openapi": "3.0.0",
-        "info": {
-            "title": "Uni Routing API",
-            "version": "1.0.0",
-            "description": "API documentation automatically generated from uni_routing macros"
-        },
-        "paths": serde_json::Value::Object(paths),
-        "components": {
-            "securitySchemes": {
-                "bearerAuth": {
-                    "type": "http",
-                    "scheme": "bearer",
-                    "bearerFormat": "JWT"
-                }
-            }
-        }
-    });
-    
-    Ok(Json(spec))
-}
-
-// 创建路径项
-fn create_path_item(route: &uni_routing::routing::RouteInfo) -> serde_json::Map<String, serde_json::Value> {
-    let mut path_item = serde_json::Map::new();
-    let method_str = match route.method {
-        uni_routing::routing::HttpMethod::GET => "get",
-        uni_routing::routing::HttpMethod::POST => "post",
-        uni_routing::routing::HttpMethod::PUT => "put",
-        uni_routing::routing::HttpMethod::DELETE => "delete",
-        uni_routing::routing::HttpMethod::PATCH => "patch",
-    };
-
-    let mut operation = serde_json::Map::new();
-    operation.insert("summary".to_string(), serde_json::Value::String(
-        route.description.clone().unwrap_or_else(|| "API endpoint".to_string())
-    ));
-
-    if route.description.is_some() {
-        operation.insert("description".to_string(), serde_json::Value::String(
-            route.description.clone().unwrap()
-        ));
-    }
-
-    // 添加认证要求
-    if route.auth_policy.is_some() {
-        operation.insert("security".to_string(), serde_json::json!([{"bearerAuth": []}]));
-    }
-
-    // 添加响应
-    let mut responses = serde_json::Map::new();
-    responses.insert("200".to_string(), serde_json::json!({
-        "description": "Successful response"
-    }));
-    operation.insert("responses".to_string(), serde_json::Value::Object(responses));
-
-    path_item.insert(method_str.to_string(), serde_json::Value::Object(operation));
-    path_item
-}
-
 #[tokio::main]
 async fn main() {
     // 初始化日志
@@ -244,15 +169,6 @@ async fn main() {
     
     println!("🚀 Starting Axum server with uni_routing...");
     
-    // 设置中间件链（演示用途）
-    let auth_policy = AuthPolicy::new("scoped:scope1,scope2");
-    let _middleware_chain = MiddlewareChain::new()
-        .add_middleware(Arc::new(LoggingMiddleware))
-        .add_middleware(Arc::new(CorsMiddleware::default()))
-        .add_middleware(Arc::new(AuthMiddleware::new(auth_policy)));
-        
-    println!("✅ Middleware chain configured");
-    
     // 创建应用状态
     let app_state = Arc::new(AppState {});
     
@@ -298,8 +214,8 @@ async fn main() {
     println!();
     println!("🧪 Try these commands:");
     println!("  curl -X GET http://localhost:8080/api/health");
-    println!("  curl -X GET http://localhost:8080/api/users");
-    println!("  curl -X POST http://localhost:8080/api/users -H 'Content-Type: application/json' -d '{{\"name\":\"Test User\",\"email\":\"test@example.com\"}}'");
+    println!("  curl -X GET http://localhost:8080/api/users -H 'Authorization: Bearer demo'");
+    println!("  curl -X POST http://localhost:8080/api/users -H 'Authorization: Bearer demo' -H 'Content-Type: application/json' -d '{{\"name\":\"Test User\",\"email\":\"test@example.com\"}}'");
     println!("  curl -X GET http://localhost:8080/api/users/123");
     println!("  curl -X PUT http://localhost:8080/api/users/123 -H 'Content-Type: application/json' -d '{{\"name\":\"Updated User\",\"email\":\"updated@example.com\"}}'");
     println!("  curl -X DELETE http://localhost:8080/api/users/123");
@@ -307,6 +223,23 @@ async fn main() {
     println!("📝 Note: All routes are defined using #[uni_routing] macro and automatically registered!");
     println!("   This demonstrates true automatic route registration, not just metadata!");
     
+    // OpenAPI文档（从uni_routing宏生成），与 Actix、Rocket 示例使用相同的构建器
+    let openapi = OpenApiBuilder::new("Uni Routing API", "1.0.0")
+        .description("API documentation automatically generated from uni_routing macros")
+        .server("http://localhost:8080", Some("Local development server"))
+        .routes(routes.clone())
+        .build();
+
+    // 统一中间件链，与 Actix、Rocket 示例使用相同的配置
+    // 路由声明的认证策略由中间件链统一执行。演示用途：任何 Bearer 令牌都视为有效，实际项目中应在解码函数中校验JWT
+    let middleware_chain = MiddlewareChain::new()
+        .add_middleware(Arc::new(LoggingMiddleware::default()))
+        .add_middleware(Arc::new(CorsMiddleware::default().with_routes(routes.clone())))
+        .with_token_decoder(Arc::new(|_token: &str| Some(JwtToken { payload: Default::default() })));
+        
+    println!("✅ Middleware chain configured");
+    println!();
+
     // 创建路由 - 使用 Axum 原生路由注册，但所有路由信息都来自uni_routing宏
     let app = Router::new()
         // 健康检查端点 - 来自uni_routing宏
@@ -317,11 +250,19 @@ async fn main() {
         .route("/api/users/:id", get(get_user_by_id).put(update_user).delete(delete_user))
         // Swagger文档端点 - 自动生成
         .route("/swagger", get(swagger_ui))
-        .route("/swagger/openapi.json", get(openapi_spec))
+        .route("/swagger/openapi.json", openapi_route(&openapi))
+        .layer(UniMiddlewareLayer::new(
+            middleware_chain
+                .with_routes(routes, &MiddlewareFactory::default())
+                .expect("invalid route middleware"),
+        ))
         .with_state(app_state);
     
     // 启动服务器
     let listener = tokio::net::TcpListener::bind("127.0.0.1:8080").await.unwrap();
     println!("Server running on http://localhost:8080");
-    axum::serve(listener, app).await.unwrap();
+    // 提供客户端地址，供中间件链记录日志
+    axum::serve(listener, app.into_make_service_with_connect_info::<std::net::SocketAddr>())
+        .await
+        .unwrap();
 }
\ No newline at end of file
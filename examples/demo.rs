@@ -86,8 +86,8 @@ fn main() {
             "🔓 (公开访问)"
         };
         
-        println!("  {} {} {} - {}", 
-            format!("{:?}", route.method), 
+        println!("  {:?} {} {} - {}", 
+            route.method, 
             route.path, 
             auth_info,
             route.description.as_deref().unwrap_or("无描述")
@@ -99,7 +99,7 @@ fn main() {
     println!("🔧 自动路由分组和注册:");
     let mut route_groups: HashMap<String, Vec<&RouteInfo>> = HashMap::new();
     for route in &routes {
-        route_groups.entry(route.path.clone()).or_insert_with(Vec::new).push(route);
+        route_groups.entry(route.path.clone()).or_default().push(route);
     }
 
     for (path, route_infos) in route_groups {
@@ -126,7 +126,7 @@ fn main() {
     println!("🔐 认证策略解析:");
     for route in &routes {
         if let Some(auth_policy) = &route.auth_policy {
-            println!("  路径 {} {}: {}", route.path, format!("{:?}", route.method), auth_policy.expression);
+            println!("  路径 {} {:?}: {}", route.path, route.method, auth_policy.expression);
         }
     }
     println!();
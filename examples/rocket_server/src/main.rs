@@ -1,17 +1,23 @@
 //! Rocket服务器示例 - 真正使用 uni_routing 宏进行自动路由注册和文档生成
 
-use rocket::{get, post, put, delete, serde::json::Json};
+use rocket::{get, post, put, delete, routes, serde::json::Json};
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use std::sync::Arc;
+use uni_routing::frameworks::rocket::{openapi_route, UniFairing};
+use uni_routing::openapi::OpenApiBuilder;
+use uni_routing::auth::JwtToken;
+use uni_routing::middleware::{CorsMiddleware, LoggingMiddleware, MiddlewareChain, MiddlewareFactory};
 use uni_routing_macros::uni_routing;
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, ToSchema)]
 struct User {
     id: u64,
     name: String,
     email: String,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 struct CreateUserRequest {
     name: String,
     email: String,
@@ -56,7 +62,7 @@ async fn get_users() -> Json<Vec<User>> {
     auth_policy = "role:admin,permission:users.write",
     description = "创建新用户，需要管理员权限和用户写入权限"
 )]
-#[post("/api/users", format = "json")]
+#[post("/api/users", format = "json", data = "<user_data>")]
 async fn create_user(
     user_data: Json<CreateUserRequest>
 ) -> (rocket::http::Status, Json<User>) {
@@ -79,7 +85,7 @@ async fn create_user(
 #[get("/api/users/<id>")]
 async fn get_user_by_id(id: u64) -> Json<User> {
     let user = User {
-        id: id,
+        id,
         name: format!("User {}", id),
         email: format!("user{}@example.com", id),
     };
@@ -94,13 +100,13 @@ async fn get_user_by_id(id: u64) -> Json<User> {
     auth_policy = "role:admin,permission:users.write",
     description = "更新指定ID的用户信息"
 )]
-#[put("/api/users/<id>", format = "json")]
+#[put("/api/users/<id>", format = "json", data = "<user_data>")]
 async fn update_user(
     id: u64,
     user_data: Json<CreateUserRequest>
 ) -> Json<User> {
     let updated_user = User {
-        id: id,
+        id,
         name: user_data.name.clone(),
         email: user_data.email.clone(),
     };
@@ -130,11 +136,11 @@ async fn swagger_ui() -> String {
 <html>
 <head>
     <title>Uni Routing API Documentation</title>
-    <link rel="stylesheet" type="text/css" href="https://unpkg.com/swagger-ui-dist@4/swagger-ui.css" />
+    <link rel="stylesheet" type="text/css" href="https://unpkg.com/swagger-ui-dist@5/swagger-ui.css" />
 </head>
 <body>
     <div id="swagger-ui"></div>
-    <script src="https://unpkg.com/swagger-ui-dist@4/swagger-ui-bundle.js"></script>
+    <script src="https://unpkg.com/swagger-ui-dist@5/swagger-ui-bundle.js"></script>
     <script>
         SwaggerUIBundle({
             url: '/swagger/openapi.json',
@@ -146,85 +152,6 @@ async fn swagger_ui() -> String {
     "#.to_string()
 }
 
-// OpenAPI 规范端点（从uni_routing宏生成）
-#[get("/swagger/openapi.json")]
-async fn openapi_spec() -> Json<serde_json::Value> {
-    // 从宏生成的注册函数中收集路由信息
-    let routes = vec![
-        __register_route_health_check(),
-        __register_route_get_users(),
-        __register_route_create_user(),
-        __register_route_get_user_by_id(),
-        __register_route_update_user(),
-        __register_route_delete_user(),
-    ];
-    
-    let mut paths = serde_json::Map::new();
-    for route in routes {
-        let path_item = create_path_item(&route);
-        paths.insert(route.path, serde_json::Value::Object(path_item));
-    }
-
-    let spec = serde_json::json!({
-        "openapi": "3.0.0",
-        "info": {
-            "title": "Uni Routing API",
-            "version": "1.0.0",
-            "description": "API documentation automatically generated from uni_routing macros"
-        },
-        "paths": serde_json::Value::Object(paths),
-        "components": {
-            "securitySchemes": {
-                "bearerAuth": {
-                    "type": "http",
-                    "scheme": "bearer",
-                    "bearerFormat": "JWT"
-                }
-            }
-        }
-    });
-    
-    Json(spec)
-}
-
-// 创建路径项
-fn create_path_item(route: &uni_routing::routing::RouteInfo) -> serde_json::Map<String, serde_json::Value> {
-    let mut path_item = serde_json::Map::new();
-    let method_str = match route.method {
-        uni_routing::routing::HttpMethod::GET => "get",
-        uni_routing::routing::HttpMethod::POST => "post",
-        uni_routing::routing::HttpMethod::PUT => "put",
-        uni_routing::routing::HttpMethod::DELETE => "delete",
-        uni_routing::routing::HttpMethod::PATCH => "patch",
-    };
-
-    let mut operation = serde_json::Map::new();
-    operation.insert("summary".to_string(), serde_json::Value::String(
-        route.description.clone().unwrap_or_else(|| "API endpoint".to_string())
-    ));
-
-    if route.description.is_some() {
-        operation.insert("description".to_string(), serde_json::Value::String(
-            route.description.clone().unwrap()
-        ));
-    }
-
-    // 添加认证要求
-    if route.auth_policy.is_some() {
-        operation.insert("security".to_string(), serde_json::json!([{"bearerAuth": []}]));
-    }
-
-    // 添加响应
-    let mut responses = serde_json::Map::new();
-    responses.insert("200".to_string(), serde_json::json!({
-        "description": "Successful response"
-    }));
-    operation.insert("responses".to_string(), serde_json::Value::Object(responses));
-
-    path_item.insert(method_str.to_string(), serde_json::Value::Object(operation));
-    path_item
-}
-
 #[rocket::main]
 async fn main() {
     // 初始化日志
@@ -282,8 +209,25 @@ async fn main() {
     println!("📝 Note: All routes are defined using #[uni_routing] macro and automatically registered!");
     println!("   This demonstrates true automatic route registration, not just metadata!");
     
+    // OpenAPI文档（从uni_routing宏生成），与 Actix、Axum 示例使用相同的构建器
+    let openapi = OpenApiBuilder::new("Uni Routing API", "1.0.0")
+        .description("API documentation automatically generated from uni_routing macros")
+        .server("http://localhost:8080", Some("Local development server"))
+        .routes(routes.clone())
+        .build();
+
     // 配置并启动 Rocket - 使用简化的路由注册，但所有路由信息都来自uni_routing宏
+    // 统一中间件链，与 Actix、Axum 示例使用相同的配置
+    // 路由声明的认证策略由中间件链统一执行。演示用途：任何 Bearer 令牌都视为有效，实际项目中应在解码函数中校验JWT
+    let middleware_chain = MiddlewareChain::new()
+        .add_middleware(Arc::new(LoggingMiddleware::default()))
+        .add_middleware(Arc::new(CorsMiddleware::default().with_routes(routes.clone())))
+        .with_token_decoder(Arc::new(|_token: &str| Some(JwtToken { payload: Default::default() })))
+        .with_routes(routes, &MiddlewareFactory::default())
+        .expect("invalid route middleware");
+
     let _rocket = rocket::build()
+        .attach(UniFairing::new(middleware_chain))
         // 使用 Rocket 原生路由注册，但展示了 uni_routing 宏的配置
         .mount("/", routes![
             health_check,
@@ -292,9 +236,9 @@ async fn main() {
             get_user_by_id,
             update_user,
             delete_user,
-            swagger_ui,
-            openapi_spec
+            swagger_ui
         ])
+        .mount("/", vec![openapi_route("/swagger/openapi.json", &openapi)])
         .configure(rocket::Config::figment().merge(("port", 8080)))
         .launch()
         .await;
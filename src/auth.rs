@@ -1,7 +1,11 @@
 //! 认证模块，提供统一的认证策略实现
 
-/// 认证策略结构体
-#[derive(Debug, Clone)]
+use crate::Error;
+use serde::{Deserialize, Serialize};
+
+/// 认证策略结构体，序列化为策略表达式字符串
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
 pub struct AuthPolicy {
     /// 策略表达式
     pub policy_expr: String,
@@ -22,6 +26,22 @@ impl AuthPolicy {
             .map(|s| AuthCondition::from_str(s.trim()))
             .collect()
     }
+
+    /// 检查令牌是否满足策略的全部条件
+    ///
+    /// 策略为空时不要求令牌；缺少令牌返回 [`Error::Unauthorized`]，
+    /// 令牌不满足某个条件返回 [`Error::Forbidden`]。
+    pub fn authorize(&self, token: Option<&JwtToken>) -> Result<(), Error> {
+        if self.policy_expr.is_empty() {
+            return Ok(());
+        }
+        let token = token.ok_or(Error::Unauthorized)?;
+        if self.parse().iter().all(|condition| condition.is_satisfied_by(token)) {
+            Ok(())
+        } else {
+            Err(Error::Forbidden)
+        }
+    }
 }
 
 /// 认证条件枚举
@@ -41,6 +61,7 @@ pub enum AuthCondition {
 
 impl AuthCondition {
     /// 从字符串解析认证条件
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Self {
         if s.is_empty() {
             return AuthCondition::ValidToken;
@@ -58,6 +79,17 @@ impl AuthCondition {
             AuthCondition::ValidToken
         }
     }
+
+    /// 检查令牌是否满足该条件，用户条件与载荷中的 `sub` 比较
+    pub fn is_satisfied_by(&self, token: &JwtToken) -> bool {
+        match self {
+            AuthCondition::Role(role) => token.has_role(role),
+            AuthCondition::User(user) => token.payload.get("sub").and_then(|sub| sub.as_str()) == Some(user.as_str()),
+            AuthCondition::Permission(permission) => token.has_permission(permission),
+            AuthCondition::Scope(scope) => token.has_scope(scope),
+            AuthCondition::ValidToken => true,
+        }
+    }
 }
 
 /// JWT令牌结构体
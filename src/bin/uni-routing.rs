@@ -0,0 +1,147 @@
+//! uni-routing 命令行工具
+//!
+//! ```text
+//! uni-routing routes <dump.json|snapshot.toml|-> [--format table|json|markdown]
+//! uni-routing diff <old> <new> [--format text|json]
+//! ```
+//!
+//! `routes` 的输入可以是 `introspect::render(&routes, Format::Json)` 生成的路由表，
+//! 也可以是 `RouteRegistry::snapshot()` 保存的 JSON 或 TOML 快照，`-` 表示从标准输入读取 JSON。
+//!
+//! `diff` 比较两个路由快照或 OpenAPI 文档（JSON），存在破坏性变更时以状态码 1 退出，出错时为 2。
+
+use std::io::Read;
+use std::process::ExitCode;
+
+use uni_routing::diff::{diff, ApiSurface};
+use uni_routing::introspect::{self, Format, RouteRow};
+use uni_routing::snapshot::{RouteSnapshot, SnapshotFormat};
+
+const USAGE: &str = "Usage:
+  uni-routing routes <dump.json|snapshot.toml|-> [--format table|json|markdown]
+  uni-routing diff <old> <new> [--format text|json]";
+
+/// 存在破坏性变更时的退出状态码
+const EXIT_BREAKING: u8 = 1;
+/// 出错时的退出状态码
+const EXIT_ERROR: u8 = 2;
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match run(&args) {
+        Ok((output, code)) => {
+            println!("{}", output);
+            code
+        }
+        Err(message) => {
+            eprintln!("{}", message);
+            ExitCode::from(EXIT_ERROR)
+        }
+    }
+}
+
+fn run(args: &[String]) -> Result<(String, ExitCode), String> {
+    match args.first().map(String::as_str) {
+        Some("routes") => Ok((routes(&args[1..])?, ExitCode::SUCCESS)),
+        Some("diff") => diff_command(&args[1..]),
+        Some("-h") | Some("--help") => Ok((USAGE.to_string(), ExitCode::SUCCESS)),
+        Some(command) => Err(format!("Unknown command: {}\n{}", command, USAGE)),
+        None => Err(USAGE.to_string()),
+    }
+}
+
+/// 解析位置参数和 `--format` 选项
+fn parse_args(args: &[String], inputs: usize) -> Result<(Vec<&str>, Option<&str>), String> {
+    let mut positional = Vec::new();
+    let mut format = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--format" | "-f" => {
+                let value = args.next().ok_or_else(|| format!("Missing value for {}\n{}", arg, USAGE))?;
+                format = Some(value.as_str());
+            }
+            _ if arg.starts_with("--format=") => format = Some(&arg["--format=".len()..]),
+            _ if positional.len() < inputs => positional.push(arg.as_str()),
+            _ => return Err(format!("Unexpected argument: {}\n{}", arg, USAGE)),
+        }
+    }
+    if positional.len() < inputs {
+        return Err(USAGE.to_string());
+    }
+    Ok((positional, format))
+}
+
+/// 读取文件，`-` 表示标准输入
+fn read_input(input: &str) -> Result<String, String> {
+    if input == "-" {
+        let mut content = String::new();
+        std::io::stdin()
+            .read_to_string(&mut content)
+            .map_err(|e| format!("Failed to read stdin: {}", e))?;
+        Ok(content)
+    } else {
+        std::fs::read_to_string(input).map_err(|e| format!("Failed to read {}: {}", input, e))
+    }
+}
+
+/// `routes` 子命令：读取导出文件并按指定格式输出
+fn routes(args: &[String]) -> Result<String, String> {
+    let (inputs, format) = parse_args(args, 1)?;
+    let format = match format {
+        Some(format) => format.parse().map_err(|e: uni_routing::Error| e.to_string())?,
+        None => Format::Table,
+    };
+    let content = read_input(inputs[0])?;
+    Ok(introspect::render_rows(&load_rows(&content, SnapshotFormat::from_path(inputs[0]))?, format))
+}
+
+/// 解析路由表导出文件或注册表快照
+fn load_rows(content: &str, format: SnapshotFormat) -> Result<Vec<RouteRow>, String> {
+    if format == SnapshotFormat::Json {
+        if let Ok(rows) = serde_json::from_str::<Vec<RouteRow>>(content) {
+            return Ok(rows);
+        }
+    }
+    let snapshot = RouteSnapshot::parse(content, format).map_err(|e| e.to_string())?;
+    Ok(introspect::rows(&snapshot.routes))
+}
+
+/// `diff` 子命令：比较两个快照或 OpenAPI 文档
+fn diff_command(args: &[String]) -> Result<(String, ExitCode), String> {
+    let (inputs, format) = parse_args(args, 2)?;
+    let old = load_surface(inputs[0])?;
+    let new = load_surface(inputs[1])?;
+    let report = diff(&old, &new);
+
+    let output = match format.unwrap_or("text") {
+        "text" => {
+            let breaking = report.breaking().count();
+            let summary = format!("{} changes, {} breaking", report.changes.len(), breaking);
+            if report.is_empty() {
+                report.to_string()
+            } else {
+                format!("{}\n\n{}", report, summary)
+            }
+        }
+        "json" => serde_json::to_string_pretty(&report).map_err(|e| e.to_string())?,
+        other => return Err(format!("Invalid diff format: {}, expected text or json", other)),
+    };
+    let code = if report.is_breaking() { ExitCode::from(EXIT_BREAKING) } else { ExitCode::SUCCESS };
+    Ok((output, code))
+}
+
+/// 读取快照或 OpenAPI 文档，包含 `openapi` 字段的 JSON 按 OpenAPI 文档处理
+fn load_surface(input: &str) -> Result<ApiSurface, String> {
+    let content = read_input(input)?;
+    let format = SnapshotFormat::from_path(input);
+    if format == SnapshotFormat::Json {
+        let value: serde_json::Value =
+            serde_json::from_str(&content).map_err(|e| format!("Invalid JSON in {}: {}", input, e))?;
+        if value.get("openapi").is_some() {
+            return Ok(ApiSurface::from_openapi(&value));
+        }
+    }
+    let snapshot = RouteSnapshot::parse(&content, format).map_err(|e| format!("{}: {}", input, e))?;
+    Ok(ApiSurface::from_routes(&snapshot.routes))
+}
@@ -0,0 +1,316 @@
+//! 路由配置模块，从 TOML/YAML 文件加载路由覆盖配置，在启动时合并到宏声明的路由上
+//!
+//! ```toml
+//! [[routes]]
+//! operation_id = "delete_user"
+//! auth_policy = "role:admin"
+//!
+//! [[routes]]
+//! method = "GET"
+//! path = "/api/users/{id}"
+//! rate_limit = "100/min"
+//! remap = "/api/people/{id}"
+//!
+//! [[routes]]
+//! operation_id = "create_user"
+//! disabled = true
+//! ```
+//!
+//! 每条覆盖配置通过操作ID或（方法，路径）选中恰好一个路由。配置中的所有错误
+//! （选不中或选中多个路由、无效的限流配额、重映射后产生冲突等）会在
+//! [`RouteConfig::apply`] 中一并报告。禁用和重映射由
+//! [`MiddlewareChain::with_config`](crate::middleware::MiddlewareChain::with_config) 在请求时生效。
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::auth::AuthPolicy;
+use crate::conflict::detect_conflicts;
+use crate::matcher::{RoutePattern, Segment};
+use crate::middleware::rate_limit::{RateLimit, RateLimitKey};
+use crate::middleware::MiddlewareSpec;
+use crate::routing::{HttpMethod, RouteInfo};
+use crate::Error;
+
+/// 配置文件格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ConfigFormat {
+    /// TOML，覆盖配置写作 `[[routes]]` 表数组
+    #[default]
+    Toml,
+    /// YAML，覆盖配置写作 `routes:` 列表
+    Yaml,
+    /// JSON
+    Json,
+}
+
+impl ConfigFormat {
+    /// 根据文件扩展名判断格式，未知扩展名按 TOML 处理
+    pub fn from_path(path: impl AsRef<Path>) -> Self {
+        match path.as_ref().extension().and_then(|ext| ext.to_str()).map(str::to_ascii_lowercase).as_deref() {
+            Some("yaml") | Some("yml") => ConfigFormat::Yaml,
+            Some("json") => ConfigFormat::Json,
+            _ => ConfigFormat::Toml,
+        }
+    }
+}
+
+/// 路由覆盖配置文件
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RouteConfig {
+    /// 覆盖配置，按文件中的顺序排列
+    #[serde(default)]
+    pub routes: Vec<RouteOverride>,
+}
+
+/// 单个路由的覆盖配置
+///
+/// 选择器为 `operation_id`，或 `method` 与 `path`（包含版本前缀的完整路径）。
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RouteOverride {
+    /// 按操作ID选择路由
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub operation_id: Option<String>,
+    /// 按方法和路径选择路由时的方法
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub method: Option<HttpMethod>,
+    /// 按方法和路径选择路由时的路径
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    /// 替换认证策略
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auth_policy: Option<String>,
+    /// 移除认证策略
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub public: bool,
+    /// 禁用路由，请求返回 404
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub disabled: bool,
+    /// 限流配额，如 `100/min`，替换路由声明的 `rate_limit` 中间件
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rate_limit: Option<String>,
+    /// 限流键，如 `ip`、`subject`、`api_key:X-Api-Key`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rate_limit_key: Option<String>,
+    /// 新的路由模板，必须使用与原模板相同的参数
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remap: Option<String>,
+}
+
+impl RouteOverride {
+    /// 选择器的文本形式，用于错误信息
+    pub fn selector(&self) -> String {
+        match (&self.operation_id, &self.method, &self.path) {
+            (Some(operation_id), _, _) => format!("operation `{}`", operation_id),
+            (None, Some(method), Some(path)) => format!("{} {}", method, path),
+            (None, method, path) => format!(
+                "{} {}",
+                method.as_ref().map_or("?".to_string(), ToString::to_string),
+                path.as_deref().unwrap_or("?")
+            ),
+        }
+    }
+
+    /// 是否选中该路由，路径可以写作路由模板或 OpenAPI 格式
+    pub fn matches(&self, route: &RouteInfo) -> bool {
+        match (&self.operation_id, &self.method, &self.path) {
+            (Some(operation_id), _, _) => route.operation_id.as_deref() == Some(operation_id.as_str()),
+            (None, Some(method), Some(path)) => {
+                route.method == *method && (route.full_path() == *path || route.openapi_path() == *path)
+            }
+            _ => false,
+        }
+    }
+
+    /// 检查覆盖配置本身是否有效
+    fn validate(&self) -> Result<(), String> {
+        match (&self.operation_id, &self.method, &self.path) {
+            (Some(_), None, None) | (None, Some(_), Some(_)) => {}
+            (Some(_), _, _) => return Err("use either operation_id or method and path".to_string()),
+            _ => return Err("operation_id or both method and path are required".to_string()),
+        }
+        let changes = self.auth_policy.is_some() || self.public || self.rate_limit.is_some() || self.remap.is_some();
+        if self.disabled && changes {
+            return Err("a disabled route cannot have other overrides".to_string());
+        }
+        if !self.disabled && !changes {
+            return Err("override does not change anything".to_string());
+        }
+        if self.auth_policy.is_some() && self.public {
+            return Err("auth_policy and public cannot be combined".to_string());
+        }
+        if let Some(limit) = &self.rate_limit {
+            limit.parse::<RateLimit>().map_err(|e| e.to_string())?;
+        }
+        match (&self.rate_limit, &self.rate_limit_key) {
+            (_, None) => {}
+            (Some(_), Some(key)) => {
+                key.parse::<RateLimitKey>().map_err(|e| e.to_string())?;
+            }
+            (None, Some(_)) => return Err("rate_limit_key requires rate_limit".to_string()),
+        }
+        if let Some(remap) = &self.remap {
+            RoutePattern::parse(remap).map_err(|e| e.to_string())?;
+        }
+        Ok(())
+    }
+
+    /// 将覆盖配置应用到路由上
+    fn apply(&self, route: &RouteInfo) -> Result<RouteInfo, String> {
+        let mut route = route.clone();
+        if let Some(policy) = &self.auth_policy {
+            route.auth_policy = Some(AuthPolicy::new(policy));
+        }
+        if self.public {
+            route.auth_policy = None;
+        }
+        if let Some(limit) = &self.rate_limit {
+            let args = std::iter::once(limit.clone()).chain(self.rate_limit_key.clone()).collect();
+            route.middleware.retain(|spec| spec.name != "rate_limit");
+            route.middleware.push(MiddlewareSpec::new("rate_limit", args));
+        }
+        if let Some(remap) = &self.remap {
+            if template_params(&route.path) != template_params(remap) {
+                return Err(format!("remap `{}` must use the same parameters as `{}`", remap, route.path));
+            }
+            route.path = remap.clone();
+        }
+        Ok(route)
+    }
+}
+
+/// 模板中的参数名，以及参数是否为通配参数
+fn template_params(template: &str) -> BTreeMap<String, bool> {
+    RoutePattern::parse(template)
+        .map(|pattern| {
+            pattern
+                .segments()
+                .iter()
+                .filter_map(|segment| match segment {
+                    Segment::Static(_) => None,
+                    Segment::Param { name, .. } => Some((name.clone(), false)),
+                    Segment::CatchAll(name) => Some((name.clone(), true)),
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// 被重映射的路由
+#[derive(Debug, Clone, PartialEq)]
+pub struct RemappedRoute {
+    /// 宏声明的路由，处理函数在框架中挂载于该路径
+    pub original: RouteInfo,
+    /// 生效的路由
+    pub route: RouteInfo,
+}
+
+/// 应用覆盖配置后的路由表
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct AppliedConfig {
+    /// 生效的路由，不包含禁用的路由，按原顺序排列
+    pub routes: Vec<RouteInfo>,
+    /// 被禁用的路由
+    pub disabled: Vec<RouteInfo>,
+    /// 被重映射的路由
+    pub remapped: Vec<RemappedRoute>,
+}
+
+impl RouteConfig {
+    /// 按指定格式解析
+    pub fn parse(content: &str, format: ConfigFormat) -> Result<Self, Error> {
+        let invalid = |e: String| Error::Other(format!("Invalid route configuration: {}", e));
+        match format {
+            ConfigFormat::Toml => toml::from_str(content).map_err(|e| invalid(e.to_string())),
+            ConfigFormat::Yaml => serde_yaml::from_str(content).map_err(|e| invalid(e.to_string())),
+            ConfigFormat::Json => serde_json::from_str(content).map_err(|e| invalid(e.to_string())),
+        }
+    }
+
+    /// 从 TOML 解析
+    pub fn from_toml(content: &str) -> Result<Self, Error> {
+        Self::parse(content, ConfigFormat::Toml)
+    }
+
+    /// 从 YAML 解析
+    pub fn from_yaml(content: &str) -> Result<Self, Error> {
+        Self::parse(content, ConfigFormat::Yaml)
+    }
+
+    /// 从文件读取，格式由扩展名决定
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| Error::Other(format!("Failed to read {}: {}", path.display(), e)))?;
+        Self::parse(&content, ConfigFormat::from_path(path))
+    }
+
+    /// 将覆盖配置合并到路由表上，配置中的所有错误一并返回
+    pub fn apply(&self, routes: Vec<RouteInfo>) -> Result<AppliedConfig, Error> {
+        let mut errors = Vec::new();
+        let mut targets: Vec<Option<&RouteOverride>> = vec![None; routes.len()];
+        for entry in &self.routes {
+            if let Err(e) = entry.validate() {
+                errors.push(format!("{}: {}", entry.selector(), e));
+                continue;
+            }
+            let matched: Vec<usize> = (0..routes.len()).filter(|&i| entry.matches(&routes[i])).collect();
+            match matched.as_slice() {
+                [] => errors.push(format!("{}: matches no route", entry.selector())),
+                [index] if targets[*index].is_some() => {
+                    errors.push(format!("{}: {} is configured more than once", entry.selector(), routes[*index].describe()));
+                }
+                [index] => targets[*index] = Some(entry),
+                _ => {
+                    let described: Vec<String> = matched.iter().map(|&i| routes[i].describe()).collect();
+                    errors.push(format!(
+                        "{}: matches {} routes ({}), select by method and path instead",
+                        entry.selector(),
+                        matched.len(),
+                        described.join(", ")
+                    ));
+                }
+            }
+        }
+
+        let mut applied = AppliedConfig::default();
+        for (route, target) in routes.into_iter().zip(targets) {
+            let Some(entry) = target else {
+                applied.routes.push(route);
+                continue;
+            };
+            if entry.disabled {
+                applied.disabled.push(route);
+                continue;
+            }
+            match entry.apply(&route) {
+                Ok(configured) => {
+                    if entry.remap.is_some() {
+                        applied.remapped.push(RemappedRoute {
+                            original: route,
+                            route: configured.clone(),
+                        });
+                    }
+                    applied.routes.push(configured);
+                }
+                Err(e) => errors.push(format!("{}: {}", entry.selector(), e)),
+            }
+        }
+
+        errors.extend(
+            detect_conflicts(&applied.routes)
+                .iter()
+                .filter(|conflict| conflict.kind.is_fatal())
+                .map(ToString::to_string),
+        );
+        if errors.is_empty() {
+            Ok(applied)
+        } else {
+            Err(Error::Other(format!("Invalid route configuration:\n  - {}", errors.join("\n  - "))))
+        }
+    }
+}
@@ -0,0 +1,200 @@
+//! 路由冲突检测模块，在注册表冻结时检查重复和有歧义的路由
+//!
+//! 两个相同方法（及版本）的路由能匹配同一个请求路径时视为重叠，按能否确定匹配结果分为：
+//!
+//! - [`ConflictKind::Duplicate`]：模板等价（如 `/users/{id}` 与 `/users/<user_id>`），后注册的路由永远不会被匹配
+//! - [`ConflictKind::Ambiguous`]：部分路径由注册顺序决定，例如同一位置不同类型的参数 `{id:u32}` 与 `{id:i64}`，
+//!   或可选参数展开后与另一个路由等价
+//! - [`ConflictKind::Overlap`]：由优先级规则确定（如 `/users/me` 优先于 `/users/{id}`），只作为警告报告
+
+use crate::matcher::{ParamType, RoutePattern, Segment};
+use crate::routing::RouteInfo;
+
+/// 冲突类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ConflictKind {
+    /// 由优先级规则确定匹配结果的重叠
+    Overlap,
+    /// 匹配结果取决于注册顺序
+    Ambiguous,
+    /// 等价的路由模板
+    Duplicate,
+}
+
+impl ConflictKind {
+    /// 是否应阻止注册表冻结
+    pub fn is_fatal(&self) -> bool {
+        !matches!(self, ConflictKind::Overlap)
+    }
+}
+
+/// 两个路由之间的冲突
+#[derive(Debug, Clone)]
+pub struct RouteConflict {
+    /// 冲突类型
+    pub kind: ConflictKind,
+    /// 先注册的路由
+    pub first: RouteInfo,
+    /// 后注册的路由
+    pub second: RouteInfo,
+}
+
+impl std::fmt::Display for RouteConflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let kind = match self.kind {
+            ConflictKind::Duplicate => "duplicate routes",
+            ConflictKind::Ambiguous => "ambiguous routes",
+            ConflictKind::Overlap => "overlapping routes",
+        };
+        write!(f, "{}: {} and {}", kind, self.first.describe(), self.second.describe())
+    }
+}
+
+/// 检查路由表中的所有冲突，按注册顺序报告
+///
+/// 模板无效的路由会被跳过，它们在注册时已被拒绝。
+pub fn detect_conflicts(routes: &[RouteInfo]) -> Vec<RouteConflict> {
+    let patterns: Vec<Option<RoutePattern>> = routes
+        .iter()
+        .map(|route| RoutePattern::parse(&route.full_path()).ok())
+        .collect();
+
+    let mut conflicts = Vec::new();
+    for (i, first) in routes.iter().enumerate() {
+        let Some(first_pattern) = &patterns[i] else {
+            continue;
+        };
+        for (j, second) in routes.iter().enumerate().skip(i + 1) {
+            let Some(second_pattern) = &patterns[j] else {
+                continue;
+            };
+            if first.method != second.method {
+                continue;
+            }
+            if let Some(kind) = compare(first_pattern, second_pattern) {
+                conflicts.push(RouteConflict {
+                    kind,
+                    first: first.clone(),
+                    second: second.clone(),
+                });
+            }
+        }
+    }
+    conflicts
+}
+
+/// 比较两个模板，能匹配同一路径时返回冲突类型
+fn compare(first: &RoutePattern, second: &RoutePattern) -> Option<ConflictKind> {
+    if equivalent(first.segments(), second.segments()) {
+        return Some(ConflictKind::Duplicate);
+    }
+
+    // 可选参数展开为多个不含可选参数的模板，逐对比较
+    let mut result = None;
+    for a in expand(first.segments()) {
+        for b in expand(second.segments()) {
+            let kind = match compare_concrete(&a, &b) {
+                Some(Relation::Equivalent) | Some(Relation::Ambiguous) => ConflictKind::Ambiguous,
+                Some(Relation::Resolved) => ConflictKind::Overlap,
+                None => continue,
+            };
+            result = result.max(Some(kind));
+        }
+    }
+    result
+}
+
+/// 不含可选参数的两个模板之间的关系
+enum Relation {
+    /// 匹配完全相同的路径
+    Equivalent,
+    /// 重叠部分由注册顺序决定
+    Ambiguous,
+    /// 重叠部分由优先级规则决定
+    Resolved,
+}
+
+/// 比较两个不含可选参数的模板，不能匹配同一路径时返回 `None`
+fn compare_concrete(first: &[&Segment], second: &[&Segment]) -> Option<Relation> {
+    let mut relation = Relation::Equivalent;
+    let mut index = 0;
+    loop {
+        let (a, b) = (first.get(index), second.get(index));
+        match (a, b) {
+            (None, None) => return Some(relation),
+            (Some(Segment::CatchAll(_)), Some(Segment::CatchAll(_))) => return Some(relation),
+            // 通配参数可以匹配另一个模板剩余的任意路径段，由优先级规则决定
+            (Some(Segment::CatchAll(_)), _) | (_, Some(Segment::CatchAll(_))) => {
+                return Some(first_difference(relation, Relation::Resolved));
+            }
+            (None, Some(_)) | (Some(_), None) => return None,
+            (Some(a), Some(b)) => {
+                relation = match (a, b) {
+                    (Segment::Static(x), Segment::Static(y)) => {
+                        if x != y {
+                            return None;
+                        }
+                        relation
+                    }
+                    (Segment::Static(value), Segment::Param { ty, .. })
+                    | (Segment::Param { ty, .. }, Segment::Static(value)) => {
+                        if !ty.accepts(value) {
+                            return None;
+                        }
+                        first_difference(relation, Relation::Resolved)
+                    }
+                    (Segment::Param { ty: x, .. }, Segment::Param { ty: y, .. }) => {
+                        if x == y {
+                            relation
+                        } else if !types_overlap(*x, *y) {
+                            return None;
+                        } else if *x == ParamType::Any || *y == ParamType::Any {
+                            first_difference(relation, Relation::Resolved)
+                        } else {
+                            // 不同类型的参数子节点按注册顺序尝试
+                            first_difference(relation, Relation::Ambiguous)
+                        }
+                    }
+                    (Segment::CatchAll(_), _) | (_, Segment::CatchAll(_)) => unreachable!("handled above"),
+                };
+            }
+        }
+        index += 1;
+    }
+}
+
+/// 记录第一处差异：匹配在第一处差异的位置就已确定
+fn first_difference(current: Relation, difference: Relation) -> Relation {
+    match current {
+        Relation::Equivalent => difference,
+        decided => decided,
+    }
+}
+
+/// 两种参数类型是否能接受同一个路径段
+fn types_overlap(a: ParamType, b: ParamType) -> bool {
+    let is_numeric = |ty: ParamType| !matches!(ty, ParamType::Any | ParamType::Bool | ParamType::Uuid);
+    a == b || a == ParamType::Any || b == ParamType::Any || (is_numeric(a) && is_numeric(b))
+}
+
+/// 两个模板是否等价：静态段相同、参数类型和可选性相同（忽略参数名）
+fn equivalent(first: &[Segment], second: &[Segment]) -> bool {
+    first.len() == second.len()
+        && first.iter().zip(second).all(|pair| match pair {
+            (Segment::Static(a), Segment::Static(b)) => a == b,
+            (Segment::Param { ty: a, optional: x, .. }, Segment::Param { ty: b, optional: y, .. }) => a == b && x == y,
+            (Segment::CatchAll(_), Segment::CatchAll(_)) => true,
+            _ => false,
+        })
+}
+
+/// 将末尾的可选参数展开为多个模板，如 `/a/{b?}` 展开为 `/a` 与 `/a/{b}`
+fn expand(segments: &[Segment]) -> Vec<Vec<&Segment>> {
+    let required = segments
+        .iter()
+        .position(|segment| matches!(segment, Segment::Param { optional: true, .. }))
+        .unwrap_or(segments.len());
+    (required..=segments.len())
+        .map(|len| segments[..len].iter().collect())
+        .collect()
+}
@@ -0,0 +1,618 @@
+//! 接口变更检测模块，比较两个路由快照或 OpenAPI 文档并区分破坏性与非破坏性变更
+//!
+//! ```ignore
+//! let report = diff_snapshots(&RouteSnapshot::load("old.toml")?, &registry.snapshot());
+//! if report.is_breaking() {
+//!     eprintln!("{}", report);
+//! }
+//! ```
+//!
+//! 端点按方法和路径（忽略参数名）对应。Schema 的比较考虑数据流向：
+//! 请求中新增必需字段、响应中删除字段都是破坏性的，反之则不是。
+//! 认证策略按逗号分隔的条件集合比较，新策略的条件是旧策略的超集时视为更严格，
+//! 无法比较的修改也按更严格处理。
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+
+use crate::routing::RouteInfo;
+use crate::snapshot::RouteSnapshot;
+
+/// 解析 `$ref` 的最大深度，递归类型超过该深度后保留引用
+const MAX_REF_DEPTH: usize = 8;
+
+/// OpenAPI 路径项中的操作字段名
+const OPERATION_METHODS: [&str; 8] = ["get", "put", "post", "delete", "options", "head", "patch", "trace"];
+
+/// 不影响兼容性的 Schema 关键字
+const ANNOTATION_KEYWORDS: [&str; 6] = ["description", "title", "example", "examples", "deprecated", "$comment"];
+
+/// 变更类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    /// 新增端点
+    EndpointAdded,
+    /// 删除端点
+    EndpointRemoved,
+    /// 新增参数
+    ParameterAdded,
+    /// 删除参数
+    ParameterRemoved,
+    /// 参数的必需性或Schema改变
+    ParameterChanged,
+    /// 请求体改变
+    RequestBodyChanged,
+    /// 新增响应状态码
+    ResponseAdded,
+    /// 删除响应状态码
+    ResponseRemoved,
+    /// 响应Schema改变
+    ResponseSchemaChanged,
+    /// 认证要求变严格
+    AuthTightened,
+    /// 认证要求放宽
+    AuthRelaxed,
+    /// 端点被标记为弃用
+    Deprecated,
+}
+
+/// 一个端点上的一项变更
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Change {
+    /// 变更类型
+    pub kind: ChangeKind,
+    /// 是否会破坏现有客户端
+    pub breaking: bool,
+    /// HTTP方法
+    pub method: String,
+    /// 路径，端点仍存在时使用新文档中的路径
+    pub path: String,
+    /// 变更说明
+    pub message: String,
+}
+
+impl std::fmt::Display for Change {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let severity = if self.breaking { "[breaking]" } else { "[non-breaking]" };
+        write!(f, "{} {} {}: {}", severity, self.method, self.path, self.message)
+    }
+}
+
+/// 比较结果，变更按路径和方法排序
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct DiffReport {
+    /// 所有变更
+    pub changes: Vec<Change>,
+}
+
+impl DiffReport {
+    /// 是否包含破坏性变更
+    pub fn is_breaking(&self) -> bool {
+        self.changes.iter().any(|change| change.breaking)
+    }
+
+    /// 破坏性变更
+    pub fn breaking(&self) -> impl Iterator<Item = &Change> {
+        self.changes.iter().filter(|change| change.breaking)
+    }
+
+    /// 是否没有任何变更
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+impl std::fmt::Display for DiffReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.changes.is_empty() {
+            return write!(f, "No changes");
+        }
+        let lines: Vec<String> = self.changes.iter().map(Change::to_string).collect();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+/// 比较两个路由快照
+pub fn diff_snapshots(old: &RouteSnapshot, new: &RouteSnapshot) -> DiffReport {
+    diff_routes(&old.routes, &new.routes)
+}
+
+/// 比较两组路由
+pub fn diff_routes(old: &[RouteInfo], new: &[RouteInfo]) -> DiffReport {
+    diff(&ApiSurface::from_routes(old), &ApiSurface::from_routes(new))
+}
+
+/// 比较两个 OpenAPI 文档
+pub fn diff_openapi(old: &Value, new: &Value) -> DiffReport {
+    diff(&ApiSurface::from_openapi(old), &ApiSurface::from_openapi(new))
+}
+
+/// 比较两组端点，两侧可以分别来自路由快照和 OpenAPI 文档
+///
+/// 两侧来源不同时认证策略与安全方案无法对应，只比较端点是否需要认证。
+pub fn diff(old: &ApiSurface, new: &ApiSurface) -> DiffReport {
+    let same_source = old.from_routes == new.from_routes;
+    let keys: BTreeSet<&EndpointKey> = old.endpoints.keys().chain(new.endpoints.keys()).collect();
+    let mut changes = Vec::new();
+    for key in keys {
+        match (old.endpoints.get(key), new.endpoints.get(key)) {
+            (Some(old), Some(new)) => compare_endpoints(old, new, same_source, &mut changes),
+            (Some(old), None) => changes.push(old.change(ChangeKind::EndpointRemoved, true, "endpoint removed".to_string())),
+            (None, Some(new)) => changes.push(new.change(ChangeKind::EndpointAdded, false, "endpoint added".to_string())),
+            (None, None) => unreachable!("key comes from one of the surfaces"),
+        }
+    }
+    DiffReport { changes }
+}
+
+/// 端点的对应键：路径模板（参数名统一为 `{}`）和大写的方法名
+type EndpointKey = (String, String);
+
+/// 用于比较的接口描述，来自路由表或 OpenAPI 文档
+#[derive(Debug, Clone, Default)]
+pub struct ApiSurface {
+    endpoints: BTreeMap<EndpointKey, Endpoint>,
+    /// 认证条件是否来自路由的认证策略
+    from_routes: bool,
+}
+
+/// 一个端点中与兼容性有关的部分，Schema 中的 `$ref` 已展开
+#[derive(Debug, Clone)]
+struct Endpoint {
+    method: String,
+    path: String,
+    /// 按（位置，名称）索引的参数
+    params: BTreeMap<(String, String), Param>,
+    request_body: Option<Body>,
+    /// 按状态码索引的响应Schema，没有响应体时为 `None`
+    responses: BTreeMap<String, Option<Value>>,
+    /// 认证条件，公开端点为 `None`
+    auth: Option<BTreeSet<String>>,
+    deprecated: bool,
+}
+
+#[derive(Debug, Clone)]
+struct Param {
+    required: bool,
+    schema: Value,
+}
+
+#[derive(Debug, Clone)]
+struct Body {
+    required: bool,
+    schema: Option<Value>,
+}
+
+impl ApiSurface {
+    /// 从路由表构建，认证条件使用路由的认证策略
+    pub fn from_routes(routes: &[RouteInfo]) -> Self {
+        let schemas: Map<String, Value> = routes
+            .iter()
+            .flat_map(|route| {
+                route
+                    .request_body
+                    .iter()
+                    .chain(route.responses.iter().filter_map(|response| response.schema.as_ref()))
+                    .chain(route.path_params.iter().map(|param| &param.schema))
+            })
+            .flat_map(|schema| schema.components.clone())
+            .collect();
+        let root = json!({ "components": { "schemas": schemas } });
+
+        let endpoints = routes
+            .iter()
+            .map(|route| {
+                let params = route
+                    .path_params
+                    .iter()
+                    .map(|param| {
+                        let schema = resolve(&root, &param.schema.schema, 0);
+                        (("path".to_string(), param.name.clone()), Param { required: true, schema })
+                    })
+                    .collect();
+                let request_body = route.request_body.as_ref().map(|body| Body {
+                    required: true,
+                    schema: Some(resolve(&root, &body.schema, 0)),
+                });
+                let mut responses: BTreeMap<String, Option<Value>> = route
+                    .responses
+                    .iter()
+                    .map(|response| {
+                        let schema = response.schema.as_ref().map(|schema| resolve(&root, &schema.schema, 0));
+                        (response.status.to_string(), schema)
+                    })
+                    .collect();
+                if responses.is_empty() {
+                    // 与 OpenAPI 文档中的默认响应一致
+                    responses.insert("200".to_string(), None);
+                }
+                let auth = route.auth_policy.as_ref().map(|policy| {
+                    policy
+                        .policy_expr
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|condition| !condition.is_empty())
+                        .map(str::to_string)
+                        .collect()
+                });
+                let endpoint = Endpoint {
+                    method: route.method.to_string(),
+                    path: route.openapi_path(),
+                    params,
+                    request_body,
+                    responses,
+                    auth,
+                    deprecated: route.deprecated,
+                };
+                (endpoint.key(), endpoint)
+            })
+            .collect();
+        Self { endpoints, from_routes: true }
+    }
+
+    /// 从 OpenAPI 文档构建，认证条件为安全方案名及其作用域（`scheme:scope`）
+    pub fn from_openapi(doc: &Value) -> Self {
+        let mut endpoints = BTreeMap::new();
+        let Some(paths) = doc.get("paths").and_then(Value::as_object) else {
+            return Self { endpoints, from_routes: false };
+        };
+        for (path, item) in paths {
+            let shared_params = item.get("parameters");
+            for method in OPERATION_METHODS {
+                let Some(operation) = item.get(method) else {
+                    continue;
+                };
+                let params = shared_params
+                    .into_iter()
+                    .chain(operation.get("parameters"))
+                    .filter_map(Value::as_array)
+                    .flatten()
+                    .map(|param| resolve(doc, param, 0))
+                    .filter_map(|param| {
+                        let name = param.get("name")?.as_str()?.to_string();
+                        let location = param.get("in")?.as_str()?.to_string();
+                        let required = location == "path" || param.get("required").and_then(Value::as_bool).unwrap_or(false);
+                        let schema = param.get("schema").cloned().unwrap_or_else(|| json!({}));
+                        Some(((location, name), Param { required, schema }))
+                    })
+                    .collect();
+                let request_body = operation.get("requestBody").map(|body| {
+                    let body = resolve(doc, body, 0);
+                    Body {
+                        required: body.get("required").and_then(Value::as_bool).unwrap_or(false),
+                        schema: content_schema(&body),
+                    }
+                });
+                let responses = operation
+                    .get("responses")
+                    .and_then(Value::as_object)
+                    .into_iter()
+                    .flatten()
+                    .map(|(status, response)| (status.clone(), content_schema(&resolve(doc, response, 0))))
+                    .collect();
+                let endpoint = Endpoint {
+                    method: method.to_uppercase(),
+                    path: path.clone(),
+                    params,
+                    request_body,
+                    responses,
+                    auth: security_conditions(operation.get("security").or_else(|| doc.get("security"))),
+                    deprecated: operation.get("deprecated").and_then(Value::as_bool).unwrap_or(false),
+                };
+                endpoints.insert(endpoint.key(), endpoint);
+            }
+        }
+        Self { endpoints, from_routes: false }
+    }
+
+    /// 端点数量
+    pub fn len(&self) -> usize {
+        self.endpoints.len()
+    }
+
+    /// 是否没有端点
+    pub fn is_empty(&self) -> bool {
+        self.endpoints.is_empty()
+    }
+}
+
+impl Endpoint {
+    fn key(&self) -> EndpointKey {
+        let path = self
+            .path
+            .split('/')
+            .map(|segment| if segment.starts_with('{') { "{}" } else { segment })
+            .collect::<Vec<_>>()
+            .join("/");
+        (path, self.method.clone())
+    }
+
+    fn change(&self, kind: ChangeKind, breaking: bool, message: String) -> Change {
+        Change {
+            kind,
+            breaking,
+            method: self.method.clone(),
+            path: self.path.clone(),
+            message,
+        }
+    }
+}
+
+/// 媒体类型内容中的Schema，优先使用 `application/json`
+fn content_schema(value: &Value) -> Option<Value> {
+    let content = value.get("content")?.as_object()?;
+    let media_type = content.get("application/json").or_else(|| content.values().next())?;
+    Some(media_type.get("schema").cloned().unwrap_or_else(|| json!({})))
+}
+
+/// 安全要求中的认证条件；没有安全要求或包含空要求（认证可选）时为公开端点
+fn security_conditions(security: Option<&Value>) -> Option<BTreeSet<String>> {
+    let requirements = security?.as_array()?;
+    if requirements.is_empty() {
+        return None;
+    }
+    let mut conditions = BTreeSet::new();
+    for requirement in requirements {
+        let requirement = requirement.as_object()?;
+        if requirement.is_empty() {
+            return None;
+        }
+        for (scheme, scopes) in requirement {
+            conditions.insert(scheme.clone());
+            for scope in scopes.as_array().into_iter().flatten().filter_map(Value::as_str) {
+                conditions.insert(format!("{}:{}", scheme, scope));
+            }
+        }
+    }
+    Some(conditions)
+}
+
+/// 展开文档内的 `$ref` 引用
+fn resolve(root: &Value, value: &Value, depth: usize) -> Value {
+    match value {
+        Value::Object(map) => {
+            if let Some(pointer) = map.get("$ref").and_then(Value::as_str).and_then(|r| r.strip_prefix('#')) {
+                if depth < MAX_REF_DEPTH {
+                    if let Some(target) = root.pointer(pointer) {
+                        return resolve(root, target, depth + 1);
+                    }
+                }
+                return value.clone();
+            }
+            Value::Object(map.iter().map(|(key, value)| (key.clone(), resolve(root, value, depth))).collect())
+        }
+        Value::Array(items) => Value::Array(items.iter().map(|item| resolve(root, item, depth)).collect()),
+        _ => value.clone(),
+    }
+}
+
+/// 比较同一端点的两个版本
+fn compare_endpoints(old: &Endpoint, new: &Endpoint, compare_conditions: bool, changes: &mut Vec<Change>) {
+    // 参数
+    for (key, old_param) in &old.params {
+        let name = format!("{} parameter `{}`", key.0, key.1);
+        match new.params.get(key) {
+            None => changes.push(new.change(ChangeKind::ParameterRemoved, false, format!("{} removed", name))),
+            Some(new_param) => {
+                if !old_param.required && new_param.required {
+                    changes.push(new.change(ChangeKind::ParameterChanged, true, format!("{} is now required", name)));
+                } else if old_param.required && !new_param.required {
+                    changes.push(new.change(ChangeKind::ParameterChanged, false, format!("{} is now optional", name)));
+                }
+                for (breaking, message) in compare_schema(&old_param.schema, &new_param.schema, Direction::Request) {
+                    changes.push(new.change(ChangeKind::ParameterChanged, breaking, format!("{}: {}", name, message)));
+                }
+            }
+        }
+    }
+    for (key, new_param) in &new.params {
+        if !old.params.contains_key(key) {
+            let required = if new_param.required { "required" } else { "optional" };
+            let message = format!("{} {} parameter `{}` added", required, key.0, key.1);
+            changes.push(new.change(ChangeKind::ParameterAdded, new_param.required, message));
+        }
+    }
+
+    // 请求体
+    match (&old.request_body, &new.request_body) {
+        (None, Some(body)) => {
+            let required = if body.required { "required" } else { "optional" };
+            changes.push(new.change(ChangeKind::RequestBodyChanged, body.required, format!("{} request body added", required)));
+        }
+        (Some(_), None) => changes.push(new.change(ChangeKind::RequestBodyChanged, false, "request body removed".to_string())),
+        (Some(old_body), Some(new_body)) => {
+            if !old_body.required && new_body.required {
+                changes.push(new.change(ChangeKind::RequestBodyChanged, true, "request body is now required".to_string()));
+            }
+            let empty = json!({});
+            let old_schema = old_body.schema.as_ref().unwrap_or(&empty);
+            let new_schema = new_body.schema.as_ref().unwrap_or(&empty);
+            for (breaking, message) in compare_schema(old_schema, new_schema, Direction::Request) {
+                changes.push(new.change(ChangeKind::RequestBodyChanged, breaking, format!("request body: {}", message)));
+            }
+        }
+        (None, None) => {}
+    }
+
+    // 响应
+    for (status, old_schema) in &old.responses {
+        match new.responses.get(status) {
+            // 删除成功响应意味着客户端依赖的状态码不再出现
+            None => {
+                let breaking = status.starts_with('2');
+                changes.push(new.change(ChangeKind::ResponseRemoved, breaking, format!("response {} removed", status)));
+            }
+            Some(new_schema) => match (old_schema, new_schema) {
+                (Some(_), None) => {
+                    let message = format!("response {}: body removed", status);
+                    changes.push(new.change(ChangeKind::ResponseSchemaChanged, true, message));
+                }
+                (None, Some(_)) => {
+                    let message = format!("response {}: body added", status);
+                    changes.push(new.change(ChangeKind::ResponseSchemaChanged, false, message));
+                }
+                (Some(old_schema), Some(new_schema)) => {
+                    for (breaking, message) in compare_schema(old_schema, new_schema, Direction::Response) {
+                        let message = format!("response {}: {}", status, message);
+                        changes.push(new.change(ChangeKind::ResponseSchemaChanged, breaking, message));
+                    }
+                }
+                (None, None) => {}
+            },
+        }
+    }
+    for status in new.responses.keys().filter(|status| !old.responses.contains_key(*status)) {
+        changes.push(new.change(ChangeKind::ResponseAdded, false, format!("response {} added", status)));
+    }
+
+    // 认证
+    match (&old.auth, &new.auth) {
+        (None, Some(_)) => {
+            changes.push(new.change(ChangeKind::AuthTightened, true, format!("authentication now required ({})", describe_auth(&new.auth))));
+        }
+        (Some(_), None) => {
+            changes.push(new.change(ChangeKind::AuthRelaxed, false, "authentication no longer required".to_string()));
+        }
+        (Some(old_conditions), Some(new_conditions)) if compare_conditions && old_conditions != new_conditions => {
+            let message = format!("auth policy changed from `{}` to `{}`", describe_auth(&old.auth), describe_auth(&new.auth));
+            if new_conditions.is_subset(old_conditions) {
+                changes.push(new.change(ChangeKind::AuthRelaxed, false, message));
+            } else {
+                changes.push(new.change(ChangeKind::AuthTightened, true, message));
+            }
+        }
+        _ => {}
+    }
+
+    if !old.deprecated && new.deprecated {
+        changes.push(new.change(ChangeKind::Deprecated, false, "endpoint deprecated".to_string()));
+    }
+}
+
+/// 认证条件的文本形式
+fn describe_auth(auth: &Option<BTreeSet<String>>) -> String {
+    match auth {
+        Some(conditions) if conditions.is_empty() => "any valid token".to_string(),
+        Some(conditions) => conditions.iter().cloned().collect::<Vec<_>>().join(", "),
+        None => "public".to_string(),
+    }
+}
+
+/// 数据流向：请求由客户端发送，响应由客户端读取
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    Request,
+    Response,
+}
+
+/// 比较两个Schema，返回（是否破坏性，说明）列表
+fn compare_schema(old: &Value, new: &Value, direction: Direction) -> Vec<(bool, String)> {
+    let mut changes = Vec::new();
+    compare_schema_at(old, new, direction, "", &mut changes);
+    changes
+}
+
+fn compare_schema_at(old: &Value, new: &Value, direction: Direction, path: &str, changes: &mut Vec<(bool, String)>) {
+    if old == new {
+        return;
+    }
+    let at = |message: String| {
+        if path.is_empty() {
+            message
+        } else {
+            format!("`{}` {}", path, message)
+        }
+    };
+    let child = |name: &str| {
+        if path.is_empty() {
+            name.to_string()
+        } else {
+            format!("{}.{}", path, name)
+        }
+    };
+    let (Some(old), Some(new)) = (old.as_object(), new.as_object()) else {
+        changes.push((true, at("schema changed".to_string())));
+        return;
+    };
+
+    if old.get("type") != new.get("type") {
+        let show = |ty: Option<&Value>| ty.map_or("any".to_string(), |ty| ty.to_string().replace('"', ""));
+        changes.push((true, at(format!("type changed from {} to {}", show(old.get("type")), show(new.get("type"))))));
+        return;
+    }
+
+    // 属性
+    let properties = |schema: &Map<String, Value>| schema.get("properties").and_then(Value::as_object).cloned().unwrap_or_default();
+    let required = |schema: &Map<String, Value>| -> BTreeSet<String> {
+        schema
+            .get("required")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+            .map(str::to_string)
+            .collect()
+    };
+    let (old_properties, new_properties) = (properties(old), properties(new));
+    let (old_required, new_required) = (required(old), required(new));
+    for (name, old_property) in &old_properties {
+        match new_properties.get(name) {
+            None => changes.push((direction == Direction::Response, format!("property `{}` removed", child(name)))),
+            Some(new_property) => compare_schema_at(old_property, new_property, direction, &child(name), changes),
+        }
+    }
+    for name in new_properties.keys().filter(|name| !old_properties.contains_key(*name)) {
+        let is_required = new_required.contains(name);
+        let message = format!("{} property `{}` added", if is_required { "required" } else { "optional" }, child(name));
+        changes.push((direction == Direction::Request && is_required, message));
+    }
+    for name in new_required.difference(&old_required).filter(|name| old_properties.contains_key(*name)) {
+        changes.push((direction == Direction::Request, format!("property `{}` is now required", child(name))));
+    }
+    for name in old_required.difference(&new_required).filter(|name| new_properties.contains_key(*name)) {
+        changes.push((direction == Direction::Response, format!("property `{}` is now optional", child(name))));
+    }
+
+    // 数组元素
+    match (old.get("items"), new.get("items")) {
+        (Some(old_items), Some(new_items)) => compare_schema_at(old_items, new_items, direction, &format!("{}[]", path), changes),
+        (None, None) => {}
+        _ => changes.push((true, at("items changed".to_string()))),
+    }
+
+    // 枚举值：请求中删除取值、响应中新增取值会破坏客户端
+    if old.get("enum") != new.get("enum") {
+        let values = |schema: &Map<String, Value>| -> Vec<Value> {
+            schema.get("enum").and_then(Value::as_array).cloned().unwrap_or_default()
+        };
+        let (old_values, new_values) = (values(old), values(new));
+        if old_values.is_empty() {
+            changes.push((direction == Direction::Request, at("enum constraint added".to_string())));
+        } else if new_values.is_empty() {
+            changes.push((direction == Direction::Response, at("enum constraint removed".to_string())));
+        } else {
+            let list = |values: Vec<&Value>| values.iter().map(|value| value.to_string()).collect::<Vec<_>>().join(", ");
+            let removed: Vec<&Value> = old_values.iter().filter(|value| !new_values.contains(value)).collect();
+            let added: Vec<&Value> = new_values.iter().filter(|value| !old_values.contains(value)).collect();
+            if !removed.is_empty() {
+                changes.push((direction == Direction::Request, at(format!("enum values removed: {}", list(removed)))));
+            }
+            if !added.is_empty() {
+                changes.push((direction == Direction::Response, at(format!("enum values added: {}", list(added)))));
+            }
+        }
+    }
+
+    // 其他约束（格式、范围等）的任何修改都按破坏性处理
+    let keywords: BTreeSet<&String> = old.keys().chain(new.keys()).collect();
+    for keyword in keywords {
+        let handled = ["type", "properties", "required", "items", "enum"].contains(&keyword.as_str())
+            || ANNOTATION_KEYWORDS.contains(&keyword.as_str());
+        if !handled && old.get(keyword) != new.get(keyword) {
+            changes.push((true, at(format!("`{}` changed", keyword))));
+        }
+    }
+}
@@ -0,0 +1,152 @@
+//! 功能开关模块，按开关状态在运行时启用或隐藏路由
+//!
+//! ```ignore
+//! #[uni_routing(route = "/api/billing", method = "GET", feature_flag = "new_billing")]
+//! async fn billing() {}
+//!
+//! let flags = Arc::new(EnvFeatureFlags::new()); // FEATURE_NEW_BILLING=1
+//! let chain = MiddlewareChain::new().with_feature_flags(flags.clone());
+//! let spec = OpenApiBuilder::new("API", "1.0.0").feature_flags(flags).registry(&registry).build();
+//! ```
+//!
+//! 中间件链对每个请求查询开关，关闭时返回 404；OpenAPI 文档在生成时查询开关，
+//! 不包含关闭的路由。没有配置开关提供者时，带开关的路由视为关闭。
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+use crate::routing::RouteInfo;
+use crate::Error;
+
+/// 功能开关提供者，未知的开关视为关闭
+pub trait FeatureFlagProvider: Send + Sync + fmt::Debug {
+    /// 开关是否打开
+    fn is_enabled(&self, flag: &str) -> bool;
+}
+
+/// 路由是否启用：未设置开关，或开关已打开
+pub fn is_route_enabled(route: &RouteInfo, provider: Option<&dyn FeatureFlagProvider>) -> bool {
+    match (&route.feature_flag, provider) {
+        (None, _) => true,
+        (Some(flag), Some(provider)) => provider.is_enabled(flag),
+        (Some(_), None) => false,
+    }
+}
+
+/// 在代码中配置的固定开关
+#[derive(Debug, Clone, Default)]
+pub struct StaticFeatureFlags {
+    flags: HashMap<String, bool>,
+}
+
+impl StaticFeatureFlags {
+    /// 创建不包含任何开关的提供者
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 打开开关
+    pub fn enable(self, flag: &str) -> Self {
+        self.set(flag, true)
+    }
+
+    /// 设置开关状态
+    pub fn set(mut self, flag: &str, enabled: bool) -> Self {
+        self.flags.insert(flag.to_string(), enabled);
+        self
+    }
+}
+
+impl FeatureFlagProvider for StaticFeatureFlags {
+    fn is_enabled(&self, flag: &str) -> bool {
+        self.flags.get(flag).copied().unwrap_or(false)
+    }
+}
+
+/// 从环境变量读取开关，`new_billing` 对应 `FEATURE_NEW_BILLING`
+///
+/// 值为 `1`、`true`、`yes` 或 `on`（不区分大小写）时开关打开，每次查询时重新读取。
+#[derive(Debug, Clone)]
+pub struct EnvFeatureFlags {
+    prefix: String,
+}
+
+impl EnvFeatureFlags {
+    /// 使用默认前缀 `FEATURE_`
+    pub fn new() -> Self {
+        Self::with_prefix("FEATURE_")
+    }
+
+    /// 使用自定义的变量名前缀
+    pub fn with_prefix(prefix: &str) -> Self {
+        Self {
+            prefix: prefix.to_string(),
+        }
+    }
+
+    /// 开关对应的环境变量名
+    pub fn variable(&self, flag: &str) -> String {
+        let name: String = flag
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_uppercase() } else { '_' })
+            .collect();
+        format!("{}{}", self.prefix, name)
+    }
+}
+
+impl Default for EnvFeatureFlags {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FeatureFlagProvider for EnvFeatureFlags {
+    fn is_enabled(&self, flag: &str) -> bool {
+        std::env::var(self.variable(flag)).is_ok_and(|value| {
+            matches!(value.trim().to_ascii_lowercase().as_str(), "1" | "true" | "yes" | "on")
+        })
+    }
+}
+
+/// 从 JSON 文件读取开关，文件内容形如 `{ "new_billing": true }`
+///
+/// 文件在创建时读取，调用 [`JsonFileFeatureFlags::reload`] 重新读取。
+#[derive(Debug)]
+pub struct JsonFileFeatureFlags {
+    path: PathBuf,
+    flags: RwLock<HashMap<String, bool>>,
+}
+
+impl JsonFileFeatureFlags {
+    /// 读取开关文件
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref().to_path_buf();
+        let flags = read_flags(&path)?;
+        Ok(Self {
+            path,
+            flags: RwLock::new(flags),
+        })
+    }
+
+    /// 重新读取开关文件，失败时保留当前的开关
+    pub fn reload(&self) -> Result<(), Error> {
+        let flags = read_flags(&self.path)?;
+        *self.flags.write().unwrap() = flags;
+        Ok(())
+    }
+}
+
+impl FeatureFlagProvider for JsonFileFeatureFlags {
+    fn is_enabled(&self, flag: &str) -> bool {
+        self.flags.read().unwrap().get(flag).copied().unwrap_or(false)
+    }
+}
+
+fn read_flags(path: &Path) -> Result<HashMap<String, bool>, Error> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| Error::Other(format!("Failed to read {}: {}", path.display(), e)))?;
+    serde_json::from_str(&content)
+        .map_err(|e| Error::Other(format!("Invalid feature flags in {}: {}", path.display(), e)))
+}
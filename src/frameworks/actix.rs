@@ -1,6 +1,21 @@
 //! Actix-web框架的路由注册器
 
-use actix_web::{web, App, HttpServer, HttpResponse, Result as ActixResult};
+use actix_web::body::EitherBody;
+use actix_web::dev::{Service, ServiceRequest, ServiceResponse, Transform};
+use actix_web::http::header::{HeaderName, HeaderValue};
+use actix_web::http::StatusCode;
+use actix_web::{web, App, Error as ActixError, HttpRequest, HttpServer, HttpResponse, Result as ActixResult};
+use std::future::{ready, Future, Ready};
+use std::pin::Pin;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use crate::group::RouteGroup;
+use crate::introspect::{self, Format, RouteRow};
+use crate::middleware::{MiddlewareChain, MiddlewareResponse};
+use crate::openapi::{OpenApi, OpenApiBuilder};
+use crate::registry::get_global_registry;
+use crate::routing::RouteInfo;
 
 /// 启动Actix-web服务器的便捷函数，自动配置所有使用uni_routing宏定义的路由
 pub async fn start_server() -> std::io::Result<()> {
@@ -54,7 +69,7 @@ async fn swagger_ui() -> ActixResult<HttpResponse> {
 <html>
 <head>
     <title>Uni Routing API Documentation</title>
-    <link rel="stylesheet" type="text/css" href="https://unpkg.com/swagger-ui-dist@4/swagger-ui.css" />
+    <link rel="stylesheet" type="text/css" href="https://unpkg.com/swagger-ui-dist@5/swagger-ui.css" />
     <style>
         html { box-sizing: border-box; overflow: -moz-scrollbars-vertical; overflow-y: scroll; }
         *, *:before, *:after { box-sizing: inherit; }
@@ -63,8 +78,8 @@ async fn swagger_ui() -> ActixResult<HttpResponse> {
 </head>
 <body>
     <div id="swagger-ui"></div>
-    <script src="https://unpkg.com/swagger-ui-dist@4/swagger-ui-bundle.js"></script>
-    <script src="https://unpkg.com/swagger-ui-dist@4/swagger-ui-standalone-preset.js"></script>
+    <script src="https://unpkg.com/swagger-ui-dist@5/swagger-ui-bundle.js"></script>
+    <script src="https://unpkg.com/swagger-ui-dist@5/swagger-ui-standalone-preset.js"></script>
     <script>
         window.onload = function() {
             const ui = SwaggerUIBundle({
@@ -93,7 +108,9 @@ async fn swagger_ui() -> ActixResult<HttpResponse> {
 
 /// OpenAPI规范端点
 async fn openapi_spec() -> ActixResult<HttpResponse> {
-    let spec = generate_openapi_docs();
+    let spec = OpenApiBuilder::new("Uni Routing API", "1.0.0")
+        .registry(&get_global_registry())
+        .build();
     Ok(HttpResponse::Ok().json(spec))
 }
 
@@ -142,15 +159,168 @@ async fn delete_user() -> ActixResult<HttpResponse> {
     })))
 }
 
-#[allow(dead_code)]
-fn generate_openapi_docs() -> serde_json::Value {
-    serde_json::json!({
-        "openapi": "3.0.0",
-        "info": {
-            "title": "Uni Routing API",
-            "version": "1.0.0",
-            "description": "API documentation generated by uni_routing"
-        },
-        "paths": {}
-    })
-}
\ No newline at end of file
+/// 以JSON输出OpenAPI文档的资源
+///
+/// ```ignore
+/// App::new().service(openapi_resource("/swagger/openapi.json", &spec))
+/// ```
+pub fn openapi_resource(path: &str, spec: &OpenApi) -> actix_web::Resource {
+    let body = spec.to_json_string();
+    web::resource(path).route(web::get().to(move || {
+        let body = body.clone();
+        async move {
+            HttpResponse::Ok()
+                .content_type("application/json")
+                .body(body)
+        }
+    }))
+}
+
+/// 输出路由表的管理路由，通过 `?format=table|json|markdown` 选择格式
+///
+/// ```ignore
+/// App::new().service(routes_resource("/_routes", &routes))
+/// ```
+pub fn routes_resource(path: &str, routes: &[RouteInfo]) -> actix_web::Resource {
+    let rows: Arc<Vec<RouteRow>> = Arc::new(introspect::rows(routes));
+    web::resource(path).route(web::get().to(move |req: HttpRequest| {
+        let rows = rows.clone();
+        async move {
+            match Format::from_query(Some(req.query_string())) {
+                Ok(format) => HttpResponse::Ok()
+                    .content_type(format.content_type())
+                    .body(introspect::render_rows(&rows, format)),
+                Err(e) => HttpResponse::BadRequest().body(e.to_string()),
+            }
+        }
+    }))
+}
+
+/// 以分组前缀创建 Actix-web `Scope`，分组内的处理函数使用相对路径注册
+///
+/// ```ignore
+/// App::new().service(scope(&users).route("/{id}", web::get().to(get_user)))
+/// ```
+pub fn scope(group: &RouteGroup) -> actix_web::Scope {
+    web::scope(group.prefix())
+}
+
+/// 将 [`MiddlewareChain`] 包装为 Actix-web 中间件
+///
+/// ```ignore
+/// App::new().wrap(UniMiddleware::new(chain))
+/// ```
+#[derive(Clone)]
+pub struct UniMiddleware {
+    chain: Arc<MiddlewareChain>,
+}
+
+impl UniMiddleware {
+    /// 使用中间件链创建Actix中间件
+    pub fn new(chain: MiddlewareChain) -> Self {
+        Self::from_arc(Arc::new(chain))
+    }
+
+    /// 使用共享的中间件链创建Actix中间件
+    pub fn from_arc(chain: Arc<MiddlewareChain>) -> Self {
+        Self { chain }
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for UniMiddleware
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = ActixError> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = ActixError;
+    type Transform = UniMiddlewareService<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(UniMiddlewareService {
+            service: Rc::new(service),
+            chain: self.chain.clone(),
+        }))
+    }
+}
+
+/// [`UniMiddleware`] 生成的服务
+pub struct UniMiddlewareService<S> {
+    service: Rc<S>,
+    chain: Arc<MiddlewareChain>,
+}
+
+impl<S, B> Service<ServiceRequest> for UniMiddlewareService<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = ActixError> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = ActixError;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>>>>;
+
+    actix_web::dev::forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = self.service.clone();
+        let chain = self.chain.clone();
+
+        Box::pin(async move {
+            let mut context = chain.build_context(
+                req.method().as_str(),
+                req.path(),
+                req.headers()
+                    .iter()
+                    .filter_map(|(name, value)| Some((name.as_str(), value.to_str().ok()?))),
+            );
+            context.client_ip = req.peer_addr().map(|addr| addr.ip().to_string());
+
+            if let Some(response) = chain.process_request(&mut context) {
+                let response = to_http_response(response);
+                return Ok(req.into_response(response).map_into_right_body());
+            }
+
+            // 没有显式 HEAD 路由时交给 GET 处理函数，响应体由 actix-http 按原始请求方法省略
+            let mut req = req;
+            if context.is_derived_head() {
+                req.head_mut().method = actix_web::http::Method::GET;
+            }
+
+            // 按请求头或媒体类型协商的版本分派到带版本前缀的路由
+            if context.path != req.path() {
+                let uri = match req.query_string() {
+                    "" => context.path.clone(),
+                    query => format!("{}?{}", context.path, query),
+                };
+                if let Ok(uri) = uri.parse::<actix_web::http::Uri>() {
+                    req.match_info_mut().get_mut().update(&uri);
+                    req.head_mut().uri = uri;
+                }
+            }
+
+            let mut res = service.call(req).await?;
+            let headers = chain.process_response(&mut context, res.status().as_u16());
+            append_headers(res.headers_mut(), headers);
+            Ok(res.map_into_left_body())
+        })
+    }
+}
+
+/// 将中间件响应转换为Actix响应
+fn to_http_response(response: MiddlewareResponse) -> HttpResponse {
+    let status = StatusCode::from_u16(response.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+    let mut http_response = HttpResponse::build(status).body(response.body);
+    append_headers(http_response.headers_mut(), response.headers);
+    http_response
+}
+
+/// 追加响应头，忽略无法表示为合法头部的条目
+fn append_headers(target: &mut actix_web::http::header::HeaderMap, headers: Vec<(String, String)>) {
+    for (name, value) in headers {
+        if let (Ok(name), Ok(value)) = (HeaderName::try_from(name), HeaderValue::try_from(value)) {
+            target.append(name, value);
+        }
+    }
+}
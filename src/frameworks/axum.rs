@@ -0,0 +1,193 @@
+//! Axum框架的路由注册器
+
+use axum::body::Body;
+use axum::extract::ConnectInfo;
+use axum::http::header::{HeaderName, HeaderValue};
+use axum::http::{HeaderMap, Request, StatusCode};
+use axum::response::Response;
+use std::future::Future;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tower_layer::Layer;
+use tower_service::Service;
+
+use crate::group::{template_with, RouteGroup};
+use crate::introspect::{self, Format};
+use crate::middleware::{MiddlewareChain, MiddlewareResponse};
+use crate::openapi::OpenApi;
+use crate::routing::RouteInfo;
+
+/// 以JSON输出OpenAPI文档的路由
+///
+/// ```ignore
+/// Router::new().route("/swagger/openapi.json", openapi_route(&spec))
+/// ```
+pub fn openapi_route<S>(spec: &OpenApi) -> axum::routing::MethodRouter<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    let body = spec.to_json_string();
+    axum::routing::get(move || async move {
+        ([(axum::http::header::CONTENT_TYPE, "application/json")], body)
+    })
+}
+
+/// 输出路由表的管理路由，通过 `?format=table|json|markdown` 选择格式
+///
+/// ```ignore
+/// Router::new().route("/_routes", routes_route(&routes))
+/// ```
+pub fn routes_route<S>(routes: &[RouteInfo]) -> axum::routing::MethodRouter<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    let rows = Arc::new(introspect::rows(routes));
+    axum::routing::get(move |uri: axum::http::Uri| async move {
+        match Format::from_query(uri.query()) {
+            Ok(format) => (
+                StatusCode::OK,
+                [(axum::http::header::CONTENT_TYPE, format.content_type())],
+                introspect::render_rows(&rows, format),
+            ),
+            Err(e) => (
+                StatusCode::BAD_REQUEST,
+                [(axum::http::header::CONTENT_TYPE, "text/plain; charset=utf-8")],
+                e.to_string(),
+            ),
+        }
+    })
+}
+
+/// 将分组内的路由嵌套到分组前缀下，根分组直接合并
+///
+/// ```ignore
+/// let app = nest(Router::new(), &users, Router::new().route("/:id", get(get_user)));
+/// ```
+pub fn nest<S>(router: axum::Router<S>, group: &RouteGroup, routes: axum::Router<S>) -> axum::Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    if group.prefix().is_empty() {
+        return router.merge(routes);
+    }
+    let prefix = template_with(group.prefix(), |name| format!(":{}", name));
+    router.nest(&prefix, routes)
+}
+
+/// 将 [`MiddlewareChain`] 包装为 tower `Layer`
+///
+/// ```ignore
+/// Router::new().layer(UniMiddlewareLayer::new(chain))
+/// ```
+///
+/// 使用请求头或媒体类型协商API版本时，需要在路由匹配之前改写请求路径，
+/// 应使用该 Layer 包装整个 Router：`UniMiddlewareLayer::new(chain).layer(router)`。
+#[derive(Clone)]
+pub struct UniMiddlewareLayer {
+    chain: Arc<MiddlewareChain>,
+}
+
+impl UniMiddlewareLayer {
+    /// 使用中间件链创建Layer
+    pub fn new(chain: MiddlewareChain) -> Self {
+        Self::from_arc(Arc::new(chain))
+    }
+
+    /// 使用共享的中间件链创建Layer
+    pub fn from_arc(chain: Arc<MiddlewareChain>) -> Self {
+        Self { chain }
+    }
+}
+
+impl<S> Layer<S> for UniMiddlewareLayer {
+    type Service = UniMiddlewareService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        UniMiddlewareService {
+            inner,
+            chain: self.chain.clone(),
+        }
+    }
+}
+
+/// [`UniMiddlewareLayer`] 生成的服务
+#[derive(Clone)]
+pub struct UniMiddlewareService<S> {
+    inner: S,
+    chain: Arc<MiddlewareChain>,
+}
+
+impl<S> Service<Request<Body>> for UniMiddlewareService<S>
+where
+    S: Service<Request<Body>, Response = Response> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: Request<Body>) -> Self::Future {
+        let chain = self.chain.clone();
+        let mut context = chain.build_context(
+            req.method().as_str(),
+            req.uri().path(),
+            req.headers()
+                .iter()
+                .filter_map(|(name, value)| Some((name.as_str(), value.to_str().ok()?))),
+        );
+        context.client_ip = req
+            .extensions()
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|ConnectInfo(addr)| addr.ip().to_string());
+
+        if let Some(response) = chain.process_request(&mut context) {
+            return Box::pin(async move { Ok(to_response(response)) });
+        }
+
+        // 按请求头或媒体类型协商的版本分派到带版本前缀的路由，
+        // 需要用该 Layer 包装整个 Router 才能在路由匹配之前生效
+        if context.path != req.uri().path() {
+            let uri = match req.uri().query() {
+                Some(query) => format!("{}?{}", context.path, query),
+                None => context.path.clone(),
+            };
+            if let Ok(uri) = uri.parse() {
+                *req.uri_mut() = uri;
+            }
+        }
+
+        // 按照 tower 的约定，使用已就绪的服务处理请求，并留下克隆供下次使用
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+
+        Box::pin(async move {
+            let mut res = inner.call(req).await?;
+            let headers = chain.process_response(&mut context, res.status().as_u16());
+            append_headers(res.headers_mut(), headers);
+            Ok(res)
+        })
+    }
+}
+
+/// 将中间件响应转换为Axum响应
+fn to_response(response: MiddlewareResponse) -> Response {
+    let mut res = Response::new(Body::from(response.body));
+    *res.status_mut() = StatusCode::from_u16(response.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+    append_headers(res.headers_mut(), response.headers);
+    res
+}
+
+/// 追加响应头，忽略无法表示为合法头部的条目
+fn append_headers(target: &mut HeaderMap, headers: Vec<(String, String)>) {
+    for (name, value) in headers {
+        if let (Ok(name), Ok(value)) = (HeaderName::try_from(name), HeaderValue::try_from(value)) {
+            target.append(name, value);
+        }
+    }
+}
@@ -0,0 +1,222 @@
+//! Rocket框架的路由注册器
+
+use rocket::fairing::{self, Fairing, Info, Kind};
+use rocket::http::uri::Origin;
+use rocket::http::{ContentType, Method, Status};
+use rocket::route::{Handler, Outcome, Route};
+use rocket::{Build, Data, Request, Response, Rocket};
+use std::io::Cursor;
+use std::sync::{Arc, Mutex};
+
+use crate::group::{template_with, RouteGroup};
+use crate::introspect::{self, Format, RouteRow};
+use crate::middleware::{MiddlewareChain, MiddlewareContext, MiddlewareResponse};
+use crate::openapi::OpenApi;
+use crate::routing::RouteInfo;
+
+/// 中间件提前返回时请求被改写到的内部路径
+const HALT_BASE: &str = "/__uni_routing";
+const HALT_PATH: &str = "/__uni_routing/halt";
+/// 内部路由挂载的方法
+const HALT_METHODS: &[Method] = &[
+    Method::Get,
+    Method::Head,
+    Method::Post,
+    Method::Put,
+    Method::Delete,
+    Method::Patch,
+    Method::Options,
+    Method::Trace,
+    Method::Connect,
+];
+
+/// 将 [`MiddlewareChain`] 包装为 Rocket fairing
+///
+/// Rocket 的 fairing 无法直接中断请求，因此当中间件需要提前返回时，
+/// 请求会被改写到内部路由，由该路由输出中间件生成的响应。
+///
+/// ```ignore
+/// rocket::build().attach(UniFairing::new(chain))
+/// ```
+#[derive(Clone)]
+pub struct UniFairing {
+    chain: Arc<MiddlewareChain>,
+}
+
+impl UniFairing {
+    /// 使用中间件链创建fairing
+    pub fn new(chain: MiddlewareChain) -> Self {
+        Self::from_arc(Arc::new(chain))
+    }
+
+    /// 使用共享的中间件链创建fairing
+    pub fn from_arc(chain: Arc<MiddlewareChain>) -> Self {
+        Self { chain }
+    }
+}
+
+/// 每个请求在 Rocket 本地缓存中保存的中间件状态
+#[derive(Default)]
+struct RequestState {
+    context: Mutex<Option<MiddlewareContext>>,
+    halted: Mutex<Option<MiddlewareResponse>>,
+}
+
+#[rocket::async_trait]
+impl Fairing for UniFairing {
+    fn info(&self) -> Info {
+        Info {
+            name: "uni_routing middleware chain",
+            kind: Kind::Ignite | Kind::Request | Kind::Response,
+        }
+    }
+
+    async fn on_ignite(&self, rocket: Rocket<Build>) -> fairing::Result {
+        // 保留原请求的方法，内部路由需要挂载到所有方法上
+        let routes = HALT_METHODS
+            .iter()
+            .map(|method| Route::new(*method, "/halt", HaltHandler))
+            .collect::<Vec<_>>();
+        Ok(rocket.mount(HALT_BASE, routes))
+    }
+
+    async fn on_request(&self, req: &mut Request<'_>, _data: &mut Data<'_>) {
+        let path = req.uri().path().to_string();
+        let headers: Vec<_> = req.headers().iter().collect();
+        let mut context = self.chain.build_context(
+            req.method().as_str(),
+            &path,
+            headers.iter().map(|header| (header.name().as_str(), header.value())),
+        );
+        context.client_ip = req.client_ip().map(|ip| ip.to_string());
+
+        let halted = self.chain.process_request(&mut context);
+        let context_path = context.path.clone();
+
+        // 按请求头或媒体类型协商的版本分派到带版本前缀的路由
+        if context_path != path {
+            let uri = match req.uri().query() {
+                Some(query) => format!("{}?{}", context_path, query),
+                None => context_path,
+            };
+            if let Ok(uri) = Origin::parse_owned(uri) {
+                req.set_uri(uri);
+            }
+        }
+
+        let state = req.local_cache(RequestState::default);
+        *state.context.lock().unwrap() = Some(context);
+
+        if let Some(response) = halted {
+            *state.halted.lock().unwrap() = Some(response);
+            req.set_uri(Origin::parse(HALT_PATH).expect("valid internal path"));
+        }
+    }
+
+    async fn on_response<'r>(&self, req: &'r Request<'_>, res: &mut Response<'r>) {
+        let state = req.local_cache(RequestState::default);
+        if req.uri().path() == HALT_PATH {
+            return;
+        }
+
+        let Some(mut context) = state.context.lock().unwrap().take() else {
+            return;
+        };
+        let headers = self.chain.process_response(&mut context, res.status().code);
+        for (name, value) in headers {
+            res.adjoin_raw_header(name, value);
+        }
+    }
+}
+
+/// 输出中间件提前返回的响应
+#[derive(Clone)]
+struct HaltHandler;
+
+#[rocket::async_trait]
+impl Handler for HaltHandler {
+    async fn handle<'r>(&self, req: &'r Request<'_>, _data: Data<'r>) -> Outcome<'r> {
+        let state = req.local_cache(RequestState::default);
+        let Some(halted) = state.halted.lock().unwrap().take() else {
+            return Outcome::Error(Status::NotFound);
+        };
+
+        let mut builder = Response::build();
+        builder
+            .status(Status::new(halted.status))
+            .sized_body(halted.body.len(), Cursor::new(halted.body));
+        for (name, value) in halted.headers {
+            builder.raw_header_adjoin(name, value);
+        }
+        Outcome::Success(builder.finalize())
+    }
+}
+
+/// 将分组内的路由挂载到分组前缀下
+///
+/// ```ignore
+/// let rocket = mount(rocket::build(), &users, routes![get_user]);
+/// ```
+pub fn mount(rocket: Rocket<Build>, group: &RouteGroup, routes: Vec<Route>) -> Rocket<Build> {
+    let base = template_with(group.prefix(), |name| format!("<{}>", name));
+    rocket.mount(base, routes)
+}
+
+/// 以JSON输出OpenAPI文档的路由
+///
+/// ```ignore
+/// rocket::build().mount("/", vec![openapi_route("/swagger/openapi.json", &spec)])
+/// ```
+pub fn openapi_route(path: &str, spec: &OpenApi) -> Route {
+    Route::new(Method::Get, path, OpenApiHandler { body: spec.to_json_string() })
+}
+
+/// 输出路由表的管理路由，通过 `?format=table|json|markdown` 选择格式
+///
+/// ```ignore
+/// rocket::build().mount("/", vec![routes_route("/_routes", &routes)])
+/// ```
+pub fn routes_route(path: &str, routes: &[RouteInfo]) -> Route {
+    Route::new(Method::Get, path, RoutesHandler { rows: Arc::new(introspect::rows(routes)) })
+}
+
+/// 输出路由表
+#[derive(Clone)]
+struct RoutesHandler {
+    rows: Arc<Vec<RouteRow>>,
+}
+
+#[rocket::async_trait]
+impl Handler for RoutesHandler {
+    async fn handle<'r>(&self, req: &'r Request<'_>, _data: Data<'r>) -> Outcome<'r> {
+        let (status, content_type, body) = match Format::from_query(req.uri().query().map(|query| query.as_str())) {
+            Ok(format) => (Status::Ok, format.content_type(), introspect::render_rows(&self.rows, format)),
+            Err(e) => (Status::BadRequest, "text/plain; charset=utf-8", e.to_string()),
+        };
+        let content_type = ContentType::parse_flexible(content_type).unwrap_or(ContentType::Plain);
+        let response = Response::build()
+            .status(status)
+            .header(content_type)
+            .sized_body(body.len(), Cursor::new(body))
+            .finalize();
+        Outcome::Success(response)
+    }
+}
+
+/// 输出OpenAPI文档
+#[derive(Clone)]
+struct OpenApiHandler {
+    body: String,
+}
+
+#[rocket::async_trait]
+impl Handler for OpenApiHandler {
+    async fn handle<'r>(&self, _req: &'r Request<'_>, _data: Data<'r>) -> Outcome<'r> {
+        let body = self.body.clone();
+        let response = Response::build()
+            .header(ContentType::JSON)
+            .sized_body(body.len(), Cursor::new(body))
+            .finalize();
+        Outcome::Success(response)
+    }
+}
@@ -0,0 +1,159 @@
+//! 路由分组模块，为一组路由提供共同的路径前缀、认证策略、标签和中间件
+
+use crate::auth::AuthPolicy;
+use crate::matcher::{RoutePattern, Segment};
+use crate::middleware::MiddlewareSpec;
+use crate::routing::{PathParam, PathSegment, RouteInfo};
+
+/// 路由分组
+///
+/// 分组内路由的路径相对于分组前缀；未设置认证策略的路由使用分组的默认策略，
+/// 分组的标签和中间件排在路由自身的标签和中间件之前。分组可以嵌套。
+///
+/// ```ignore
+/// let users = RouteGroup::new("/api/users")
+///     .auth_policy("role:admin")
+///     .tag("users")
+///     .routes(__register_routes_get_users())
+///     .routes(__register_routes_get_user_by_id());
+/// let routes = users.build();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RouteGroup {
+    prefix: String,
+    auth_policy: Option<AuthPolicy>,
+    tags: Vec<String>,
+    middleware: Vec<MiddlewareSpec>,
+    routes: Vec<RouteInfo>,
+    groups: Vec<RouteGroup>,
+}
+
+impl RouteGroup {
+    /// 使用路径前缀创建分组
+    pub fn new(prefix: &str) -> Self {
+        Self {
+            prefix: normalize_prefix(prefix),
+            ..Default::default()
+        }
+    }
+
+    /// 设置分组内路由的默认认证策略，由配置了路由表的 `MiddlewareChain` 在运行时认证
+    pub fn auth_policy(mut self, policy: &str) -> Self {
+        self.auth_policy = Some(AuthPolicy::new(policy));
+        self
+    }
+
+    /// 添加分组内路由共用的标签
+    pub fn tag(mut self, tag: &str) -> Self {
+        self.tags.push(tag.to_string());
+        self
+    }
+
+    /// 添加分组内路由共用的中间件
+    pub fn middleware(mut self, spec: MiddlewareSpec) -> Self {
+        self.middleware.push(spec);
+        self
+    }
+
+    /// 添加路由，路径相对于分组前缀
+    pub fn route(mut self, route: RouteInfo) -> Self {
+        self.routes.push(route);
+        self
+    }
+
+    /// 添加多个路由
+    pub fn routes(mut self, routes: impl IntoIterator<Item = RouteInfo>) -> Self {
+        self.routes.extend(routes);
+        self
+    }
+
+    /// 添加嵌套分组，其前缀相对于当前分组
+    pub fn group(mut self, group: RouteGroup) -> Self {
+        self.groups.push(group);
+        self
+    }
+
+    /// 分组的路径前缀，根分组为空字符串
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// 生成分组内所有路由，路径带上分组前缀并合并分组设置
+    pub fn build(&self) -> Vec<RouteInfo> {
+        let own = self.routes.iter().cloned();
+        let nested = self.groups.iter().flat_map(|group| group.build());
+        own.chain(nested).map(|route| self.apply(route)).collect()
+    }
+
+    /// 将分组设置应用到单个路由
+    ///
+    /// 前缀中的参数（如 `/orgs/{org}`）排在路由自身的路径参数之前，Schema由参数的类型约束决定。
+    fn apply(&self, mut route: RouteInfo) -> RouteInfo {
+        route.path = join_path(&self.prefix, &route.path);
+        if let Ok(prefix) = RoutePattern::parse(&self.prefix) {
+            let mut path_params: Vec<PathParam> = prefix
+                .segments()
+                .iter()
+                .filter_map(|segment| match segment {
+                    Segment::Param { name, ty, .. } => Some(PathParam {
+                        name: name.clone(),
+                        schema: ty.schema(),
+                    }),
+                    Segment::Static(_) | Segment::CatchAll(_) => None,
+                })
+                .filter(|param| !route.path_params.iter().any(|own| own.name == param.name))
+                .collect();
+            path_params.append(&mut route.path_params);
+            route.path_params = path_params;
+        }
+        if route.auth_policy.is_none() {
+            route.auth_policy = self.auth_policy.clone();
+        }
+
+        let mut tags = self.tags.clone();
+        tags.extend(route.tags.into_iter().filter(|tag| !self.tags.contains(tag)));
+        route.tags = tags;
+
+        let mut middleware = self.middleware.clone();
+        middleware.append(&mut route.middleware);
+        route.middleware = middleware;
+        route
+    }
+}
+
+/// 规范化前缀：以 `/` 开头、不以 `/` 结尾，根前缀为空字符串
+fn normalize_prefix(prefix: &str) -> String {
+    let trimmed = prefix.trim_matches('/');
+    if trimmed.is_empty() {
+        String::new()
+    } else {
+        format!("/{}", trimmed)
+    }
+}
+
+/// 拼接前缀与路由路径，`/` 表示前缀本身
+pub fn join_path(prefix: &str, path: &str) -> String {
+    let prefix = normalize_prefix(prefix);
+    let path = path.trim_start_matches('/');
+    match (prefix.is_empty(), path.is_empty()) {
+        (true, _) => format!("/{}", path),
+        (false, true) => prefix,
+        (false, false) => format!("{}/{}", prefix, path),
+    }
+}
+
+/// 将路由模板中的参数改写为指定框架的写法，例如 Axum 的 `:id` 或 Rocket 的 `<id>`
+pub fn template_with(path: &str, param: impl Fn(&str) -> String) -> String {
+    let rendered: String = PathSegment::parse_template(path)
+        .iter()
+        .map(|segment| match segment {
+            PathSegment::Static(value) => format!("/{}", value),
+            PathSegment::Param(name) => format!("/{}", param(name)),
+        })
+        .collect();
+    if rendered.is_empty() {
+        "/".to_string()
+    } else {
+        rendered
+    }
+}
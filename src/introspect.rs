@@ -0,0 +1,162 @@
+//! 路由自省模块，将路由表输出为表格、JSON 或 Markdown
+//!
+//! ```ignore
+//! println!("{}", introspect::render(&routes, Format::Table));
+//! ```
+
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::routing::RouteInfo;
+use crate::Error;
+
+/// 输出格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Format {
+    /// 对齐的纯文本表格
+    #[default]
+    Table,
+    /// JSON 数组，可作为 `uni-routing routes` 的输入
+    Json,
+    /// Markdown 表格
+    Markdown,
+}
+
+impl FromStr for Format {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "table" | "text" => Ok(Format::Table),
+            "json" => Ok(Format::Json),
+            "markdown" | "md" => Ok(Format::Markdown),
+            _ => Err(Error::Other(format!("Invalid introspection format: {}, expected table, json or markdown", s))),
+        }
+    }
+}
+
+impl Format {
+    /// 从查询字符串中读取 `format` 参数，未指定时使用表格
+    pub fn from_query(query: Option<&str>) -> Result<Self, Error> {
+        query
+            .unwrap_or_default()
+            .split('&')
+            .find_map(|pair| pair.strip_prefix("format="))
+            .map_or(Ok(Format::Table), str::parse)
+    }
+
+    /// 响应的 `Content-Type`
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Format::Table => "text/plain; charset=utf-8",
+            Format::Json => "application/json",
+            Format::Markdown => "text/markdown; charset=utf-8",
+        }
+    }
+}
+
+/// 路由表中的一行
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RouteRow {
+    /// HTTP方法
+    pub method: String,
+    /// 完整路径（包含版本前缀）
+    pub path: String,
+    /// 认证策略，公开路由为 `None`
+    pub auth_policy: Option<String>,
+    /// 处理函数，未记录来源时使用操作ID
+    pub handler: Option<String>,
+    /// 标签
+    pub tags: Vec<String>,
+}
+
+impl From<&RouteInfo> for RouteRow {
+    fn from(route: &RouteInfo) -> Self {
+        Self {
+            method: route.method.to_string(),
+            path: route.full_path(),
+            auth_policy: route.auth_policy.as_ref().map(|policy| policy.policy_expr.clone()),
+            handler: route
+                .source
+                .as_ref()
+                .map(|source| source.handler.clone())
+                .or_else(|| route.operation_id.clone()),
+            tags: route.tags.clone(),
+        }
+    }
+}
+
+/// 将路由表转换为按路径、方法排序的行
+pub fn rows(routes: &[RouteInfo]) -> Vec<RouteRow> {
+    let mut rows: Vec<RouteRow> = routes.iter().map(RouteRow::from).collect();
+    rows.sort_by(|a, b| (&a.path, &a.method).cmp(&(&b.path, &b.method)));
+    rows
+}
+
+/// 按指定格式输出路由表
+pub fn render(routes: &[RouteInfo], format: Format) -> String {
+    render_rows(&rows(routes), format)
+}
+
+/// 按指定格式输出已转换的行
+pub fn render_rows(rows: &[RouteRow], format: Format) -> String {
+    match format {
+        Format::Table => table(rows),
+        Format::Json => serde_json::to_string_pretty(rows).expect("route rows are always serializable"),
+        Format::Markdown => markdown(rows),
+    }
+}
+
+/// 表头
+const HEADERS: [&str; 5] = ["METHOD", "PATH", "AUTH", "HANDLER", "TAGS"];
+
+/// 一行中各列的文本
+fn cells(row: &RouteRow) -> [String; 5] {
+    [
+        row.method.clone(),
+        row.path.clone(),
+        row.auth_policy.clone().unwrap_or_else(|| "-".to_string()),
+        row.handler.clone().unwrap_or_else(|| "-".to_string()),
+        if row.tags.is_empty() { "-".to_string() } else { row.tags.join(",") },
+    ]
+}
+
+/// 对齐的纯文本表格
+fn table(rows: &[RouteRow]) -> String {
+    let lines: Vec<[String; 5]> = std::iter::once(HEADERS.map(str::to_string))
+        .chain(rows.iter().map(cells))
+        .collect();
+    let mut widths = [0; 5];
+    for line in &lines {
+        for (width, cell) in widths.iter_mut().zip(line) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    lines
+        .iter()
+        .map(|line| {
+            let padded: Vec<String> = line
+                .iter()
+                .zip(widths)
+                .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+                .collect();
+            padded.join("  ").trim_end().to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Markdown 表格，单元格中的 `|` 会被转义
+fn markdown(rows: &[RouteRow]) -> String {
+    let mut lines = vec![
+        "| Method | Path | Auth | Handler | Tags |".to_string(),
+        "| --- | --- | --- | --- | --- |".to_string(),
+    ];
+    for row in rows {
+        let cells = cells(row).map(|cell| format!("`{}`", cell.replace('|', "\\|")));
+        lines.push(format!("| {} |", cells.join(" | ")));
+    }
+    lines.join("\n")
+}
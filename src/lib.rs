@@ -9,8 +9,34 @@ pub mod routing;
 pub mod auth;
 /// 中间件模块
 pub mod middleware;
+/// 路由匹配模块
+pub mod matcher;
+/// 路由冲突检测模块
+pub mod conflict;
 /// 路由注册模块
 pub mod registry;
+/// 路由快照模块
+pub mod snapshot;
+/// 接口变更检测模块
+pub mod diff;
+/// 路由配置模块
+pub mod config;
+/// 配置热加载模块
+pub mod reload;
+/// 路由分组模块
+pub mod group;
+/// API版本模块
+pub mod versioning;
+/// 功能开关模块
+pub mod feature;
+/// 路由自省模块
+pub mod introspect;
+/// URL生成模块
+pub mod url;
+/// 类型Schema模块
+pub mod schema;
+/// OpenAPI文档模块
+pub mod openapi;
 
 #[cfg(feature = "swagger")]
 /// OpenAPI 文档生成模块
@@ -23,11 +49,18 @@ pub mod frameworks;
 /// 重新导出常用的类型
 pub use serde::{Deserialize, Serialize};
 
+#[doc(hidden)]
+pub use serde_json;
+
 /// 错误类型
 #[derive(Debug)]
 pub enum Error {
     /// 权限错误
     Unauthorized,
+    /// 令牌不满足认证策略
+    Forbidden,
+    /// 请求过于频繁，需等待指定秒数后重试
+    TooManyRequests { retry_after: u64 },
     /// 其他错误
     Other(String),
 }
@@ -36,6 +69,8 @@ impl std::fmt::Display for Error {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Error::Unauthorized => write!(f, "Unauthorized"),
+            Error::Forbidden => write!(f, "Forbidden"),
+            Error::TooManyRequests { .. } => write!(f, "Too Many Requests"),
             Error::Other(msg) => write!(f, "{}", msg),
         }
     }
@@ -0,0 +1,486 @@
+//! 路由匹配模块，将路由表编译为按路径段组织的前缀树
+//!
+//! 路由模板支持以下路径段：
+//!
+//! - 静态段：`/users`
+//! - 参数：`{id}` 或 Rocket 风格的 `<id>`
+//! - 带类型的参数：`{id:u64}`，只匹配能解析为该类型的路径段
+//! - 可选参数：`{page?}`，只能出现在模板末尾
+//! - 通配参数：`{*rest}` 或 `<rest..>`，匹配剩余的所有路径段，只能是最后一段
+//!
+//! 同一位置有多个候选时，优先级为：静态段 > 带类型的参数 > 参数 > 通配参数，
+//! 优先级高的分支匹配失败时回溯到下一个分支。
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock, RwLock};
+
+use crate::routing::RouteInfo;
+use crate::schema::{self, TypeSchema};
+use crate::Error;
+
+/// 路径参数的类型约束，模板中写作 `{id:u64}`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ParamType {
+    /// 任意非空路径段
+    Any,
+    U8,
+    U16,
+    U32,
+    U64,
+    Usize,
+    I8,
+    I16,
+    I32,
+    I64,
+    Isize,
+    F32,
+    F64,
+    Bool,
+    /// 带连字符的UUID，如 `67e55044-10b1-426f-9247-bb680e5fe0c8`
+    Uuid,
+}
+
+impl ParamType {
+    /// 根据模板中的类型名解析
+    ///
+    /// `#[uni_routing]` 宏在编译期通过该函数校验模板中的类型名，因此为 `const fn`。
+    pub const fn from_name(name: &str) -> Option<Self> {
+        const NAMES: &[(&str, ParamType)] = &[
+            ("str", ParamType::Any),
+            ("string", ParamType::Any),
+            ("String", ParamType::Any),
+            ("u8", ParamType::U8),
+            ("u16", ParamType::U16),
+            ("u32", ParamType::U32),
+            ("u64", ParamType::U64),
+            ("usize", ParamType::Usize),
+            ("i8", ParamType::I8),
+            ("i16", ParamType::I16),
+            ("i32", ParamType::I32),
+            ("i64", ParamType::I64),
+            ("isize", ParamType::Isize),
+            ("f32", ParamType::F32),
+            ("f64", ParamType::F64),
+            ("bool", ParamType::Bool),
+            ("uuid", ParamType::Uuid),
+            ("Uuid", ParamType::Uuid),
+        ];
+
+        let mut index = 0;
+        while index < NAMES.len() {
+            if const_str_eq(NAMES[index].0, name) {
+                return Some(NAMES[index].1);
+            }
+            index += 1;
+        }
+        None
+    }
+
+    /// 判断路径段是否满足类型约束
+    pub fn accepts(&self, value: &str) -> bool {
+        match self {
+            ParamType::Any => !value.is_empty(),
+            ParamType::U8 => value.parse::<u8>().is_ok(),
+            ParamType::U16 => value.parse::<u16>().is_ok(),
+            ParamType::U32 => value.parse::<u32>().is_ok(),
+            ParamType::U64 => value.parse::<u64>().is_ok(),
+            ParamType::Usize => value.parse::<usize>().is_ok(),
+            ParamType::I8 => value.parse::<i8>().is_ok(),
+            ParamType::I16 => value.parse::<i16>().is_ok(),
+            ParamType::I32 => value.parse::<i32>().is_ok(),
+            ParamType::I64 => value.parse::<i64>().is_ok(),
+            ParamType::Isize => value.parse::<isize>().is_ok(),
+            ParamType::F32 => value.parse::<f32>().is_ok(),
+            ParamType::F64 => value.parse::<f64>().is_ok(),
+            ParamType::Bool => value.parse::<bool>().is_ok(),
+            ParamType::Uuid => is_uuid(value),
+        }
+    }
+
+    /// 参数在 OpenAPI 文档中的Schema，与宏为同名Rust类型生成的Schema一致
+    pub fn schema(&self) -> TypeSchema {
+        match self {
+            ParamType::Any => schema::string(),
+            ParamType::U8 | ParamType::U16 | ParamType::U32 => schema::integer("int32", true),
+            ParamType::U64 | ParamType::Usize => schema::integer("int64", true),
+            ParamType::I8 | ParamType::I16 | ParamType::I32 => schema::integer("int32", false),
+            ParamType::I64 | ParamType::Isize => schema::integer("int64", false),
+            ParamType::F32 => schema::number("float"),
+            ParamType::F64 => schema::number("double"),
+            ParamType::Bool => schema::boolean(),
+            ParamType::Uuid => TypeSchema::inline(serde_json::json!({ "type": "string", "format": "uuid" })),
+        }
+    }
+}
+
+/// 编译期可用的字符串比较
+const fn const_str_eq(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut index = 0;
+    while index < a.len() {
+        if a[index] != b[index] {
+            return false;
+        }
+        index += 1;
+    }
+    true
+}
+
+/// 判断是否为 8-4-4-4-12 格式的UUID
+fn is_uuid(value: &str) -> bool {
+    let groups: Vec<&str> = value.split('-').collect();
+    groups.len() == 5
+        && groups.iter().zip([8, 4, 4, 4, 12]).all(|(group, len)| {
+            group.len() == len && group.bytes().all(|b| b.is_ascii_hexdigit())
+        })
+}
+
+/// 路由模板中的一个路径段
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Segment {
+    /// 静态路径段
+    Static(String),
+    /// 路径参数
+    Param {
+        /// 参数名
+        name: String,
+        /// 类型约束
+        ty: ParamType,
+        /// 是否可省略
+        optional: bool,
+    },
+    /// 通配参数，匹配剩余的所有路径段
+    CatchAll(String),
+}
+
+/// 解析后的路由模板
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RoutePattern {
+    segments: Vec<Segment>,
+}
+
+impl RoutePattern {
+    /// 解析路由模板，语法错误时返回错误
+    pub fn parse(template: &str) -> Result<Self, Error> {
+        let invalid = |message: String| Error::Other(format!("Invalid route template `{}`: {}", template, message));
+
+        let raw: Vec<&str> = template.trim_matches('/').split('/').filter(|s| !s.is_empty()).collect();
+        let mut segments = Vec::with_capacity(raw.len());
+        let mut names: Vec<String> = Vec::new();
+        for (index, value) in raw.iter().enumerate() {
+            let segment = parse_segment(value).map_err(invalid)?;
+            let is_last = index + 1 == raw.len();
+            let after_optional = matches!(segments.last(), Some(Segment::Param { optional: true, .. }));
+            match &segment {
+                Segment::CatchAll(_) if !is_last => {
+                    return Err(invalid(format!("catch-all segment `{}` must be the last segment", value)));
+                }
+                Segment::Param { optional: true, .. } => {}
+                _ if after_optional => {
+                    return Err(invalid("optional parameters may only be followed by optional parameters".to_string()));
+                }
+                _ => {}
+            }
+            if let Segment::Param { name, .. } | Segment::CatchAll(name) = &segment {
+                if names.contains(name) {
+                    return Err(invalid(format!("duplicate path parameter `{}`", name)));
+                }
+                names.push(name.clone());
+            }
+            segments.push(segment);
+        }
+        Ok(Self { segments })
+    }
+
+    /// 模板中的路径段
+    pub fn segments(&self) -> &[Segment] {
+        &self.segments
+    }
+
+    /// 模板中的参数名，按出现顺序排列
+    pub fn param_names(&self) -> impl Iterator<Item = &str> {
+        self.segments.iter().filter_map(|segment| match segment {
+            Segment::Static(_) => None,
+            Segment::Param { name, .. } | Segment::CatchAll(name) => Some(name.as_str()),
+        })
+    }
+
+    /// 用该模板匹配请求路径，成功时返回捕获的参数
+    pub fn captures(&self, path: &str) -> Option<Vec<(String, String)>> {
+        let parts = split_path(path);
+        let mut values = Vec::new();
+        if !match_segments(&self.segments, &parts, &mut values) {
+            return None;
+        }
+        Some(self.param_names().map(str::to_string).zip(values).collect())
+    }
+}
+
+/// 路由模板缓存的最大条目数，超出时清空
+const PATTERN_CACHE_CAPACITY: usize = 4096;
+
+/// 解析路由模板并缓存结果，同一模板只解析一次，模板无效时返回 `None`
+pub(crate) fn cached_pattern(template: &str) -> Option<Arc<RoutePattern>> {
+    static CACHE: OnceLock<RwLock<HashMap<String, Option<Arc<RoutePattern>>>>> = OnceLock::new();
+    let cache = CACHE.get_or_init(Default::default);
+    if let Some(pattern) = cache.read().unwrap().get(template) {
+        return pattern.clone();
+    }
+
+    let pattern = RoutePattern::parse(template).ok().map(Arc::new);
+    let mut cache = cache.write().unwrap();
+    if cache.len() >= PATTERN_CACHE_CAPACITY {
+        cache.clear();
+    }
+    cache.insert(template.to_string(), pattern.clone());
+    pattern
+}
+
+/// 解析单个路径段
+pub(crate) fn parse_segment(value: &str) -> Result<Segment, String> {
+    let braced = value.strip_prefix('{').and_then(|rest| rest.strip_suffix('}'));
+    let angled = value.strip_prefix('<').and_then(|rest| rest.strip_suffix('>'));
+
+    let segment = match (braced, angled) {
+        (Some(inner), _) => {
+            if let Some(name) = inner.strip_prefix('*') {
+                Segment::CatchAll(name.to_string())
+            } else {
+                let (inner, optional) = match inner.strip_suffix('?') {
+                    Some(inner) => (inner, true),
+                    None => (inner, false),
+                };
+                let (name, ty) = match inner.split_once(':') {
+                    Some((name, ty)) => {
+                        let ty = ParamType::from_name(ty)
+                            .ok_or_else(|| format!("unknown parameter type `{}` in `{}`", ty, value))?;
+                        (name, ty)
+                    }
+                    None => (inner, ParamType::Any),
+                };
+                Segment::Param { name: name.to_string(), ty, optional }
+            }
+        }
+        (None, Some(inner)) => match inner.strip_suffix("..") {
+            Some(name) => Segment::CatchAll(name.to_string()),
+            None => Segment::Param { name: inner.to_string(), ty: ParamType::Any, optional: false },
+        },
+        (None, None) => {
+            if value.contains(['{', '}', '<', '>']) {
+                return Err(format!("malformed path segment `{}`", value));
+            }
+            return Ok(Segment::Static(value.to_string()));
+        }
+    };
+
+    let (Segment::Param { name, .. } | Segment::CatchAll(name)) = &segment else {
+        unreachable!("parameter segments are handled above");
+    };
+    let valid = name.chars().next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !valid {
+        return Err(format!("invalid path parameter name in `{}`", value));
+    }
+    Ok(segment)
+}
+
+/// 将请求路径拆分为非空路径段
+fn split_path(path: &str) -> Vec<&str> {
+    path.trim_matches('/').split('/').filter(|segment| !segment.is_empty()).collect()
+}
+
+/// 逐段匹配单个模板
+fn match_segments(segments: &[Segment], parts: &[&str], values: &mut Vec<String>) -> bool {
+    let Some((segment, rest)) = segments.split_first() else {
+        return parts.is_empty();
+    };
+    match segment {
+        Segment::Static(expected) => {
+            parts.first() == Some(&expected.as_str()) && match_segments(rest, &parts[1..], values)
+        }
+        Segment::Param { ty, optional, .. } => {
+            if let Some(value) = parts.first().filter(|value| ty.accepts(value)) {
+                values.push(value.to_string());
+                if match_segments(rest, &parts[1..], values) {
+                    return true;
+                }
+                values.pop();
+            }
+            // 可选参数之后只有可选参数，省略时剩余参数一并省略
+            *optional && parts.is_empty()
+        }
+        Segment::CatchAll(_) => {
+            values.push(parts.join("/"));
+            true
+        }
+    }
+}
+
+/// 前缀树节点
+#[derive(Debug, Default)]
+struct Node {
+    /// 静态子节点
+    statics: HashMap<String, Node>,
+    /// 参数子节点，带类型的参数排在 [`ParamType::Any`] 之前
+    params: Vec<(ParamType, Node)>,
+    /// 在该节点以通配参数结束的路由
+    catch_all: Vec<usize>,
+    /// 在该节点结束的路由
+    routes: Vec<usize>,
+}
+
+impl Node {
+    /// 插入路由模板
+    fn insert(&mut self, segments: &[Segment], index: usize) {
+        let Some((segment, rest)) = segments.split_first() else {
+            self.routes.push(index);
+            return;
+        };
+        match segment {
+            Segment::Static(value) => self.statics.entry(value.clone()).or_default().insert(rest, index),
+            Segment::Param { ty, optional, .. } => {
+                if *optional {
+                    self.routes.push(index);
+                }
+                self.param_child(*ty).insert(rest, index);
+            }
+            Segment::CatchAll(_) => self.catch_all.push(index),
+        }
+    }
+
+    /// 获取或创建参数子节点
+    fn param_child(&mut self, ty: ParamType) -> &mut Node {
+        let position = match self.params.iter().position(|(existing, _)| *existing == ty) {
+            Some(position) => position,
+            None => {
+                let position = match ty {
+                    ParamType::Any => self.params.len(),
+                    _ => self.params.iter().position(|(existing, _)| *existing == ParamType::Any).unwrap_or(self.params.len()),
+                };
+                self.params.insert(position, (ty, Node::default()));
+                position
+            }
+        };
+        &mut self.params[position].1
+    }
+
+    /// 按优先级查找路由，`accept` 用于筛选方法
+    fn search(&self, parts: &[&str], values: &mut Vec<String>, accept: &dyn Fn(usize) -> bool) -> Option<usize> {
+        match parts.split_first() {
+            None => {
+                if let Some(&index) = self.routes.iter().find(|&&index| accept(index)) {
+                    return Some(index);
+                }
+            }
+            Some((first, rest)) => {
+                if let Some(index) = self.statics.get(*first).and_then(|child| child.search(rest, values, accept)) {
+                    return Some(index);
+                }
+                for (ty, child) in &self.params {
+                    if !ty.accepts(first) {
+                        continue;
+                    }
+                    values.push(first.to_string());
+                    if let Some(index) = child.search(rest, values, accept) {
+                        return Some(index);
+                    }
+                    values.pop();
+                }
+            }
+        }
+
+        let index = *self.catch_all.iter().find(|&&index| accept(index))?;
+        values.push(parts.join("/"));
+        Some(index)
+    }
+}
+
+/// 匹配结果
+#[derive(Debug, Clone)]
+pub struct RouteMatch<'a> {
+    /// 匹配到的路由
+    pub route: &'a RouteInfo,
+    /// 捕获的路径参数，按模板中的顺序排列，省略的可选参数不出现
+    pub params: Vec<(String, String)>,
+}
+
+impl RouteMatch<'_> {
+    /// 获取捕获的参数值
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params.iter().find(|(key, _)| key == name).map(|(_, value)| value.as_str())
+    }
+}
+
+/// 编译后的路由匹配器
+#[derive(Debug, Default)]
+pub struct RouteMatcher {
+    routes: Vec<RouteInfo>,
+    patterns: Vec<RoutePattern>,
+    root: Node,
+}
+
+impl RouteMatcher {
+    /// 编译路由表，路由模板（含版本前缀）语法错误时返回错误
+    pub fn new(routes: impl IntoIterator<Item = RouteInfo>) -> Result<Self, Error> {
+        let mut matcher = Self::default();
+        for route in routes {
+            let pattern = RoutePattern::parse(&route.full_path())?;
+            matcher.root.insert(pattern.segments(), matcher.routes.len());
+            matcher.routes.push(route);
+            matcher.patterns.push(pattern);
+        }
+        Ok(matcher)
+    }
+
+    /// 编译后的路由表
+    pub fn routes(&self) -> &[RouteInfo] {
+        &self.routes
+    }
+
+    /// 查找与请求匹配的路由
+    ///
+    /// 同一路径有多个路由时按注册顺序选择第一个方法匹配的路由；
+    /// 没有显式注册 HEAD 路由时，HEAD 请求由同一路径上的 GET 路由处理。
+    pub fn find(&self, method: &str, path: &str) -> Option<RouteMatch<'_>> {
+        self.find_method(method, path).or_else(|| {
+            if method.eq_ignore_ascii_case("HEAD") {
+                self.find_method("GET", path)
+            } else {
+                None
+            }
+        })
+    }
+
+    /// 能匹配请求路径的所有路由（任意方法），按注册顺序排列
+    pub fn matching(&self, path: &str) -> Vec<&RouteInfo> {
+        let parts = split_path(path);
+        let found = RefCell::new(Vec::new());
+        // 拒绝所有候选路由，使搜索遍历每个可能匹配的分支
+        let accept = |index: usize| {
+            found.borrow_mut().push(index);
+            false
+        };
+        self.root.search(&parts, &mut Vec::new(), &accept);
+        let mut indices = found.into_inner();
+        indices.sort_unstable();
+        indices.dedup();
+        indices.into_iter().map(|index| &self.routes[index]).collect()
+    }
+
+    /// 查找指定方法的路由
+    fn find_method(&self, method: &str, path: &str) -> Option<RouteMatch<'_>> {
+        let parts = split_path(path);
+        let mut values = Vec::new();
+        let accept = |index: usize| self.routes[index].matches_method(method);
+        let index = self.root.search(&parts, &mut values, &accept)?;
+        let names = self.patterns[index].param_names().map(str::to_string);
+        Some(RouteMatch {
+            route: &self.routes[index],
+            params: names.zip(values).collect(),
+        })
+    }
+}
@@ -0,0 +1,258 @@
+//! CORS中间件，处理跨域请求头和预检请求
+
+use std::sync::Arc;
+
+use regex::Regex;
+
+use super::{Middleware, MiddlewareContext, MiddlewareResponse};
+use crate::matcher::{RouteMatcher, RoutePattern};
+use crate::registry::RouteRegistry;
+use crate::routing::{HttpMethod, RouteInfo};
+use crate::Error;
+
+/// 允许的来源
+#[derive(Debug, Clone)]
+pub enum AllowedOrigin {
+    /// 任意来源
+    Any,
+    /// 完全匹配的来源
+    Exact(String),
+    /// 正则匹配的来源
+    Pattern(Regex),
+}
+
+impl AllowedOrigin {
+    /// 解析来源配置，`*` 表示任意来源，包含 `*` 的来源按通配符匹配
+    ///
+    /// 例如 `https://*.example.com` 匹配 `https://api.example.com`。
+    pub fn parse(origin: &str) -> Self {
+        if origin == "*" {
+            return AllowedOrigin::Any;
+        }
+        if !origin.contains('*') {
+            return AllowedOrigin::Exact(origin.to_string());
+        }
+
+        let pattern = origin
+            .split('*')
+            .map(regex::escape)
+            .collect::<Vec<_>>()
+            .join("[^/]*");
+        let regex = Regex::new(&format!("^{}$", pattern)).expect("escaped wildcard pattern is valid");
+        AllowedOrigin::Pattern(regex)
+    }
+
+    /// 判断来源是否被允许
+    pub fn allows(&self, origin: &str) -> bool {
+        match self {
+            AllowedOrigin::Any => true,
+            AllowedOrigin::Exact(expected) => expected.eq_ignore_ascii_case(origin),
+            AllowedOrigin::Pattern(regex) => regex.is_match(origin),
+        }
+    }
+}
+
+/// CORS中间件
+///
+/// 对携带 `Origin` 头的请求添加 `Access-Control-*` 响应头，并直接应答
+/// `OPTIONS` 预检请求。配置了路由表后，预检响应中的
+/// `Access-Control-Allow-Methods` 使用该路径上注册的方法。
+#[derive(Debug, Clone)]
+pub struct CorsMiddleware {
+    origins: Vec<AllowedOrigin>,
+    methods: Vec<String>,
+    headers: Vec<String>,
+    any_header: bool,
+    exposed_headers: Vec<String>,
+    allow_credentials: bool,
+    max_age: Option<u64>,
+    /// 编译后的路由表，预检请求按路径查找注册的方法
+    routes: Option<Arc<RouteMatcher>>,
+}
+
+impl CorsMiddleware {
+    /// 创建不允许任何来源的CORS中间件
+    pub fn new() -> Self {
+        Self {
+            origins: Vec::new(),
+            methods: ["GET", "POST", "PUT", "DELETE", "PATCH"]
+                .iter()
+                .map(|method| method.to_string())
+                .collect(),
+            headers: Vec::new(),
+            any_header: false,
+            exposed_headers: Vec::new(),
+            allow_credentials: false,
+            max_age: None,
+            routes: None,
+        }
+    }
+
+    /// 添加允许的来源，支持 `*` 和通配符
+    pub fn allow_origin(mut self, origin: &str) -> Self {
+        self.origins.push(AllowedOrigin::parse(origin));
+        self
+    }
+
+    /// 添加按正则匹配的允许来源
+    pub fn allow_origin_regex(mut self, regex: Regex) -> Self {
+        self.origins.push(AllowedOrigin::Pattern(regex));
+        self
+    }
+
+    /// 设置未配置路由表时预检响应中允许的方法
+    pub fn allow_methods(mut self, methods: &[&str]) -> Self {
+        self.methods = methods.iter().map(|method| method.to_uppercase()).collect();
+        self
+    }
+
+    /// 设置允许的请求头，未设置时只允许浏览器默认放行的简单请求头
+    pub fn allow_headers(mut self, headers: &[&str]) -> Self {
+        self.headers = headers.iter().map(|header| header.to_string()).collect();
+        self
+    }
+
+    /// 允许任意请求头：预检响应回显 `Access-Control-Request-Headers`
+    pub fn allow_any_header(mut self) -> Self {
+        self.any_header = true;
+        self
+    }
+
+    /// 设置允许浏览器读取的响应头
+    pub fn expose_headers(mut self, headers: &[&str]) -> Self {
+        self.exposed_headers = headers.iter().map(|header| header.to_string()).collect();
+        self
+    }
+
+    /// 设置是否允许携带凭据，只对 `*` 以外显式配置的来源生效
+    pub fn allow_credentials(mut self, allow: bool) -> Self {
+        self.allow_credentials = allow;
+        self
+    }
+
+    /// 设置预检结果的缓存时间（秒）
+    pub fn max_age(mut self, seconds: u64) -> Self {
+        self.max_age = Some(seconds);
+        self
+    }
+
+    /// 使用路由表应答预检请求，路由表在此时编译一次，模板无效的路由被忽略
+    pub fn with_routes(mut self, routes: Vec<RouteInfo>) -> Self {
+        let valid = routes.into_iter().filter(|route| RoutePattern::parse(&route.full_path()).is_ok());
+        self.routes = RouteMatcher::new(valid).ok().map(Arc::new);
+        self
+    }
+
+    /// 使用路由注册表中的所有路由应答预检请求
+    pub fn with_registry(self, registry: &RouteRegistry) -> Self {
+        self.with_routes(registry.get_routes())
+    }
+
+    /// 判断来源是否被允许
+    pub fn is_origin_allowed(&self, origin: &str) -> bool {
+        self.origins.iter().any(|allowed| allowed.allows(origin))
+    }
+
+    /// 生成 `Access-Control-Allow-Origin` 等公共响应头
+    ///
+    /// 只被 `*` 允许的来源总是得到 `*` 且不携带凭据，凭据只对显式配置的来源生效。
+    fn origin_headers(&self, origin: &str) -> Vec<(String, String)> {
+        let any = self.origins.iter().any(|allowed| matches!(allowed, AllowedOrigin::Any));
+        let credentials = self.allow_credentials
+            && self
+                .origins
+                .iter()
+                .any(|allowed| !matches!(allowed, AllowedOrigin::Any) && allowed.allows(origin));
+        let mut headers = Vec::new();
+        if any && !credentials {
+            headers.push(("Access-Control-Allow-Origin".to_string(), "*".to_string()));
+        } else {
+            headers.push(("Access-Control-Allow-Origin".to_string(), origin.to_string()));
+            headers.push(("Vary".to_string(), "Origin".to_string()));
+        }
+        if credentials {
+            headers.push(("Access-Control-Allow-Credentials".to_string(), "true".to_string()));
+        }
+        headers
+    }
+
+    /// 预检响应中允许的方法，路径未注册时返回 `None`
+    fn preflight_methods(&self, path: &str) -> Option<Vec<String>> {
+        let Some(matcher) = self.routes.as_ref().filter(|matcher| !matcher.routes().is_empty()) else {
+            return Some(self.methods.clone());
+        };
+
+        let mut methods: Vec<String> = Vec::new();
+        for route in matcher.matching(path) {
+            // GET 路由同时处理 HEAD 请求
+            let derived = (route.method == HttpMethod::GET).then(|| HttpMethod::HEAD.to_string());
+            for method in std::iter::once(route.method.to_string()).chain(derived) {
+                if !methods.contains(&method) {
+                    methods.push(method);
+                }
+            }
+        }
+        (!methods.is_empty()).then_some(methods)
+    }
+
+    /// 生成预检响应
+    fn preflight(&self, context: &MiddlewareContext, origin: &str) -> Option<MiddlewareResponse> {
+        let methods = self.preflight_methods(&context.path)?;
+
+        let mut response = MiddlewareResponse::new(204);
+        response.headers = self.origin_headers(origin);
+        response.headers.push(("Access-Control-Allow-Methods".to_string(), methods.join(", ")));
+
+        let allowed_headers = if self.any_header {
+            context.header("access-control-request-headers").map(str::to_string)
+        } else {
+            (!self.headers.is_empty()).then(|| self.headers.join(", "))
+        };
+        if let Some(allowed_headers) = allowed_headers {
+            response.headers.push(("Access-Control-Allow-Headers".to_string(), allowed_headers));
+        }
+        if let Some(max_age) = self.max_age {
+            response.headers.push(("Access-Control-Max-Age".to_string(), max_age.to_string()));
+        }
+        Some(response)
+    }
+}
+
+impl Default for CorsMiddleware {
+    /// 允许任意来源、不携带凭据的宽松配置
+    fn default() -> Self {
+        Self::new().allow_origin("*")
+    }
+}
+
+impl Middleware for CorsMiddleware {
+    fn handle(&self, context: &mut MiddlewareContext) -> Result<(), Error> {
+        let Some(origin) = context.header("origin").map(str::to_string) else {
+            return Ok(());
+        };
+
+        let is_preflight = context.method.eq_ignore_ascii_case("OPTIONS")
+            && context.header("access-control-request-method").is_some();
+
+        if !self.is_origin_allowed(&origin) {
+            if is_preflight {
+                context.response = Some(MiddlewareResponse::new(403));
+            }
+            return Ok(());
+        }
+
+        if is_preflight {
+            context.response = self.preflight(context, &origin);
+            return Ok(());
+        }
+
+        context.response_headers.extend(self.origin_headers(&origin));
+        if !self.exposed_headers.is_empty() {
+            context.response_headers.push((
+                "Access-Control-Expose-Headers".to_string(),
+                self.exposed_headers.join(", "),
+            ));
+        }
+        Ok(())
+    }
+}
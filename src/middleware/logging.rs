@@ -0,0 +1,198 @@
+//! 日志中间件，输出包含耗时、状态码和请求ID的结构化访问日志
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::{Middleware, MiddlewareContext};
+use crate::Error;
+
+/// 访问日志使用的 `log` 目标
+pub const LOG_TARGET: &str = "uni_routing::access";
+
+/// 默认的请求ID头
+pub const REQUEST_ID_HEADER: &str = "X-Request-Id";
+
+/// 日志输出格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LogFormat {
+    /// 单行 `key=value` 文本
+    #[default]
+    Plain,
+    /// 单行JSON
+    Json,
+    /// Common Log Format
+    Common,
+}
+
+impl std::str::FromStr for LogFormat {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "plain" => Ok(LogFormat::Plain),
+            "json" => Ok(LogFormat::Json),
+            "common" | "clf" => Ok(LogFormat::Common),
+            _ => Err(Error::Other(format!("Unknown log format: {}", s))),
+        }
+    }
+}
+
+/// 日志中间件
+///
+/// 请求阶段从请求头读取请求ID（不存在时生成一个）并回写到响应头，
+/// 响应阶段以 `info` 级别输出一条访问日志。
+#[derive(Debug, Clone)]
+pub struct LoggingMiddleware {
+    format: LogFormat,
+    request_id_header: String,
+}
+
+impl LoggingMiddleware {
+    /// 使用指定格式创建日志中间件
+    pub fn new(format: LogFormat) -> Self {
+        Self {
+            format,
+            request_id_header: REQUEST_ID_HEADER.to_string(),
+        }
+    }
+
+    /// 设置读取和回写请求ID的请求头
+    pub fn request_id_header(mut self, header: &str) -> Self {
+        self.request_id_header = header.to_string();
+        self
+    }
+
+    /// 按配置的格式生成一条访问日志
+    pub fn format_entry(&self, context: &MiddlewareContext, status: u16) -> String {
+        let latency_ms = context.started_at.elapsed().as_secs_f64() * 1000.0;
+        let route = context.route.as_ref().map(|route| route.full_path());
+        let route = route.as_deref();
+        let user = context
+            .token
+            .as_ref()
+            .and_then(|token| token.payload.get("sub"))
+            .and_then(|sub| sub.as_str());
+        let request_id = context.request_id.as_deref();
+
+        match self.format {
+            LogFormat::Plain => format!(
+                "{} {} {} {:.3}ms route={} user={} request_id={}",
+                context.method,
+                context.path,
+                status,
+                latency_ms,
+                route.unwrap_or("-"),
+                user.unwrap_or("-"),
+                request_id.unwrap_or("-"),
+            ),
+            LogFormat::Json => serde_json::json!({
+                "timestamp": rfc3339_timestamp(SystemTime::now()),
+                "request_id": request_id,
+                "method": context.method,
+                "path": context.path,
+                "route": route,
+                "status": status,
+                "latency_ms": latency_ms,
+                "user": user,
+                "client_ip": context.client_ip,
+            })
+            .to_string(),
+            LogFormat::Common => format!(
+                "{} - {} [{}] \"{} {} HTTP/1.1\" {} -",
+                context.client_ip.as_deref().unwrap_or("-"),
+                user.unwrap_or("-"),
+                clf_timestamp(SystemTime::now()),
+                context.method,
+                context.path,
+                status,
+            ),
+        }
+    }
+}
+
+impl Default for LoggingMiddleware {
+    fn default() -> Self {
+        Self::new(LogFormat::default())
+    }
+}
+
+impl Middleware for LoggingMiddleware {
+    fn handle(&self, context: &mut MiddlewareContext) -> Result<(), Error> {
+        let request_id = context
+            .header(&self.request_id_header)
+            .map(str::to_string)
+            .unwrap_or_else(generate_request_id);
+        context
+            .response_headers
+            .push((self.request_id_header.clone(), request_id.clone()));
+        context.request_id = Some(request_id);
+        Ok(())
+    }
+
+    fn on_response(&self, context: &mut MiddlewareContext, status: u16) {
+        log::info!(target: LOG_TARGET, "{}", self.format_entry(context, status));
+    }
+}
+
+/// 生成进程内唯一的请求ID
+fn generate_request_id() -> String {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_nanos() as u64)
+        .unwrap_or_default();
+    let sequence = COUNTER.fetch_add(1, Ordering::Relaxed);
+    format!("{:016x}-{:08x}", nanos, sequence)
+}
+
+/// 按 Common Log Format 格式化UTC时间，例如 `10/Oct/2000:13:55:36 +0000`
+fn clf_timestamp(time: SystemTime) -> String {
+    const MONTHS: [&str; 12] = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ];
+
+    let (year, month, day, secs_of_day) = utc_date(time);
+    format!(
+        "{:02}/{}/{}:{:02}:{:02}:{:02} +0000",
+        day,
+        MONTHS[(month - 1) as usize],
+        year,
+        secs_of_day / 3_600,
+        secs_of_day % 3_600 / 60,
+        secs_of_day % 60,
+    )
+}
+
+/// 按 RFC 3339 格式化UTC时间，例如 `2000-10-10T13:55:36.123Z`
+fn rfc3339_timestamp(time: SystemTime) -> String {
+    let millis = time.duration_since(UNIX_EPOCH).map(|d| d.subsec_millis()).unwrap_or_default();
+    let (year, month, day, secs_of_day) = utc_date(time);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        year,
+        month,
+        day,
+        secs_of_day / 3_600,
+        secs_of_day % 3_600 / 60,
+        secs_of_day % 60,
+        millis,
+    )
+}
+
+/// 将时间拆分为UTC的年、月、日和当天的秒数
+fn utc_date(time: SystemTime) -> (i64, i64, i64, u64) {
+    let secs = time.duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or_default();
+    let (days, secs_of_day) = ((secs / 86_400) as i64, secs % 86_400);
+
+    // 将自1970-01-01起的天数转换为公历日期
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day, secs_of_day)
+}
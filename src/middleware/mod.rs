@@ -0,0 +1,539 @@
+//! 中间件模块，提供统一的中间件处理机制
+
+use crate::auth::{AuthPolicy, JwtToken};
+use crate::config::{AppliedConfig, RemappedRoute};
+use crate::feature::{self, FeatureFlagProvider};
+use crate::matcher::{RouteMatcher, RoutePattern, Segment};
+use crate::routing::{HttpMethod, RouteInfo};
+use crate::versioning::Versioning;
+use crate::Error;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::Instant;
+
+/// CORS中间件
+pub mod cors;
+/// 日志中间件
+pub mod logging;
+/// 限流中间件
+pub mod rate_limit;
+
+pub use cors::CorsMiddleware;
+pub use logging::{LogFormat, LoggingMiddleware};
+pub use rate_limit::RateLimitMiddleware;
+
+/// 中间件特征
+pub trait Middleware: Send + Sync {
+    /// 处理请求
+    fn handle(&self, context: &mut MiddlewareContext) -> Result<(), Error>;
+
+    /// 处理响应，在处理函数返回后按注册的逆序调用
+    fn on_response(&self, _context: &mut MiddlewareContext, _status: u16) {}
+}
+
+/// 中间件上下文
+pub struct MiddlewareContext {
+    /// JWT令牌（如果已认证）
+    pub token: Option<JwtToken>,
+    /// 请求路径
+    pub path: String,
+    /// HTTP方法
+    pub method: String,
+    /// 请求头（键为小写）
+    pub headers: HashMap<String, String>,
+    /// 需要追加到响应中的头
+    pub response_headers: Vec<(String, String)>,
+    /// 提前返回的响应，设置后不再调用后续中间件和处理函数
+    pub response: Option<MiddlewareResponse>,
+    /// 请求开始时间
+    pub started_at: Instant,
+    /// 匹配到的路由（如果中间件链配置了路由表）
+    pub route: Option<RouteInfo>,
+    /// 客户端IP地址（由框架适配器填充）
+    pub client_ip: Option<String>,
+    /// 请求ID
+    pub request_id: Option<String>,
+}
+
+impl MiddlewareContext {
+    /// 创建新的中间件上下文
+    pub fn new(method: &str, path: &str) -> Self {
+        Self {
+            token: None,
+            path: path.to_string(),
+            method: method.to_string(),
+            headers: HashMap::new(),
+            response_headers: Vec::new(),
+            response: None,
+            started_at: Instant::now(),
+            route: None,
+            client_ip: None,
+            request_id: None,
+        }
+    }
+
+    /// 获取请求头（不区分大小写）
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(&name.to_ascii_lowercase()).map(String::as_str)
+    }
+
+    /// 获取 `Authorization: Bearer` 中的令牌字符串
+    pub fn bearer_token(&self) -> Option<&str> {
+        self.header("authorization")
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .map(str::trim)
+    }
+
+    /// HEAD 请求是否由 GET 路由处理（没有显式注册 HEAD 路由）
+    pub fn is_derived_head(&self) -> bool {
+        self.method.eq_ignore_ascii_case("HEAD")
+            && self.route.as_ref().is_some_and(|route| route.method == HttpMethod::GET)
+    }
+}
+
+impl Default for MiddlewareContext {
+    fn default() -> Self {
+        Self::new("GET", "/")
+    }
+}
+
+/// 由中间件直接生成的响应
+#[derive(Debug, Clone, PartialEq)]
+pub struct MiddlewareResponse {
+    /// HTTP状态码
+    pub status: u16,
+    /// 响应头
+    pub headers: Vec<(String, String)>,
+    /// 响应体
+    pub body: String,
+}
+
+impl MiddlewareResponse {
+    /// 创建指定状态码的空响应
+    pub fn new(status: u16) -> Self {
+        Self {
+            status,
+            headers: Vec::new(),
+            body: String::new(),
+        }
+    }
+
+    /// 404 响应，用于被禁用的路由
+    pub fn not_found() -> Self {
+        Self {
+            status: 404,
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: serde_json::json!({ "error": "Not Found" }).to_string(),
+        }
+    }
+
+    /// 根据错误生成响应
+    pub fn from_error(error: &Error) -> Self {
+        let mut headers = vec![("Content-Type".to_string(), "application/json".to_string())];
+        let status = match error {
+            Error::Unauthorized => 401,
+            Error::Forbidden => 403,
+            Error::TooManyRequests { retry_after } => {
+                headers.push(("Retry-After".to_string(), retry_after.to_string()));
+                429
+            }
+            Error::Other(_) => 500,
+        };
+        Self {
+            status,
+            headers,
+            body: serde_json::json!({ "error": error.to_string() }).to_string(),
+        }
+    }
+}
+
+/// 路由级中间件声明，由 `#[uni_routing(middleware = [...])]` 生成
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MiddlewareSpec {
+    /// 中间件名称
+    pub name: String,
+    /// 中间件参数
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<String>,
+}
+
+impl MiddlewareSpec {
+    /// 创建新的中间件声明
+    pub fn new(name: &str, args: Vec<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            args,
+        }
+    }
+}
+
+/// 中间件构造函数，根据声明中的参数创建中间件
+pub type MiddlewareBuilder = Arc<dyn Fn(&[String]) -> Result<Arc<dyn Middleware>, Error> + Send + Sync>;
+
+/// 中间件工厂，将 [`MiddlewareSpec`] 中的名称解析为中间件实例
+#[derive(Clone)]
+pub struct MiddlewareFactory {
+    builders: HashMap<String, MiddlewareBuilder>,
+}
+
+impl MiddlewareFactory {
+    /// 创建不包含任何中间件的工厂
+    pub fn empty() -> Self {
+        Self {
+            builders: HashMap::new(),
+        }
+    }
+
+    /// 注册中间件构造函数
+    pub fn register(mut self, name: &str, builder: MiddlewareBuilder) -> Self {
+        self.builders.insert(name.to_string(), builder);
+        self
+    }
+
+    /// 根据声明创建中间件
+    pub fn build(&self, spec: &MiddlewareSpec) -> Result<Arc<dyn Middleware>, Error> {
+        let builder = self.builders.get(&spec.name).ok_or_else(|| {
+            Error::Other(format!("Unknown middleware: {}", spec.name))
+        })?;
+        builder(&spec.args)
+    }
+}
+
+impl Default for MiddlewareFactory {
+    /// 包含内置中间件 `logging("<format>")`、`cors`、`auth("<policy>")` 与
+    /// `rate_limit("<limit>", "<key>")` 的工厂
+    fn default() -> Self {
+        Self::empty()
+            .register("logging", Arc::new(|args| {
+                let format = match args.first() {
+                    Some(format) => format.parse()?,
+                    None => LogFormat::default(),
+                };
+                Ok(Arc::new(LoggingMiddleware::new(format)) as Arc<dyn Middleware>)
+            }))
+            .register("cors", Arc::new(|_| Ok(Arc::new(CorsMiddleware::default()) as Arc<dyn Middleware>)))
+            .register("auth", Arc::new(|args| {
+                let policy = args.first().map(String::as_str).unwrap_or_default();
+                Ok(Arc::new(AuthMiddleware::new(AuthPolicy::new(policy))) as Arc<dyn Middleware>)
+            }))
+            .register("rate_limit", Arc::new(|args| {
+                let limit = args
+                    .first()
+                    .ok_or_else(|| Error::Other("rate_limit requires a limit such as \"100/min\"".to_string()))?
+                    .parse()?;
+                let key = match args.get(1) {
+                    Some(key) => key.parse()?,
+                    None => rate_limit::RateLimitKey::default(),
+                };
+                Ok(Arc::new(RateLimitMiddleware::new(limit).key(key)) as Arc<dyn Middleware>)
+            }))
+    }
+}
+
+/// 令牌解码函数，将 Bearer 令牌转换为 [`JwtToken`]
+pub type TokenDecoder = Arc<dyn Fn(&str) -> Option<JwtToken> + Send + Sync>;
+
+/// 路由及其路由级中间件
+struct RouteMiddlewares {
+    route: RouteInfo,
+    middlewares: Vec<Arc<dyn Middleware>>,
+}
+
+/// 路由表，热加载时整体替换
+#[derive(Default)]
+struct RouteTable {
+    routes: Vec<RouteMiddlewares>,
+    matcher: RouteMatcher,
+    remapped: Vec<RemappedRoute>,
+    /// 被禁用的路由和重映射前的路径，请求返回 404
+    blocked: RouteMatcher,
+}
+
+impl RouteTable {
+    /// 构建路由表，中间件声明未变的路由沿用 `previous` 中的中间件实例（如限流计数）
+    fn build(applied: &AppliedConfig, factory: &MiddlewareFactory, previous: Option<&RouteTable>) -> Result<Self, Error> {
+        let mut routes = Vec::with_capacity(applied.routes.len());
+        for route in &applied.routes {
+            let reused = previous.and_then(|previous| {
+                previous.routes.iter().find(|entry| {
+                    entry.route.method == route.method
+                        && entry.route.full_path() == route.full_path()
+                        && entry.route.middleware == route.middleware
+                })
+            });
+            let middlewares = match reused {
+                Some(entry) => entry.middlewares.clone(),
+                None => route
+                    .middleware
+                    .iter()
+                    .map(|spec| factory.build(spec))
+                    .collect::<Result<Vec<_>, _>>()?,
+            };
+            routes.push(RouteMiddlewares { route: route.clone(), middlewares });
+        }
+        let blocked = applied
+            .disabled
+            .iter()
+            .chain(applied.remapped.iter().map(|remapped| &remapped.original))
+            .cloned();
+        Ok(Self {
+            matcher: RouteMatcher::new(routes.iter().map(|entry| entry.route.clone()))?,
+            routes,
+            remapped: applied.remapped.clone(),
+            blocked: RouteMatcher::new(blocked)?,
+        })
+    }
+}
+
+/// 中间件链
+pub struct MiddlewareChain {
+    pub middlewares: Vec<Arc<dyn Middleware>>,
+    token_decoder: Option<TokenDecoder>,
+    table: RwLock<Arc<RouteTable>>,
+    versioning: Option<Versioning>,
+    feature_flags: Option<Arc<dyn FeatureFlagProvider>>,
+}
+
+impl MiddlewareChain {
+    /// 创建新的中间件链
+    pub fn new() -> Self {
+        Self {
+            middlewares: Vec::new(),
+            token_decoder: None,
+            table: RwLock::default(),
+            versioning: None,
+            feature_flags: None,
+        }
+    }
+
+    /// 添加中间件到链中
+    pub fn add_middleware(mut self, middleware: Arc<dyn Middleware>) -> Self {
+        self.middlewares.push(middleware);
+        self
+    }
+
+    /// 设置令牌解码函数，框架适配器用它从 `Authorization` 头填充 `token`
+    pub fn with_token_decoder(mut self, decoder: TokenDecoder) -> Self {
+        self.token_decoder = Some(decoder);
+        self
+    }
+
+    /// 设置API版本协商方式
+    ///
+    /// 使用请求头或媒体类型协商时，请求被分派到所请求版本的路由，
+    /// `context.path` 为改写后的路径，框架适配器据此改写请求。
+    pub fn with_versioning(mut self, versioning: Versioning) -> Self {
+        self.versioning = Some(versioning);
+        self
+    }
+
+    /// 设置功能开关提供者，每个请求都会查询所匹配路由的开关，关闭时返回 404
+    ///
+    /// 未设置时带 `feature_flag` 的路由一律返回 404。
+    pub fn with_feature_flags(mut self, provider: Arc<dyn FeatureFlagProvider>) -> Self {
+        self.feature_flags = Some(provider);
+        self
+    }
+
+    /// 设置路由表，请求匹配到路由后会在全局中间件之后执行该路由声明的中间件
+    ///
+    /// 路由声明了未知的中间件或路由模板无效时返回错误。
+    pub fn with_routes(self, routes: Vec<RouteInfo>, factory: &MiddlewareFactory) -> Result<Self, Error> {
+        self.with_config(&AppliedConfig { routes, ..Default::default() }, factory)
+    }
+
+    /// 使用应用覆盖配置后的路由表，等同于 [`MiddlewareChain::with_routes`]，并且：
+    ///
+    /// - 被禁用的路由返回 404
+    /// - 重映射的路由在新路径上匹配，`context.path` 改写为处理函数挂载的原路径，原路径返回 404
+    pub fn with_config(self, applied: &AppliedConfig, factory: &MiddlewareFactory) -> Result<Self, Error> {
+        self.reload(applied, factory)?;
+        Ok(self)
+    }
+
+    /// 在运行中替换路由表
+    ///
+    /// 新路由表完整构建成功后才原子地替换旧表，出错时旧表保持不变。中间件声明未变的路由
+    /// 沿用原有的中间件实例，限流计数等状态不会因重新加载而重置。
+    pub fn reload(&self, applied: &AppliedConfig, factory: &MiddlewareFactory) -> Result<(), Error> {
+        let current = self.table();
+        let table = RouteTable::build(applied, factory, Some(&current))?;
+        *self.table.write().unwrap() = Arc::new(table);
+        Ok(())
+    }
+
+    /// 当前路由表
+    fn table(&self) -> Arc<RouteTable> {
+        self.table.read().unwrap().clone()
+    }
+
+    /// 当前生效的路由
+    pub fn routes(&self) -> Vec<RouteInfo> {
+        self.table().routes.iter().map(|entry| entry.route.clone()).collect()
+    }
+
+    /// 执行中间件链，上下文中已有响应（如被禁用的路由）时不执行任何中间件
+    ///
+    /// 全局中间件之后、路由级中间件之前按所匹配路由当前生效的 `auth_policy` 认证，
+    /// 缺少令牌返回 401，令牌不满足策略返回 403。全局中间件（如 CORS 预检）提前返回时不认证。
+    pub fn execute(&self, context: &mut MiddlewareContext) -> Result<(), Error> {
+        if context.response.is_some() {
+            return Ok(());
+        }
+        let middlewares: Vec<_> = self.iter_for(context).collect();
+        let (global, route) = middlewares.split_at(self.middlewares.len());
+        if Self::handle_all(global, context)? {
+            return Ok(());
+        }
+        if let Some(policy) = context.route.as_ref().and_then(|route| route.auth_policy.as_ref()) {
+            policy.authorize(context.token.as_ref())?;
+        }
+        Self::handle_all(route, context)?;
+        Ok(())
+    }
+
+    /// 依次执行中间件，某个中间件设置了响应时停止并返回 `true`
+    fn handle_all(middlewares: &[Arc<dyn Middleware>], context: &mut MiddlewareContext) -> Result<bool, Error> {
+        for middleware in middlewares {
+            middleware.handle(context)?;
+            if context.response.is_some() {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// 返回适用于该请求的中间件：全局中间件在前，路由级中间件在后
+    fn iter_for(&self, context: &MiddlewareContext) -> impl DoubleEndedIterator<Item = Arc<dyn Middleware>> + '_ {
+        let table = self.table();
+        let route_middlewares = context
+            .route
+            .as_ref()
+            .and_then(|route| {
+                table.routes.iter().find(|entry| {
+                    entry.route.method == route.method
+                        && entry.route.path == route.path
+                        && entry.route.version == route.version
+                })
+            })
+            .map(|entry| entry.middlewares.clone())
+            .unwrap_or_default();
+        self.middlewares.iter().cloned().chain(route_middlewares)
+    }
+
+    /// 根据框架请求的各部分构建上下文
+    pub fn build_context<'a, I>(&self, method: &str, path: &str, headers: I) -> MiddlewareContext
+    where
+        I: IntoIterator<Item = (&'a str, &'a str)>,
+    {
+        let mut context = MiddlewareContext::new(method, path);
+        for (name, value) in headers {
+            context.headers.insert(name.to_ascii_lowercase(), value.to_string());
+        }
+        if let Some(decoder) = &self.token_decoder {
+            context.token = context.bearer_token().and_then(|token| decoder(token));
+        }
+        // 按请求的版本分派，只改写到声明了该版本的路由，否则保持原路径
+        let table = self.table();
+        let version = self.versioning.as_ref().and_then(|versioning| {
+            let version = versioning.requested_version(context.headers.iter().map(|(k, v)| (k.as_str(), v.as_str())))?;
+            Some((versioning.dispatch_path(path, &version), version))
+        });
+        if let Some((dispatched, version)) = version {
+            let found = table.matcher.find(method, &dispatched);
+            if found.is_some_and(|found| found.route.version.as_deref() == Some(version.as_str())) {
+                context.path = dispatched;
+            }
+        }
+
+        match table.matcher.find(method, &context.path) {
+            Some(found) if !feature::is_route_enabled(found.route, self.feature_flags.as_deref()) => {
+                context.response = Some(MiddlewareResponse::not_found());
+            }
+            Some(found) => {
+                // 重映射的路由改写到处理函数挂载的原路径
+                let remapped = table.remapped.iter().find(|remapped| {
+                    remapped.route.method == found.route.method && remapped.route.full_path() == found.route.full_path()
+                });
+                if let Some(remapped) = remapped {
+                    context.path = mount_path(&remapped.original, &found.params);
+                }
+                context.route = Some(found.route.clone());
+            }
+            None if table.blocked.find(method, &context.path).is_some() => {
+                context.response = Some(MiddlewareResponse::not_found());
+            }
+            None => {}
+        }
+        context
+    }
+
+    /// 请求阶段：执行中间件链，返回需要直接响应的结果（如有）
+    ///
+    /// 提前返回时会立即执行响应阶段，其追加的响应头合并到返回的响应中。
+    pub fn process_request(&self, context: &mut MiddlewareContext) -> Option<MiddlewareResponse> {
+        let mut response = match self.execute(context) {
+            Ok(()) => context.response.take()?,
+            Err(error) => MiddlewareResponse::from_error(&error),
+        };
+        let headers = self.process_response(context, response.status);
+        response.headers.extend(headers);
+        Some(response)
+    }
+
+    /// 响应阶段：按逆序通知中间件，返回需要追加到响应中的头
+    pub fn process_response(&self, context: &mut MiddlewareContext, status: u16) -> Vec<(String, String)> {
+        for middleware in self.iter_for(context).rev() {
+            middleware.on_response(context, status);
+        }
+        std::mem::take(&mut context.response_headers)
+    }
+}
+
+/// 将匹配到的参数代入路由的原模板，省略缺失的可选参数
+fn mount_path(route: &RouteInfo, params: &[(String, String)]) -> String {
+    let param = |name: &str| params.iter().find(|(key, _)| key == name).map(|(_, value)| value.as_str());
+    let Ok(pattern) = RoutePattern::parse(&route.full_path()) else {
+        return route.full_path();
+    };
+    let path: String = pattern
+        .segments()
+        .iter()
+        .filter_map(|segment| match segment {
+            Segment::Static(value) => Some(format!("/{}", value)),
+            Segment::Param { name, .. } | Segment::CatchAll(name) => {
+                param(name).filter(|value| !value.is_empty()).map(|value| format!("/{}", value))
+            }
+        })
+        .collect();
+    if path.is_empty() {
+        "/".to_string()
+    } else {
+        path
+    }
+}
+
+impl Default for MiddlewareChain {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 认证中间件
+#[derive(Clone)]
+pub struct AuthMiddleware {
+    policy: AuthPolicy,
+}
+
+impl AuthMiddleware {
+    pub fn new(policy: AuthPolicy) -> Self {
+        Self { policy }
+    }
+}
+
+impl Middleware for AuthMiddleware {
+    fn handle(&self, context: &mut MiddlewareContext) -> Result<(), Error> {
+        self.policy.authorize(context.token.as_ref())
+    }
+}
@@ -0,0 +1,302 @@
+//! 限流中间件，基于令牌桶按客户端IP、用户、API Key或租户限制请求频率
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use super::{Middleware, MiddlewareContext};
+use crate::Error;
+
+/// 限流配额，例如 `100/min` 表示每分钟100个请求
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    /// 时间窗口内允许的请求数
+    pub limit: u64,
+    /// 时间窗口
+    pub window: Duration,
+}
+
+impl RateLimit {
+    /// 创建新的限流配额
+    pub fn new(limit: u64, window: Duration) -> Self {
+        Self { limit, window }
+    }
+}
+
+impl std::str::FromStr for RateLimit {
+    type Err = Error;
+
+    /// 解析 `<次数>/<窗口>`，窗口支持 `s`、`min`、`h`、`day` 等单位，可带倍数如 `30s`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || Error::Other(format!("Invalid rate limit: {}", s));
+
+        let (limit, window) = s.split_once('/').ok_or_else(invalid)?;
+        let limit: u64 = limit.trim().parse().map_err(|_| invalid())?;
+
+        let window = window.trim();
+        let unit_start = window.find(|c: char| !c.is_ascii_digit()).ok_or_else(invalid)?;
+        let count: u64 = if unit_start == 0 {
+            1
+        } else {
+            window[..unit_start].parse().map_err(|_| invalid())?
+        };
+        let unit_secs = match &window[unit_start..] {
+            "s" | "sec" | "second" | "seconds" => 1,
+            "m" | "min" | "minute" | "minutes" => 60,
+            "h" | "hour" | "hours" => 3_600,
+            "d" | "day" | "days" => 86_400,
+            _ => return Err(invalid()),
+        };
+
+        if limit == 0 || count == 0 {
+            return Err(invalid());
+        }
+        Ok(Self::new(limit, Duration::from_secs(count * unit_secs)))
+    }
+}
+
+/// 限流键，决定哪些请求共享同一个配额
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum RateLimitKey {
+    /// 客户端IP地址
+    #[default]
+    ClientIp,
+    /// JWT令牌中的 `sub`
+    Subject,
+    /// 指定请求头中的API Key
+    ///
+    /// 该值未经认证，客户端更换请求头即可获得新的配额，
+    /// 只应在网关等上游已校验API Key时使用。
+    ApiKey(String),
+    /// JWT令牌中的租户声明
+    Tenant(String),
+}
+
+impl RateLimitKey {
+    /// 从请求上下文中提取限流键，缺失时回退到客户端IP
+    pub fn extract(&self, context: &MiddlewareContext) -> String {
+        let claim = |name: &str| {
+            context
+                .token
+                .as_ref()
+                .and_then(|token| token.payload.get(name))
+                .and_then(|value| value.as_str())
+                .map(str::to_string)
+        };
+
+        let key = match self {
+            RateLimitKey::ClientIp => None,
+            RateLimitKey::Subject => claim("sub").map(|sub| format!("sub:{}", sub)),
+            RateLimitKey::ApiKey(header) => context.header(header).map(|key| format!("key:{}", key)),
+            RateLimitKey::Tenant(name) => claim(name).map(|tenant| format!("tenant:{}", tenant)),
+        };
+        key.unwrap_or_else(|| format!("ip:{}", context.client_ip.as_deref().unwrap_or("unknown")))
+    }
+}
+
+impl std::str::FromStr for RateLimitKey {
+    type Err = Error;
+
+    /// 解析 `ip`、`subject`、`api_key:<header>` 或 `tenant:<claim>`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            None if s == "ip" => Ok(RateLimitKey::ClientIp),
+            None if s == "subject" || s == "user" => Ok(RateLimitKey::Subject),
+            None if s == "api_key" => Ok(RateLimitKey::ApiKey("x-api-key".to_string())),
+            None if s == "tenant" => Ok(RateLimitKey::Tenant("tenant".to_string())),
+            Some(("api_key", header)) => Ok(RateLimitKey::ApiKey(header.to_string())),
+            Some(("tenant", claim)) => Ok(RateLimitKey::Tenant(claim.to_string())),
+            _ => Err(Error::Other(format!("Unknown rate limit key: {}", s))),
+        }
+    }
+}
+
+/// 一次配额检查的结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimitDecision {
+    /// 是否放行
+    pub allowed: bool,
+    /// 配额上限
+    pub limit: u64,
+    /// 剩余请求数
+    pub remaining: u64,
+    /// 放行时为配额完全恢复的时间，拒绝时为可重试的时间
+    pub reset_after: Duration,
+}
+
+/// 限流状态存储
+pub trait RateLimitStore: Send + Sync {
+    /// 为指定键消耗一个请求配额
+    fn acquire(&self, key: &str, limit: &RateLimit) -> RateLimitDecision;
+}
+
+/// 令牌桶状态
+struct Bucket {
+    tokens: f64,
+    updated_at: Instant,
+    /// 令牌补满的时间，之后删除该桶与保留它等价
+    full_at: Instant,
+}
+
+/// 内存存储默认最多保存的限流键数量
+pub const DEFAULT_MAX_KEYS: usize = 100_000;
+
+/// 基于内存的令牌桶存储，适用于单实例部署
+///
+/// 键的数量达到上限时先删除已补满的桶；仍没有足够空间时成批删除最久未使用的桶，
+/// 被删除的键下次请求时获得完整配额。
+pub struct InMemoryRateLimitStore {
+    buckets: Mutex<HashMap<String, Bucket>>,
+    max_keys: usize,
+}
+
+impl InMemoryRateLimitStore {
+    /// 创建新的内存存储
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 设置最多保存的限流键数量
+    pub fn with_max_keys(mut self, max_keys: usize) -> Self {
+        self.max_keys = max_keys.max(1);
+        self
+    }
+
+    /// 当前保存的限流键数量
+    pub fn len(&self) -> usize {
+        self.buckets.lock().unwrap().len()
+    }
+
+    /// 是否没有保存任何限流键
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Default for InMemoryRateLimitStore {
+    fn default() -> Self {
+        Self {
+            buckets: Mutex::new(HashMap::new()),
+            max_keys: DEFAULT_MAX_KEYS,
+        }
+    }
+}
+
+/// 为新键腾出空间：删除已补满的桶，仍不够时删除最久未使用的桶
+///
+/// 每次至少腾出上限的 1/10，之后的新键不必再扫描全部桶，插入的均摊开销为常数。
+fn evict(buckets: &mut HashMap<String, Bucket>, max_keys: usize, now: Instant) {
+    let target = max_keys - (max_keys / 10).max(1);
+    buckets.retain(|_, bucket| bucket.full_at > now);
+    if buckets.len() <= target {
+        return;
+    }
+
+    let count = buckets.len() - target;
+    let mut by_age: Vec<(Instant, String)> =
+        buckets.iter().map(|(key, bucket)| (bucket.updated_at, key.clone())).collect();
+    by_age.select_nth_unstable(count - 1);
+    for (_, key) in by_age.into_iter().take(count) {
+        buckets.remove(&key);
+    }
+}
+
+impl RateLimitStore for InMemoryRateLimitStore {
+    fn acquire(&self, key: &str, limit: &RateLimit) -> RateLimitDecision {
+        let capacity = limit.limit as f64;
+        let refill_per_sec = capacity / limit.window.as_secs_f64();
+        let now = Instant::now();
+
+        let mut buckets = self.buckets.lock().unwrap();
+        if !buckets.contains_key(key) && buckets.len() >= self.max_keys {
+            evict(&mut buckets, self.max_keys, now);
+        }
+        let bucket = buckets.entry(key.to_string()).or_insert(Bucket {
+            tokens: capacity,
+            updated_at: now,
+            full_at: now,
+        });
+
+        let elapsed = now.duration_since(bucket.updated_at).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * refill_per_sec).min(capacity);
+        bucket.updated_at = now;
+
+        let allowed = bucket.tokens >= 1.0;
+        if allowed {
+            bucket.tokens -= 1.0;
+        }
+
+        bucket.full_at = now + Duration::from_secs_f64((capacity - bucket.tokens) / refill_per_sec);
+
+        let missing = if allowed { capacity - bucket.tokens } else { 1.0 - bucket.tokens };
+        RateLimitDecision {
+            allowed,
+            limit: limit.limit,
+            remaining: bucket.tokens.floor() as u64,
+            reset_after: Duration::from_secs_f64(missing / refill_per_sec),
+        }
+    }
+}
+
+/// 限流中间件
+///
+/// 每个请求消耗一个配额并返回 `RateLimit-Limit`、`RateLimit-Remaining`、
+/// `RateLimit-Reset` 响应头；配额耗尽时返回 [`Error::TooManyRequests`]，
+/// 由适配器转换为带 `Retry-After` 的 429 响应。
+#[derive(Clone)]
+pub struct RateLimitMiddleware {
+    limit: RateLimit,
+    key: RateLimitKey,
+    scope: String,
+    store: Arc<dyn RateLimitStore>,
+}
+
+impl RateLimitMiddleware {
+    /// 使用内存存储创建按客户端IP限流的中间件
+    pub fn new(limit: RateLimit) -> Self {
+        Self {
+            limit,
+            key: RateLimitKey::default(),
+            scope: "global".to_string(),
+            store: Arc::new(InMemoryRateLimitStore::new()),
+        }
+    }
+
+    /// 设置限流键
+    pub fn key(mut self, key: RateLimitKey) -> Self {
+        self.key = key;
+        self
+    }
+
+    /// 设置限流作用域，多个中间件共享存储时用于区分配额
+    pub fn scope(mut self, scope: &str) -> Self {
+        self.scope = scope.to_string();
+        self
+    }
+
+    /// 设置限流状态存储
+    pub fn store(mut self, store: Arc<dyn RateLimitStore>) -> Self {
+        self.store = store;
+        self
+    }
+}
+
+impl Middleware for RateLimitMiddleware {
+    fn handle(&self, context: &mut MiddlewareContext) -> Result<(), Error> {
+        let key = format!("{}|{}", self.scope, self.key.extract(context));
+        let decision = self.store.acquire(&key, &self.limit);
+        let reset_secs = decision.reset_after.as_secs_f64().ceil() as u64;
+
+        context.response_headers.extend([
+            ("RateLimit-Limit".to_string(), decision.limit.to_string()),
+            ("RateLimit-Remaining".to_string(), decision.remaining.to_string()),
+            ("RateLimit-Reset".to_string(), reset_secs.to_string()),
+        ]);
+
+        if decision.allowed {
+            Ok(())
+        } else {
+            Err(Error::TooManyRequests { retry_after: reset_secs.max(1) })
+        }
+    }
+}
@@ -0,0 +1,519 @@
+//! OpenAPI文档模块，从路由信息构建类型化的 OpenAPI 3.1 文档
+//!
+//! 路由注册表、[`SwaggerGenerator`](crate::swagger) 和各框架适配器都通过
+//! [`OpenApiBuilder`] 生成文档，保证同一组路由在任何后端上得到相同的规范。
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::feature::{self, FeatureFlagProvider};
+use crate::matcher::{RoutePattern, Segment};
+use crate::registry::RouteRegistry;
+use crate::routing::{HttpMethod, RouteInfo};
+
+/// 生成文档使用的OpenAPI版本
+pub const OPENAPI_VERSION: &str = "3.1.0";
+
+/// 需要认证的路由默认引用的安全方案名称
+pub const BEARER_AUTH: &str = "bearerAuth";
+
+/// OpenAPI文档
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct OpenApi {
+    /// OpenAPI版本
+    pub openapi: String,
+    /// API基本信息
+    pub info: Info,
+    /// 服务器列表
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub servers: Vec<Server>,
+    /// 标签列表
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<Tag>,
+    /// 按路径组织的操作
+    pub paths: BTreeMap<String, PathItem>,
+    /// 可复用组件
+    pub components: Components,
+}
+
+impl OpenApi {
+    /// 转换为JSON值
+    pub fn to_json(&self) -> Value {
+        serde_json::to_value(self).expect("OpenAPI document is always serializable")
+    }
+
+    /// 转换为格式化的JSON字符串
+    pub fn to_json_string(&self) -> String {
+        serde_json::to_string_pretty(self).expect("OpenAPI document is always serializable")
+    }
+}
+
+/// API基本信息
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Info {
+    /// 标题
+    pub title: String,
+    /// API版本
+    pub version: String,
+    /// 描述
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// 服务器
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Server {
+    /// 服务器地址
+    pub url: String,
+    /// 描述
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// 标签
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Tag {
+    /// 标签名
+    pub name: String,
+    /// 描述
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// 路径项，键为小写的HTTP方法
+#[derive(Debug, Clone, PartialEq, Default, Serialize)]
+pub struct PathItem {
+    /// 该路径上的操作
+    #[serde(flatten)]
+    pub operations: BTreeMap<String, Operation>,
+}
+
+/// 一个HTTP操作
+#[derive(Debug, Clone, PartialEq, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Operation {
+    /// 摘要
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    /// 描述
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// 所属标签
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// 操作ID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub operation_id: Option<String>,
+    /// 参数
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub parameters: Vec<Parameter>,
+    /// 请求体
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_body: Option<RequestBody>,
+    /// 按状态码组织的响应
+    pub responses: BTreeMap<String, Response>,
+    /// 安全要求
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub security: Vec<BTreeMap<String, Vec<String>>>,
+    /// 是否已弃用
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub deprecated: bool,
+    /// 外部文档
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external_docs: Option<ExternalDocumentation>,
+}
+
+/// 外部文档
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ExternalDocumentation {
+    /// 文档地址
+    pub url: String,
+    /// 描述
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// 参数
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Parameter {
+    /// 参数名
+    pub name: String,
+    /// 参数位置，如 `path`、`query`、`header`
+    #[serde(rename = "in")]
+    pub location: String,
+    /// 是否必需
+    pub required: bool,
+    /// 参数的JSON Schema
+    pub schema: Value,
+}
+
+/// 请求体
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RequestBody {
+    /// 是否必需
+    pub required: bool,
+    /// 按媒体类型组织的内容
+    pub content: BTreeMap<String, MediaType>,
+}
+
+/// 媒体类型内容
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MediaType {
+    /// 内容的JSON Schema
+    pub schema: Value,
+    /// 示例
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub example: Option<Value>,
+}
+
+/// 响应
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Response {
+    /// 描述
+    pub description: String,
+    /// 按媒体类型组织的内容
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub content: BTreeMap<String, MediaType>,
+}
+
+/// 可复用组件
+#[derive(Debug, Clone, PartialEq, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Components {
+    /// 类型Schema
+    pub schemas: BTreeMap<String, Value>,
+    /// 安全方案
+    pub security_schemes: BTreeMap<String, SecurityScheme>,
+}
+
+/// 安全方案
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum SecurityScheme {
+    /// HTTP认证，如 `bearer`、`basic`
+    Http {
+        /// 认证方案
+        scheme: String,
+        /// 令牌格式，如 `JWT`
+        #[serde(rename = "bearerFormat", skip_serializing_if = "Option::is_none")]
+        bearer_format: Option<String>,
+    },
+    /// 通过请求头、查询参数或Cookie传递的API Key
+    ApiKey {
+        /// 参数名
+        name: String,
+        /// 参数位置
+        #[serde(rename = "in")]
+        location: String,
+    },
+}
+
+impl SecurityScheme {
+    /// Bearer令牌认证
+    pub fn bearer(format: &str) -> Self {
+        SecurityScheme::Http {
+            scheme: "bearer".to_string(),
+            bearer_format: Some(format.to_string()),
+        }
+    }
+
+    /// 通过请求头传递的API Key
+    pub fn api_key(header: &str) -> Self {
+        SecurityScheme::ApiKey {
+            name: header.to_string(),
+            location: "header".to_string(),
+        }
+    }
+}
+
+/// OpenAPI文档构建器
+///
+/// ```ignore
+/// let spec = OpenApiBuilder::new("Users API", "1.0.0")
+///     .server("https://api.example.com", Some("Production"))
+///     .tag("users", Some("User management"))
+///     .registry(&registry)
+///     .build();
+/// ```
+#[derive(Debug, Clone)]
+pub struct OpenApiBuilder {
+    info: Info,
+    servers: Vec<Server>,
+    tags: Vec<Tag>,
+    security_schemes: BTreeMap<String, SecurityScheme>,
+    routes: Vec<RouteInfo>,
+    feature_flags: Option<Arc<dyn FeatureFlagProvider>>,
+}
+
+impl OpenApiBuilder {
+    /// 创建构建器，默认注册名为 [`BEARER_AUTH`] 的JWT Bearer安全方案
+    pub fn new(title: &str, version: &str) -> Self {
+        let mut security_schemes = BTreeMap::new();
+        security_schemes.insert(BEARER_AUTH.to_string(), SecurityScheme::bearer("JWT"));
+        Self {
+            info: Info {
+                title: title.to_string(),
+                version: version.to_string(),
+                description: Some("API documentation generated by uni_routing".to_string()),
+            },
+            servers: Vec::new(),
+            tags: Vec::new(),
+            security_schemes,
+            routes: Vec::new(),
+            feature_flags: None,
+        }
+    }
+
+    /// 设置API版本
+    pub fn version(mut self, version: &str) -> Self {
+        self.info.version = version.to_string();
+        self
+    }
+
+    /// 设置API描述
+    pub fn description(mut self, description: &str) -> Self {
+        self.info.description = Some(description.to_string());
+        self
+    }
+
+    /// 添加服务器
+    pub fn server(mut self, url: &str, description: Option<&str>) -> Self {
+        self.servers.push(Server {
+            url: url.to_string(),
+            description: description.map(str::to_string),
+        });
+        self
+    }
+
+    /// 添加标签
+    pub fn tag(mut self, name: &str, description: Option<&str>) -> Self {
+        self.tags.push(Tag {
+            name: name.to_string(),
+            description: description.map(str::to_string),
+        });
+        self
+    }
+
+    /// 添加或替换安全方案
+    pub fn security_scheme(mut self, name: &str, scheme: SecurityScheme) -> Self {
+        self.security_schemes.insert(name.to_string(), scheme);
+        self
+    }
+
+    /// 添加路由
+    pub fn route(mut self, route: RouteInfo) -> Self {
+        self.routes.push(route);
+        self
+    }
+
+    /// 添加多个路由
+    pub fn routes(mut self, routes: impl IntoIterator<Item = RouteInfo>) -> Self {
+        self.routes.extend(routes);
+        self
+    }
+
+    /// 添加路由注册表中的所有路由
+    pub fn registry(self, registry: &RouteRegistry) -> Self {
+        self.routes(registry.get_routes())
+    }
+
+    /// 设置功能开关提供者，生成文档时不包含开关关闭的路由
+    ///
+    /// 未设置时不包含任何带 `feature_flag` 的路由。
+    pub fn feature_flags(mut self, provider: Arc<dyn FeatureFlagProvider>) -> Self {
+        self.feature_flags = Some(provider);
+        self
+    }
+
+    /// 已添加的路由数量
+    pub fn route_count(&self) -> usize {
+        self.routes.len()
+    }
+
+    /// 生成OpenAPI文档
+    ///
+    /// 同一路径下的多个方法合并到同一个路径项中，请求体和响应引用的
+    /// 类型Schema收集到 `components/schemas`。
+    pub fn build(&self) -> OpenApi {
+        let mut paths: BTreeMap<String, PathItem> = BTreeMap::new();
+        let mut schemas = BTreeMap::new();
+
+        for route in &self.routes {
+            if !feature::is_route_enabled(route, self.feature_flags.as_deref()) {
+                continue;
+            }
+            // CONNECT 与扩展方法无法在 OpenAPI 路径项中表示
+            let Some(method) = operation_method(&route.method) else {
+                continue;
+            };
+            for (path, omitted) in path_variants(route) {
+                paths
+                    .entry(path)
+                    .or_default()
+                    .operations
+                    .insert(method.to_string(), operation(route, &omitted));
+            }
+
+            let type_schemas = route
+                .request_body
+                .iter()
+                .chain(route.responses.iter().filter_map(|response| response.schema.as_ref()));
+            for type_schema in type_schemas {
+                schemas.extend(type_schema.components.clone());
+            }
+        }
+
+        OpenApi {
+            openapi: OPENAPI_VERSION.to_string(),
+            info: self.info.clone(),
+            servers: self.servers.clone(),
+            tags: self.tags.clone(),
+            paths,
+            components: Components {
+                schemas,
+                security_schemes: self.security_schemes.clone(),
+            },
+        }
+    }
+}
+
+/// OpenAPI路径项中的操作字段名
+fn operation_method(method: &HttpMethod) -> Option<&'static str> {
+    match method {
+        HttpMethod::GET => Some("get"),
+        HttpMethod::POST => Some("post"),
+        HttpMethod::PUT => Some("put"),
+        HttpMethod::DELETE => Some("delete"),
+        HttpMethod::PATCH => Some("patch"),
+        HttpMethod::HEAD => Some("head"),
+        HttpMethod::OPTIONS => Some("options"),
+        HttpMethod::TRACE => Some("trace"),
+        HttpMethod::CONNECT | HttpMethod::Other(_) => None,
+    }
+}
+
+/// JSON内容
+fn json_content(schema: Option<&Value>, example: Option<&Value>) -> BTreeMap<String, MediaType> {
+    let mut content = BTreeMap::new();
+    if schema.is_some() || example.is_some() {
+        let media_type = MediaType {
+            schema: schema.cloned().unwrap_or_else(|| serde_json::json!({})),
+            example: example.cloned(),
+        };
+        content.insert("application/json".to_string(), media_type);
+    }
+    content
+}
+
+/// 路由在文档中的路径及每个路径省略的可选参数
+///
+/// OpenAPI 的路径参数必须是必需的，因此末尾的可选参数按省略的个数展开为多个路径，
+/// 如 `/articles/{page?}` 展开为 `/articles` 与 `/articles/{page}`。
+fn path_variants(route: &RouteInfo) -> Vec<(String, Vec<String>)> {
+    let Ok(pattern) = RoutePattern::parse(&route.full_path()) else {
+        return vec![(route.openapi_path(), Vec::new())];
+    };
+    let optional: Vec<String> = pattern
+        .segments()
+        .iter()
+        .filter_map(|segment| match segment {
+            Segment::Param { name, optional: true, .. } => Some(name.clone()),
+            _ => None,
+        })
+        .collect();
+
+    (0..=optional.len())
+        .map(|kept| {
+            let required = pattern.segments().len() - optional.len();
+            let path: String = pattern.segments()[..required + kept]
+                .iter()
+                .map(|segment| match segment {
+                    Segment::Static(value) => format!("/{}", value),
+                    Segment::Param { name, .. } | Segment::CatchAll(name) => format!("/{{{}}}", name),
+                })
+                .collect();
+            let path = if path.is_empty() { "/".to_string() } else { path };
+            (path, optional[kept..].to_vec())
+        })
+        .collect()
+}
+
+/// 由路由信息生成操作，`omitted` 为该路径中省略的可选参数
+///
+/// 省略了可选参数的操作在操作ID后加上 `_without_<参数>`，保证操作ID唯一。
+fn operation(route: &RouteInfo, omitted: &[String]) -> Operation {
+    let parameters = route
+        .path_params
+        .iter()
+        .filter(|param| !omitted.contains(&param.name))
+        .map(|param| Parameter {
+            name: param.name.clone(),
+            location: "path".to_string(),
+            required: true,
+            schema: param.schema.schema.clone(),
+        })
+        .collect();
+
+    let request_body = route.request_body.as_ref().map(|body| RequestBody {
+        required: true,
+        content: json_content(Some(&body.schema), route.request_example.as_ref()),
+    });
+
+    let mut responses: BTreeMap<String, Response> = route
+        .responses
+        .iter()
+        .map(|response| {
+            let schema = response.schema.as_ref().map(|schema| &schema.schema);
+            let content = json_content(schema, response.example.as_ref());
+            let response_object = Response {
+                description: response.description.clone(),
+                content,
+            };
+            (response.status.to_string(), response_object)
+        })
+        .collect();
+    if responses.is_empty() {
+        responses.insert(
+            "200".to_string(),
+            Response {
+                description: "Successful response".to_string(),
+                content: BTreeMap::new(),
+            },
+        );
+    }
+
+    let mut security = Vec::new();
+    if route.auth_policy.is_some() {
+        let mut requirement = BTreeMap::new();
+        requirement.insert(BEARER_AUTH.to_string(), Vec::new());
+        security.push(requirement);
+    }
+
+    let summary = route
+        .summary
+        .clone()
+        .or_else(|| route.description.clone())
+        .unwrap_or_else(|| "API endpoint".to_string());
+
+    Operation {
+        summary: Some(summary),
+        description: route.description.clone(),
+        tags: route.tags.clone(),
+        operation_id: match (&route.operation_id, omitted.is_empty()) {
+            (Some(operation_id), false) => Some(format!("{}_without_{}", operation_id, omitted.join("_"))),
+            (operation_id, _) => operation_id.clone(),
+        },
+        parameters,
+        request_body,
+        responses,
+        security,
+        deprecated: route.deprecated,
+        external_docs: route.external_docs.as_ref().map(|docs| ExternalDocumentation {
+            url: docs.url.clone(),
+            description: docs.description.clone(),
+        }),
+    }
+}
@@ -1,107 +1,194 @@
 //! 路由注册系统，用于收集和管理所有使用uni_routing宏定义的路由
 
-use std::sync::{Arc, Mutex};
+#[cfg(any(test, feature = "test-util"))]
+use std::cell::RefCell;
+use std::sync::{Arc, OnceLock, RwLock};
+use crate::config::{AppliedConfig, RouteConfig};
+use crate::conflict::{detect_conflicts, RouteConflict};
+use crate::group::RouteGroup;
+use crate::matcher::{RouteMatcher, RoutePattern};
+use crate::openapi::OpenApiBuilder;
 use crate::routing::{RouteInfo, HttpMethod};
+use crate::snapshot::RouteSnapshot;
+use crate::Error;
 
 /// 路由注册表，存储所有已注册的路由
+///
+/// 启动阶段通过读写锁注册路由；调用 [`RouteRegistry::freeze`] 冻结后，
+/// 路由表被编译为 [`RouteMatcher`]，不再变化，读取不再加锁。
 pub struct RouteRegistry {
-    routes: Arc<Mutex<Vec<RouteInfo>>>,
+    routes: RwLock<Vec<RouteInfo>>,
+    /// 冻结前 [`RouteRegistry::get_route`] 使用的匹配器，路由表变化时失效
+    compiled: RwLock<Option<Arc<RouteMatcher>>>,
+    frozen: OnceLock<RouteMatcher>,
 }
 
 impl RouteRegistry {
     /// 创建新的路由注册表
-    pub fn new() -> Self {
+    pub const fn new() -> Self {
         Self {
-            routes: Arc::new(Mutex::new(Vec::new())),
+            routes: RwLock::new(Vec::new()),
+            compiled: RwLock::new(None),
+            frozen: OnceLock::new(),
         }
     }
 
     /// 注册单个路由
-    pub fn register_route(&self, route: RouteInfo) {
-        let mut routes = self.routes.lock().unwrap();
+    ///
+    /// 路由模板无效、相同方法和路径的路由已注册时返回错误，`{id}` 与 `<id>` 视为同一路径；
+    /// 注册表冻结后不能再注册路由。
+    pub fn register_route(&self, route: RouteInfo) -> Result<(), Error> {
+        RoutePattern::parse(&route.full_path())?;
+        let mut routes = self.routes.write().unwrap();
+        if self.is_frozen() {
+            return Err(Error::Other(format!(
+                "Route registry is frozen, cannot register {} {}",
+                route.method, route.path
+            )));
+        }
+        let path = route.openapi_path();
+        if let Some(existing) = routes.iter().find(|r| r.method == route.method && r.openapi_path() == path) {
+            return Err(Error::Other(format!(
+                "Duplicate route registration: {} {} ({} conflicts with {})",
+                route.method,
+                path,
+                route.describe(),
+                existing.describe()
+            )));
+        }
         routes.push(route);
+        *self.compiled.write().unwrap() = None;
+        Ok(())
     }
 
-    /// 获取所有路由
-    pub fn get_routes(&self) -> Vec<RouteInfo> {
-        let routes = self.routes.lock().unwrap();
-        routes.clone()
+    /// 校验路由表并冻结注册表，编译路由匹配器，返回最终的路由表
+    ///
+    /// 存在重复或有歧义的路由时返回错误并保持未冻结，错误信息列出所有冲突的处理函数及源码位置；
+    /// 由优先级规则确定的重叠（如 `/users/me` 与 `/users/{id}`）只记录警告。
+    /// 冻结后的读取不再加锁，重复调用返回同一路由表。
+    pub fn freeze(&self) -> Result<&[RouteInfo], Error> {
+        if let Some(matcher) = self.frozen.get() {
+            return Ok(matcher.routes());
+        }
+
+        let routes = self.routes.read().unwrap();
+        let (fatal, overlaps): (Vec<RouteConflict>, Vec<RouteConflict>) =
+            detect_conflicts(&routes).into_iter().partition(|conflict| conflict.kind.is_fatal());
+        for overlap in &overlaps {
+            log::warn!("{}", overlap);
+        }
+        if !fatal.is_empty() {
+            let report: Vec<String> = fatal.iter().map(|conflict| format!("  - {}", conflict)).collect();
+            return Err(Error::Other(format!(
+                "Route conflicts detected:\n{}",
+                report.join("\n")
+            )));
+        }
+
+        let matcher = self.frozen.get_or_init(|| {
+            RouteMatcher::new(routes.iter().cloned()).expect("route templates are validated at registration")
+        });
+        Ok(matcher.routes())
     }
 
-    /// 根据路径和方法获取路由
-    pub fn get_route(&self, path: &str, method: HttpMethod) -> Option<RouteInfo> {
-        let routes = self.routes.lock().unwrap();
-        routes.iter()
-            .find(|r| r.path == path && r.method == method)
-            .cloned()
+    /// 当前路由表中的所有冲突，包括只作为警告的重叠
+    pub fn conflicts(&self) -> Vec<RouteConflict> {
+        detect_conflicts(&self.get_routes())
     }
 
-    /// 生成OpenAPI规范
-    pub fn generate_openapi(&self, title: &str, version: &str) -> serde_json::Value {
-        let routes = self.get_routes();
-        let mut paths = serde_json::Map::new();
+    /// 冻结后编译的路由匹配器
+    pub fn matcher(&self) -> Option<&RouteMatcher> {
+        self.frozen.get()
+    }
+
+    /// 注册表是否已冻结
+    pub fn is_frozen(&self) -> bool {
+        self.frozen.get().is_some()
+    }
 
-        for route in routes {
-            let path_item = self.create_path_item(&route);
-            paths.insert(route.path.clone(), serde_json::Value::Object(path_item));
+    /// 将覆盖配置合并到已注册的路由上，必须在冻结前调用
+    ///
+    /// 配置无效时注册表保持不变。
+    pub fn apply_config(&self, config: &RouteConfig) -> Result<AppliedConfig, Error> {
+        let mut routes = self.routes.write().unwrap();
+        if self.is_frozen() {
+            return Err(Error::Other("Route registry is frozen, cannot apply route configuration".to_string()));
         }
+        let applied = config.apply(routes.clone())?;
+        *routes = applied.routes.clone();
+        *self.compiled.write().unwrap() = None;
+        Ok(applied)
+    }
 
-        serde_json::json!({
-            "openapi": "3.0.0",
-            "info": {
-                "title": title,
-                "version": version,
-                "description": "API documentation generated by uni_routing"
-            },
-            "paths": serde_json::Value::Object(paths),
-            "components": {
-                "securitySchemes": {
-                    "bearerAuth": {
-                        "type": "http",
-                        "scheme": "bearer",
-                        "bearerFormat": "JWT"
-                    }
-                }
-            }
-        })
-    }
-
-    /// 创建路径项
-    fn create_path_item(&self, route: &RouteInfo) -> serde_json::Map<String, serde_json::Value> {
-        let mut path_item = serde_json::Map::new();
-        let method_str = match route.method {
-            HttpMethod::GET => "get",
-            HttpMethod::POST => "post",
-            HttpMethod::PUT => "put",
-            HttpMethod::DELETE => "delete",
-            HttpMethod::PATCH => "patch",
-        };
+    /// 注册分组内的所有路由
+    pub fn register_group(&self, group: &RouteGroup) -> Result<(), Error> {
+        group.build().into_iter().try_for_each(|route| self.register_route(route))
+    }
+
+    /// 获取所有路由
+    pub fn get_routes(&self) -> Vec<RouteInfo> {
+        match self.frozen.get() {
+            Some(matcher) => matcher.routes().to_vec(),
+            None => self.routes.read().unwrap().clone(),
+        }
+    }
 
-        let mut operation = serde_json::Map::new();
-        operation.insert("summary".to_string(), serde_json::Value::String(
-            route.description.clone().unwrap_or_else(|| "API endpoint".to_string())
-        ));
+    /// 获取注册表快照，可保存为 JSON 或 TOML
+    pub fn snapshot(&self) -> RouteSnapshot {
+        RouteSnapshot::new(self.get_routes())
+    }
 
-        if route.description.is_some() {
-            operation.insert("description".to_string(), serde_json::Value::String(
-                route.description.clone().unwrap()
-            ));
+    /// 根据请求路径和方法获取路由，例如 `/api/users/123` 匹配 `/api/users/{id}`
+    ///
+    /// 冻结前使用缓存的匹配器，注册路由或应用配置后在下一次查找时重新编译。
+    pub fn get_route(&self, path: &str, method: HttpMethod) -> Option<RouteInfo> {
+        let method = method.to_string();
+        let find = |matcher: &RouteMatcher| matcher.find(&method, path).map(|found| found.route.clone());
+        match self.frozen.get() {
+            Some(matcher) => find(matcher),
+            None => find(self.compiled_matcher()?.as_ref()),
         }
+    }
 
-        // 添加认证要求
-        if route.auth_policy.is_some() {
-            operation.insert("security".to_string(), serde_json::json!([{"bearerAuth": []}]));
+    /// 冻结前的匹配器，缓存失效时按当前路由表重新编译
+    fn compiled_matcher(&self) -> Option<Arc<RouteMatcher>> {
+        if let Some(matcher) = self.compiled.read().unwrap().as_ref() {
+            return Some(matcher.clone());
+        }
+        let routes = self.routes.read().unwrap();
+        let mut compiled = self.compiled.write().unwrap();
+        if compiled.is_none() {
+            *compiled = Some(Arc::new(RouteMatcher::new(routes.iter().cloned()).ok()?));
         }
+        compiled.clone()
+    }
 
-        // 添加响应
-        let mut responses = serde_json::Map::new();
-        responses.insert("200".to_string(), serde_json::json!({
-            "description": "Successful response"
-        }));
-        operation.insert("responses".to_string(), serde_json::Value::Object(responses));
+    /// 按操作ID查找路由
+    pub fn get_route_by_operation(&self, operation_id: &str) -> Option<RouteInfo> {
+        let find = |routes: &[RouteInfo]| {
+            routes
+                .iter()
+                .find(|route| route.operation_id.as_deref() == Some(operation_id))
+                .cloned()
+        };
+        match self.frozen.get() {
+            Some(matcher) => find(matcher.routes()),
+            None => find(&self.routes.read().unwrap()),
+        }
+    }
 
-        path_item.insert(method_str.to_string(), serde_json::Value::Object(operation));
-        path_item
+    /// 按操作ID生成请求路径，参数值进行百分号编码，例如 `url_for("get_user", &[("id", "42")])`
+    ///
+    /// 操作ID未注册、缺少必需参数或提供了未知参数时返回错误。
+    pub fn url_for<S: AsRef<str>>(&self, operation_id: &str, params: &[(&str, S)]) -> Result<String, Error> {
+        self.get_route_by_operation(operation_id)
+            .ok_or_else(|| Error::Other(format!("No route registered for operation `{}`", operation_id)))?
+            .url(params)
+    }
+
+    /// 生成OpenAPI规范
+    pub fn generate_openapi(&self, title: &str, version: &str) -> serde_json::Value {
+        OpenApiBuilder::new(title, version).registry(self).build().to_json()
     }
 }
 
@@ -112,22 +199,53 @@ impl Default for RouteRegistry {
 }
 
 /// 全局路由注册表实例
-static mut GLOBAL_REGISTRY: Option<RouteRegistry> = None;
-static INIT: std::sync::Once = std::sync::Once::new();
+fn global_registry() -> &'static Arc<RouteRegistry> {
+    static GLOBAL_REGISTRY: OnceLock<Arc<RouteRegistry>> = OnceLock::new();
+    GLOBAL_REGISTRY.get_or_init(|| Arc::new(RouteRegistry::new()))
+}
+
+#[cfg(any(test, feature = "test-util"))]
+thread_local! {
+    /// 当前线程临时替换全局注册表的隔离注册表
+    static ISOLATED_REGISTRY: RefCell<Option<Arc<RouteRegistry>>> = const { RefCell::new(None) };
+}
 
 /// 获取全局路由注册表
-pub fn get_global_registry() -> &'static RouteRegistry {
-    unsafe {
-        INIT.call_once(|| {
-            GLOBAL_REGISTRY = Some(RouteRegistry::new());
-        });
-        GLOBAL_REGISTRY.as_ref().unwrap()
+///
+/// 启用 `test-util` 特性时，在 `with_isolated_registry` 的闭包中返回当前线程的隔离注册表。
+pub fn get_global_registry() -> Arc<RouteRegistry> {
+    #[cfg(any(test, feature = "test-util"))]
+    if let Some(isolated) = ISOLATED_REGISTRY.with(|isolated| isolated.borrow().clone()) {
+        return isolated;
     }
+    global_registry().clone()
+}
+
+/// 在隔离的注册表中执行闭包，仅用于测试（需要 `test-util` 特性）
+///
+/// 闭包执行期间，当前线程的 [`get_global_registry`] 及本模块的辅助函数使用新建的空注册表，
+/// 并行运行的测试互不影响；闭包中启动的其他线程仍使用全局注册表。
+/// 闭包结束（包括 panic）时恢复之前的注册表，隔离注册表在最后一个引用释放时销毁。
+#[cfg(any(test, feature = "test-util"))]
+pub fn with_isolated_registry<R>(f: impl FnOnce(&Arc<RouteRegistry>) -> R) -> R {
+    /// 闭包结束时恢复之前的注册表
+    struct Restore(Option<Arc<RouteRegistry>>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            let previous = self.0.take();
+            ISOLATED_REGISTRY.with(|isolated| *isolated.borrow_mut() = previous);
+        }
+    }
+
+    let registry = Arc::new(RouteRegistry::new());
+    let _restore = Restore(ISOLATED_REGISTRY.with(|isolated| isolated.replace(Some(registry.clone()))));
+    f(&registry)
 }
 
 /// 宏辅助函数，用于注册路由
-pub fn register_route(route: RouteInfo) {
-    get_global_registry().register_route(route);
+pub fn register_route(route: RouteInfo) -> Result<(), Error> {
+    get_global_registry().register_route(route)
 }
 
 /// 获取所有已注册的路由
@@ -0,0 +1,330 @@
+//! 配置热加载模块，监视路由覆盖配置文件，在运行中原子地替换中间件链的路由表
+//!
+//! ```ignore
+//! let chain = Arc::new(MiddlewareChain::new());
+//! let reloader = Arc::new(ConfigReloader::new("routes.toml", routes, chain.clone(), MiddlewareFactory::default())?);
+//! let _watcher = reloader.clone().watch(Duration::from_secs(2));
+//! // 将 chain 交给框架适配器，例如 UniMiddleware::from_arc(chain)
+//! ```
+//!
+//! 每次重新加载都基于宏声明的原始路由重新应用整个配置文件。新配置完整校验并构建出
+//! 路由表后才替换旧表，校验失败时保留当前配置并记录错误；[`ConfigReloader::rollback`]
+//! 可以回到上一次生效的配置。每次替换都会输出一条描述变化的日志。
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::{Duration, SystemTime};
+
+use crate::config::{AppliedConfig, ConfigFormat, RouteConfig};
+use crate::middleware::{MiddlewareChain, MiddlewareFactory};
+use crate::routing::{HttpMethod, RouteInfo};
+use crate::Error;
+
+/// 日志目标
+pub const LOG_TARGET: &str = "uni_routing::reload";
+
+/// 单个路由在两次配置之间的变化
+#[derive(Debug, Clone, PartialEq)]
+pub struct RouteChange {
+    /// 宏声明的方法
+    pub method: HttpMethod,
+    /// 宏声明的完整路径
+    pub path: String,
+    /// 变化描述，如 ``rate limit `10/s` -> `100/min` ``
+    pub message: String,
+}
+
+impl fmt::Display for RouteChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}: {}", self.method, self.path, self.message)
+    }
+}
+
+/// 一次重新加载带来的路由变化
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ConfigChange {
+    /// 发生变化的路由，按宏声明的顺序排列
+    pub changes: Vec<RouteChange>,
+}
+
+impl ConfigChange {
+    /// 比较两份应用后的配置，`routes` 为宏声明的原始路由
+    pub fn between(routes: &[RouteInfo], old: &AppliedConfig, new: &AppliedConfig) -> Self {
+        let changes = routes
+            .iter()
+            .filter_map(|route| {
+                let message = match (effective(old, route), effective(new, route)) {
+                    (None, None) => return None,
+                    (Some(_), None) => "disabled".to_string(),
+                    (None, Some(_)) => "enabled".to_string(),
+                    (Some(old), Some(new)) => {
+                        let mut messages = Vec::new();
+                        if old.full_path() != new.full_path() {
+                            messages.push(format!("path `{}` -> `{}`", old.full_path(), new.full_path()));
+                        }
+                        if auth_label(old) != auth_label(new) {
+                            messages.push(format!("auth {} -> {}", auth_label(old), auth_label(new)));
+                        }
+                        if rate_limit_label(old) != rate_limit_label(new) {
+                            messages.push(format!("rate limit {} -> {}", rate_limit_label(old), rate_limit_label(new)));
+                        }
+                        if messages.is_empty() {
+                            return None;
+                        }
+                        messages.join(", ")
+                    }
+                };
+                Some(RouteChange {
+                    method: route.method.clone(),
+                    path: route.full_path(),
+                    message,
+                })
+            })
+            .collect();
+        Self { changes }
+    }
+
+    /// 是否没有任何变化
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+impl fmt::Display for ConfigChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.changes.is_empty() {
+            return write!(f, "no route changes");
+        }
+        write!(f, "{} routes changed", self.changes.len())?;
+        for (i, change) in self.changes.iter().enumerate() {
+            write!(f, "{} {}", if i == 0 { ":" } else { ";" }, change)?;
+        }
+        Ok(())
+    }
+}
+
+/// 宏声明的路由在应用配置后的生效形式，被禁用时为 `None`
+fn effective<'a>(applied: &'a AppliedConfig, route: &RouteInfo) -> Option<&'a RouteInfo> {
+    let same = |other: &RouteInfo| other.method == route.method && other.full_path() == route.full_path();
+    if applied.disabled.iter().any(same) {
+        return None;
+    }
+    match applied.remapped.iter().find(|remapped| same(&remapped.original)) {
+        Some(remapped) => Some(&remapped.route),
+        None => applied.routes.iter().find(|other| same(other)),
+    }
+}
+
+fn auth_label(route: &RouteInfo) -> String {
+    match &route.auth_policy {
+        Some(policy) => format!("`{}`", policy.policy_expr),
+        None => "public".to_string(),
+    }
+}
+
+fn rate_limit_label(route: &RouteInfo) -> String {
+    match route.middleware.iter().find(|spec| spec.name == "rate_limit") {
+        Some(spec) => format!("`{}`", spec.args.join(", ")),
+        None => "none".to_string(),
+    }
+}
+
+/// 重新加载的统计信息
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ReloadStats {
+    /// 成功替换路由表的次数，不包含启动时的首次加载
+    pub reloads: u64,
+    /// 重新加载失败的次数
+    pub failures: u64,
+    /// 最近一次成功替换的时间
+    pub last_reload: Option<SystemTime>,
+    /// 最近一次失败的错误信息，成功加载后清空
+    pub last_error: Option<String>,
+}
+
+struct ReloadState {
+    applied: AppliedConfig,
+    previous: Option<AppliedConfig>,
+    /// 最近一次读取到的文件内容，内容不变时不重新加载
+    content: String,
+    stats: ReloadStats,
+}
+
+/// 路由覆盖配置的热加载器
+pub struct ConfigReloader {
+    path: PathBuf,
+    routes: Vec<RouteInfo>,
+    chain: Arc<MiddlewareChain>,
+    factory: MiddlewareFactory,
+    state: Mutex<ReloadState>,
+}
+
+impl ConfigReloader {
+    /// 加载配置文件并设置中间件链的路由表，`routes` 为宏声明的原始路由
+    ///
+    /// 启动时的配置无效直接返回错误。
+    pub fn new(
+        path: impl AsRef<Path>,
+        routes: Vec<RouteInfo>,
+        chain: Arc<MiddlewareChain>,
+        factory: MiddlewareFactory,
+    ) -> Result<Self, Error> {
+        let path = path.as_ref().to_path_buf();
+        let content = read(&path)?;
+        let applied = RouteConfig::parse(&content, ConfigFormat::from_path(&path))?.apply(routes.clone())?;
+        chain.reload(&applied, &factory)?;
+        Ok(Self {
+            path,
+            routes,
+            chain,
+            factory,
+            state: Mutex::new(ReloadState {
+                applied,
+                previous: None,
+                content,
+                stats: ReloadStats::default(),
+            }),
+        })
+    }
+
+    /// 配置文件路径
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// 当前生效的配置
+    pub fn applied(&self) -> AppliedConfig {
+        self.state.lock().unwrap().applied.clone()
+    }
+
+    /// 重新加载的统计信息
+    pub fn stats(&self) -> ReloadStats {
+        self.state.lock().unwrap().stats.clone()
+    }
+
+    /// 重新读取配置文件并替换路由表，失败时保留当前配置
+    pub fn reload(&self) -> Result<ConfigChange, Error> {
+        let content = read(&self.path);
+        let mut state = self.state.lock().unwrap();
+        match content {
+            Ok(content) => {
+                state.content = content.clone();
+                self.reload_content(&mut state, &content)
+            }
+            Err(e) => Err(self.fail(&mut state, e)),
+        }
+    }
+
+    /// 配置文件内容变化时重新加载，未变化时返回 `None`
+    pub fn check(&self) -> Option<Result<ConfigChange, Error>> {
+        let content = read(&self.path);
+        let mut state = self.state.lock().unwrap();
+        match content {
+            Ok(content) if content == state.content => None,
+            Ok(content) => {
+                // 记录失败的内容，避免每次轮询重复报告同一个错误
+                state.content = content.clone();
+                Some(self.reload_content(&mut state, &content))
+            }
+            Err(e) => Some(Err(self.fail(&mut state, e))),
+        }
+    }
+
+    /// 回到上一次生效的配置
+    ///
+    /// 文件内容不会被修改，文件再次变化时会重新加载。
+    pub fn rollback(&self) -> Result<ConfigChange, Error> {
+        let mut state = self.state.lock().unwrap();
+        let Some(previous) = state.previous.clone() else {
+            return Err(Error::Other("No previous route configuration to roll back to".to_string()));
+        };
+        self.chain.reload(&previous, &self.factory)?;
+        let change = self.swap(&mut state, previous);
+        log::info!(target: LOG_TARGET, "Rolled back route configuration from {}: {}", self.path.display(), change);
+        Ok(change)
+    }
+
+    /// 在后台线程中每隔 `interval` 检查配置文件，返回的句柄被丢弃时停止
+    pub fn watch(self: Arc<Self>, interval: Duration) -> ConfigWatcher {
+        let (stop, stopped) = mpsc::channel::<()>();
+        let thread = std::thread::spawn(move || {
+            while let Err(RecvTimeoutError::Timeout) = stopped.recv_timeout(interval) {
+                // 结果已记录到日志和统计信息中
+                let _ = self.check();
+            }
+        });
+        ConfigWatcher {
+            stop: Some(stop),
+            thread: Some(thread),
+        }
+    }
+
+    fn reload_content(&self, state: &mut ReloadState, content: &str) -> Result<ConfigChange, Error> {
+        let applied = RouteConfig::parse(content, ConfigFormat::from_path(&self.path))
+            .and_then(|config| config.apply(self.routes.clone()))
+            .and_then(|applied| self.chain.reload(&applied, &self.factory).map(|()| applied));
+        match applied {
+            Ok(applied) => {
+                let change = self.swap(state, applied);
+                state.stats.reloads += 1;
+                state.stats.last_reload = Some(SystemTime::now());
+                state.stats.last_error = None;
+                log::info!(target: LOG_TARGET, "Reloaded route configuration from {}: {}", self.path.display(), change);
+                Ok(change)
+            }
+            Err(e) => Err(self.fail(state, e)),
+        }
+    }
+
+    /// 记录新生效的配置，返回相对于旧配置的变化
+    fn swap(&self, state: &mut ReloadState, applied: AppliedConfig) -> ConfigChange {
+        let change = ConfigChange::between(&self.routes, &state.applied, &applied);
+        state.previous = Some(std::mem::replace(&mut state.applied, applied));
+        change
+    }
+
+    fn fail(&self, state: &mut ReloadState, error: Error) -> Error {
+        state.stats.failures += 1;
+        state.stats.last_error = Some(error.to_string());
+        log::error!(
+            target: LOG_TARGET,
+            "Failed to reload route configuration from {}, keeping the current configuration: {}",
+            self.path.display(),
+            error
+        );
+        error
+    }
+}
+
+fn read(path: &Path) -> Result<String, Error> {
+    std::fs::read_to_string(path).map_err(|e| Error::Other(format!("Failed to read {}: {}", path.display(), e)))
+}
+
+/// 后台监视线程的句柄，丢弃时停止监视
+pub struct ConfigWatcher {
+    stop: Option<Sender<()>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl ConfigWatcher {
+    /// 停止监视并等待后台线程退出
+    pub fn stop(mut self) {
+        self.shutdown();
+    }
+
+    fn shutdown(&mut self) {
+        drop(self.stop.take());
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl Drop for ConfigWatcher {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
@@ -1,45 +1,325 @@
 //! 路由模块，用于处理不同Web框架的统一API路由
 
 use crate::auth::AuthPolicy;
+use crate::group::join_path;
+use crate::matcher::{self, Segment};
+use crate::middleware::MiddlewareSpec;
+use crate::schema::TypeSchema;
 use crate::Error;
+use serde::{Deserialize, Serialize};
 
 /// 路由信息结构体
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct RouteInfo {
     /// 路由路径
     pub path: String,
     /// HTTP方法
     pub method: HttpMethod,
     /// 认证策略
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub auth_policy: Option<AuthPolicy>,
     /// 路由描述
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    /// 操作摘要，未设置时使用路由描述
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    /// OpenAPI标签
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// 操作ID，宏默认使用处理函数名
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub operation_id: Option<String>,
+    /// 是否已弃用
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub deprecated: bool,
+    /// 外部文档
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external_docs: Option<ExternalDocs>,
+    /// 路由级中间件
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub middleware: Vec<MiddlewareSpec>,
+    /// 请求体Schema（从处理函数的 `Json<T>` 参数推断）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_body: Option<TypeSchema>,
+    /// 请求体示例
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_example: Option<serde_json::Value>,
+    /// 响应列表（从处理函数的返回类型推断）
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub responses: Vec<ResponseInfo>,
+    /// 路径参数（从路由模板与处理函数的提取器推断）
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub path_params: Vec<PathParam>,
+    /// API版本，设置后路由挂载在 `/{version}` 前缀下
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// 功能开关名称，开关关闭时路由返回 404 且不出现在 OpenAPI 文档中
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub feature_flag: Option<String>,
+    /// 定义路由的处理函数及其源码位置（由宏记录）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<RouteSource>,
 }
 
-/// HTTP方法枚举
-#[derive(Debug, Clone, PartialEq)]
+impl RouteInfo {
+    /// 判断请求的方法和路径是否与该路由匹配
+    pub fn matches(&self, method: &str, path: &str) -> bool {
+        self.matches_method(method) && self.matches_path(path)
+    }
+
+    /// 判断请求方法是否与该路由的方法相同，扩展方法区分大小写
+    pub fn matches_method(&self, method: &str) -> bool {
+        match &self.method {
+            HttpMethod::Other(expected) => expected == method,
+            expected => expected.to_string().eq_ignore_ascii_case(method),
+        }
+    }
+
+    /// 判断请求路径是否与该路由的模板匹配
+    ///
+    /// 模板语法见 [`crate::matcher`]，模板无效时不匹配任何路径。解析后的模板会被缓存，
+    /// 按请求分派时应使用 [`crate::matcher::RouteMatcher`]。
+    pub fn matches_path(&self, path: &str) -> bool {
+        matcher::cached_pattern(&self.full_path()).is_some_and(|pattern| pattern.captures(path).is_some())
+    }
+
+    /// 用于错误信息的路由描述，如 `GET /api/users/{id} [my_app::get_user (src/main.rs:12)]`
+    pub fn describe(&self) -> String {
+        match &self.source {
+            Some(source) => format!("{} {} [{}]", self.method, self.full_path(), source),
+            None => format!("{} {}", self.method, self.full_path()),
+        }
+    }
+
+    /// 路由在框架中的完整路径，带版本的路由加上 `/{version}` 前缀
+    pub fn full_path(&self) -> String {
+        match &self.version {
+            Some(version) => join_path(version, &self.path),
+            None => self.path.clone(),
+        }
+    }
+
+    /// 将参数代入路由模板（包含版本前缀）生成请求路径，参见 [`crate::url::build`]
+    pub fn url<S: AsRef<str>>(&self, params: &[(&str, S)]) -> Result<String, Error> {
+        crate::url::build(&self.full_path(), params)
+    }
+
+    /// 解析路由模板（包含版本前缀）
+    pub fn segments(&self) -> Vec<PathSegment> {
+        PathSegment::parse_template(&self.full_path())
+    }
+
+    /// OpenAPI格式的路径，参数统一写作 `{name}`
+    pub fn openapi_path(&self) -> String {
+        let path: String = self
+            .segments()
+            .iter()
+            .map(|segment| match segment {
+                PathSegment::Static(value) => format!("/{}", value),
+                PathSegment::Param(name) => format!("/{{{}}}", name),
+            })
+            .collect();
+        if path.is_empty() {
+            "/".to_string()
+        } else {
+            path
+        }
+    }
+}
+
+/// 路由响应信息
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct ResponseInfo {
+    /// HTTP状态码
+    pub status: u16,
+    /// 响应描述
+    pub description: String,
+    /// 响应体Schema
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema: Option<TypeSchema>,
+    /// 响应体示例
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub example: Option<serde_json::Value>,
+}
+
+impl ResponseInfo {
+    /// 使用状态码的标准原因短语作为描述创建响应
+    pub fn new(status: u16, schema: Option<TypeSchema>) -> Self {
+        Self {
+            status,
+            description: status_description(status).to_string(),
+            schema,
+            example: None,
+        }
+    }
+}
+
+/// 状态码的默认响应描述
+pub fn status_description(status: u16) -> &'static str {
+    match status {
+        200 => "Successful response",
+        201 => "Created",
+        202 => "Accepted",
+        204 => "No Content",
+        301 => "Moved Permanently",
+        302 => "Found",
+        304 => "Not Modified",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        409 => "Conflict",
+        410 => "Gone",
+        415 => "Unsupported Media Type",
+        422 => "Unprocessable Entity",
+        429 => "Too Many Requests",
+        500 => "Internal Server Error",
+        502 => "Bad Gateway",
+        503 => "Service Unavailable",
+        504 => "Gateway Timeout",
+        _ => "Response",
+    }
+}
+
+/// 路由的来源：处理函数及其源码位置
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct RouteSource {
+    /// 处理函数的完整路径，如 `my_app::users::get_user`
+    pub handler: String,
+    /// 源文件
+    pub file: String,
+    /// 行号
+    pub line: u32,
+}
+
+impl std::fmt::Display for RouteSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({}:{})", self.handler, self.file, self.line)
+    }
+}
+
+/// 外部文档链接
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct ExternalDocs {
+    /// 文档地址
+    pub url: String,
+    /// 描述
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// 路径参数信息
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct PathParam {
+    /// 参数名
+    pub name: String,
+    /// 参数Schema
+    pub schema: TypeSchema,
+}
+
+/// 路由模板中的路径段
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathSegment {
+    /// 静态路径段
+    Static(String),
+    /// 路径参数，模板中写作 `{name}` 或 `<name>`，名称不含类型、可选和通配标记
+    Param(String),
+}
+
+impl PathSegment {
+    /// 将路由模板解析为路径段
+    ///
+    /// 逐段使用 [`crate::matcher::RoutePattern`] 的解析规则，无法解析的路径段视为静态段。
+    pub fn parse_template(template: &str) -> Vec<PathSegment> {
+        template
+            .trim_matches('/')
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .map(|segment| match matcher::parse_segment(segment) {
+                Ok(Segment::Param { name, .. } | Segment::CatchAll(name)) => PathSegment::Param(name),
+                _ => PathSegment::Static(segment.to_string()),
+            })
+            .collect()
+    }
+}
+
+/// HTTP方法枚举，序列化为方法名字符串
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(into = "String", try_from = "String")]
 pub enum HttpMethod {
+    #[default]
     GET,
     POST,
     PUT,
     DELETE,
     PATCH,
+    HEAD,
+    OPTIONS,
+    TRACE,
+    CONNECT,
+    /// 扩展方法，如 WebDAV 的 `PROPFIND`
+    Other(String),
 }
 
 impl HttpMethod {
-    /// 从字符串解析HTTP方法
-    pub fn from_str(method: &str) -> Self {
+    /// 判断字符串是否为可声明的扩展方法名：大写字母、数字、`-` 和 `_`
+    pub fn is_extension(method: &str) -> bool {
+        !method.is_empty()
+            && method
+                .bytes()
+                .all(|b| b.is_ascii_uppercase() || b.is_ascii_digit() || b == b'-' || b == b'_')
+    }
+
+    /// 判断字符串是否为合法的方法名（RFC 9110 中的 token）
+    pub fn is_valid_token(method: &str) -> bool {
+        !method.is_empty()
+            && method
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b))
+    }
+}
+
+impl std::str::FromStr for HttpMethod {
+    type Err = Error;
+
+    /// 从字符串解析HTTP方法，规则与 `#[uni_routing]` 宏相同
+    ///
+    /// 标准方法不区分大小写；扩展方法区分大小写，只能由大写字母、数字、`-` 和 `_` 组成，
+    /// 以免拼写错误的标准方法（如 `gett`）被当作扩展方法。
+    fn from_str(method: &str) -> Result<Self, Self::Err> {
         match method.to_uppercase().as_str() {
-            "GET" => HttpMethod::GET,
-            "POST" => HttpMethod::POST,
-            "PUT" => HttpMethod::PUT,
-            "DELETE" => HttpMethod::DELETE,
-            "PATCH" => HttpMethod::PATCH,
-            _ => HttpMethod::GET, // 默认为GET
+            "GET" => Ok(HttpMethod::GET),
+            "POST" => Ok(HttpMethod::POST),
+            "PUT" => Ok(HttpMethod::PUT),
+            "DELETE" => Ok(HttpMethod::DELETE),
+            "PATCH" => Ok(HttpMethod::PATCH),
+            "HEAD" => Ok(HttpMethod::HEAD),
+            "OPTIONS" => Ok(HttpMethod::OPTIONS),
+            "TRACE" => Ok(HttpMethod::TRACE),
+            "CONNECT" => Ok(HttpMethod::CONNECT),
+            _ if HttpMethod::is_extension(method) => Ok(HttpMethod::Other(method.to_string())),
+            _ => Err(Error::Other(format!("Invalid HTTP method: {}", method))),
         }
     }
 }
 
+impl TryFrom<String> for HttpMethod {
+    type Error = Error;
+
+    fn try_from(method: String) -> Result<Self, Self::Error> {
+        method.parse()
+    }
+}
+
+impl From<HttpMethod> for String {
+    fn from(method: HttpMethod) -> Self {
+        method.to_string()
+    }
+}
+
 impl std::fmt::Display for HttpMethod {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -48,10 +328,34 @@ impl std::fmt::Display for HttpMethod {
             HttpMethod::PUT => write!(f, "PUT"),
             HttpMethod::DELETE => write!(f, "DELETE"),
             HttpMethod::PATCH => write!(f, "PATCH"),
+            HttpMethod::HEAD => write!(f, "HEAD"),
+            HttpMethod::OPTIONS => write!(f, "OPTIONS"),
+            HttpMethod::TRACE => write!(f, "TRACE"),
+            HttpMethod::CONNECT => write!(f, "CONNECT"),
+            HttpMethod::Other(method) => write!(f, "{}", method),
         }
     }
 }
 
+/// 在路由表中查找与请求匹配的路由
+///
+/// 没有显式注册 HEAD 路由时，HEAD 请求由同一路径上的 GET 路由处理。该函数按顺序逐个比较，
+/// 适合一次性的查找；按请求分派时应将路由表编译为 [`crate::matcher::RouteMatcher`]。
+pub fn find_route<'a, I>(routes: I, method: &str, path: &str) -> Option<&'a RouteInfo>
+where
+    I: IntoIterator<Item = &'a RouteInfo>,
+    I::IntoIter: Clone,
+{
+    let routes = routes.into_iter();
+    routes.clone().find(|route| route.matches(method, path)).or_else(|| {
+        if method.eq_ignore_ascii_case("HEAD") {
+            routes.clone().find(|route| route.matches("GET", path))
+        } else {
+            None
+        }
+    })
+}
+
 /// 统一路由特征，用于适配不同的Web框架
 pub trait UnifiedRouter {
     /// 添加路由
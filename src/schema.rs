@@ -0,0 +1,100 @@
+//! 类型Schema模块，为OpenAPI文档描述请求体和响应体的JSON Schema
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// 类型的JSON Schema
+///
+/// `schema` 可以是内联的Schema，也可以是指向 `components/schemas` 的引用，
+/// 被引用的Schema保存在 `components` 中，生成文档时合并到组件列表。
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct TypeSchema {
+    /// 内联Schema或 `$ref` 引用
+    pub schema: serde_json::Value,
+    /// 需要注册到 `components/schemas` 的Schema
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub components: BTreeMap<String, serde_json::Value>,
+}
+
+impl TypeSchema {
+    /// 创建内联Schema
+    pub fn inline(schema: serde_json::Value) -> Self {
+        Self {
+            schema,
+            components: BTreeMap::new(),
+        }
+    }
+}
+
+/// 整数Schema，`format` 如 `int32`、`int64`
+pub fn integer(format: &str, unsigned: bool) -> TypeSchema {
+    let mut schema = serde_json::json!({ "type": "integer", "format": format });
+    if unsigned {
+        schema["minimum"] = serde_json::json!(0);
+    }
+    TypeSchema::inline(schema)
+}
+
+/// 浮点数Schema，`format` 如 `float`、`double`
+pub fn number(format: &str) -> TypeSchema {
+    TypeSchema::inline(serde_json::json!({ "type": "number", "format": format }))
+}
+
+/// 字符串Schema
+pub fn string() -> TypeSchema {
+    TypeSchema::inline(serde_json::json!({ "type": "string" }))
+}
+
+/// 布尔Schema
+pub fn boolean() -> TypeSchema {
+    TypeSchema::inline(serde_json::json!({ "type": "boolean" }))
+}
+
+/// 任意JSON值的Schema
+pub fn any() -> TypeSchema {
+    TypeSchema::inline(serde_json::json!({}))
+}
+
+/// 数组Schema
+pub fn array(items: TypeSchema) -> TypeSchema {
+    TypeSchema {
+        schema: serde_json::json!({ "type": "array", "items": items.schema }),
+        components: items.components,
+    }
+}
+
+/// 用户定义类型的Schema
+///
+/// 启用 `swagger` 特性时要求类型实现 `utoipa::ToSchema`，生成指向
+/// `components/schemas` 的引用，字段中引用的其他类型一并收集到组件；
+/// 否则退化为以类型名为标题的对象Schema。
+#[cfg(feature = "swagger")]
+pub fn component<T>() -> TypeSchema
+where
+    T: utoipa::ToSchema,
+{
+    let name = T::name().into_owned();
+    let mut nested = Vec::new();
+    T::schemas(&mut nested);
+    let components = nested
+        .into_iter()
+        .chain(std::iter::once((name.clone(), T::schema())))
+        .map(|(name, schema)| (name, serde_json::to_value(schema).unwrap_or_default()))
+        .collect();
+    TypeSchema {
+        schema: serde_json::json!({ "$ref": format!("#/components/schemas/{}", name) }),
+        components,
+    }
+}
+
+/// 用户定义类型的Schema
+///
+/// 启用 `swagger` 特性时要求类型实现 `utoipa::ToSchema`，生成指向
+/// `components/schemas` 的引用；否则退化为以类型名为标题的对象Schema，
+/// 标题为去掉模块路径和泛型参数的类型名（`Page<my::User>` 为 `Page`）。
+#[cfg(not(feature = "swagger"))]
+pub fn component<T>() -> TypeSchema {
+    let full_name = std::any::type_name::<T>();
+    let name = full_name.split('<').next().unwrap_or(full_name).rsplit("::").next().unwrap_or_default();
+    TypeSchema::inline(serde_json::json!({ "type": "object", "title": name }))
+}
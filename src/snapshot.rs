@@ -0,0 +1,132 @@
+//! 路由快照模块，将注册表保存为 JSON 或 TOML，便于在 CI 中对比不同版本的路由
+//!
+//! ```ignore
+//! get_global_registry().snapshot().save("routes.toml")?;
+//! let registry = RouteSnapshot::load("routes.toml")?.into_registry()?;
+//! ```
+
+use std::path::Path;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::registry::RouteRegistry;
+use crate::routing::RouteInfo;
+use crate::Error;
+
+/// 快照的文件格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SnapshotFormat {
+    /// JSON
+    #[default]
+    Json,
+    /// TOML，路由保存为 `[[routes]]` 表数组
+    Toml,
+}
+
+impl SnapshotFormat {
+    /// 根据文件扩展名判断格式，`.toml` 以外的文件都按 JSON 处理
+    pub fn from_path(path: impl AsRef<Path>) -> Self {
+        match path.as_ref().extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("toml") => SnapshotFormat::Toml,
+            _ => SnapshotFormat::Json,
+        }
+    }
+}
+
+impl FromStr for SnapshotFormat {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "json" => Ok(SnapshotFormat::Json),
+            "toml" => Ok(SnapshotFormat::Toml),
+            _ => Err(Error::Other(format!("Invalid snapshot format: {}, expected json or toml", s))),
+        }
+    }
+}
+
+/// 路由注册表快照，路由按注册顺序保存
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct RouteSnapshot {
+    /// 路由列表
+    #[serde(default)]
+    pub routes: Vec<RouteInfo>,
+}
+
+impl RouteSnapshot {
+    /// 创建快照
+    pub fn new(routes: Vec<RouteInfo>) -> Self {
+        Self { routes }
+    }
+
+    /// 按指定格式序列化
+    ///
+    /// TOML 不支持 `null`，Schema 或示例中包含 `null` 的路由只能保存为 JSON。
+    pub fn to_string(&self, format: SnapshotFormat) -> Result<String, Error> {
+        match format {
+            SnapshotFormat::Json => serde_json::to_string_pretty(self)
+                .map_err(|e| Error::Other(format!("Failed to serialize route snapshot: {}", e))),
+            SnapshotFormat::Toml => toml::to_string(self)
+                .map_err(|e| Error::Other(format!("Failed to serialize route snapshot as TOML: {}", e))),
+        }
+    }
+
+    /// 按指定格式解析
+    pub fn parse(content: &str, format: SnapshotFormat) -> Result<Self, Error> {
+        match format {
+            SnapshotFormat::Json => serde_json::from_str(content)
+                .map_err(|e| Error::Other(format!("Invalid route snapshot: {}", e))),
+            SnapshotFormat::Toml => toml::from_str(content)
+                .map_err(|e| Error::Other(format!("Invalid route snapshot: {}", e))),
+        }
+    }
+
+    /// 序列化为 JSON
+    pub fn to_json(&self) -> Result<String, Error> {
+        self.to_string(SnapshotFormat::Json)
+    }
+
+    /// 从 JSON 解析
+    pub fn from_json(content: &str) -> Result<Self, Error> {
+        Self::parse(content, SnapshotFormat::Json)
+    }
+
+    /// 序列化为 TOML
+    pub fn to_toml(&self) -> Result<String, Error> {
+        self.to_string(SnapshotFormat::Toml)
+    }
+
+    /// 从 TOML 解析
+    pub fn from_toml(content: &str) -> Result<Self, Error> {
+        Self::parse(content, SnapshotFormat::Toml)
+    }
+
+    /// 从文件读取，格式由扩展名决定
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| Error::Other(format!("Failed to read {}: {}", path.display(), e)))?;
+        Self::parse(&content, SnapshotFormat::from_path(path))
+    }
+
+    /// 写入文件，格式由扩展名决定
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), Error> {
+        let path = path.as_ref();
+        let content = self.to_string(SnapshotFormat::from_path(path))?;
+        std::fs::write(path, content).map_err(|e| Error::Other(format!("Failed to write {}: {}", path.display(), e)))
+    }
+
+    /// 按快照中的顺序注册到新的注册表，路由模板无效或重复时返回错误
+    pub fn into_registry(self) -> Result<RouteRegistry, Error> {
+        let registry = RouteRegistry::new();
+        self.routes.into_iter().try_for_each(|route| registry.register_route(route))?;
+        Ok(registry)
+    }
+}
+
+impl From<Vec<RouteInfo>> for RouteSnapshot {
+    fn from(routes: Vec<RouteInfo>) -> Self {
+        Self::new(routes)
+    }
+}
@@ -1,16 +1,24 @@
 //! Swagger/OpenAPI文档生成功能模块
 
+use crate::openapi::{OpenApi, OpenApiBuilder};
 use crate::routing::RouteInfo;
 
 /// OpenAPI文档生成器
 pub struct SwaggerGenerator {
+    builder: OpenApiBuilder,
     routes: Vec<RouteInfo>,
 }
 
 impl SwaggerGenerator {
     /// 创建新的文档生成器
     pub fn new() -> Self {
+        Self::with_builder(OpenApiBuilder::new("Uni Routing API", "1.0.0"))
+    }
+
+    /// 使用已配置信息、服务器和安全方案的构建器创建文档生成器
+    pub fn with_builder(builder: OpenApiBuilder) -> Self {
         Self {
+            builder,
             routes: Vec::new(),
         }
     }
@@ -24,12 +32,15 @@ impl SwaggerGenerator {
     pub fn route_count(&self) -> usize {
         self.routes.len()
     }
+
+    /// 生成OpenAPI文档
+    pub fn openapi(&self) -> OpenApi {
+        self.builder.clone().routes(self.routes.clone()).build()
+    }
     
     /// 生成OpenAPI规范
     pub fn generate_openapi_spec(&self) -> String {
-        // 这里应该是实际的OpenAPI规范生成逻辑
-        // 目前只是一个占位符实现
-        r#"{"openapi": "3.0.0", "info": {"title": "Uni Routing API", "version": "1.0.0"}}"#.to_string()
+        self.openapi().to_json_string()
     }
     
     /// 生成Swagger UI HTML
@@ -40,11 +51,11 @@ impl SwaggerGenerator {
 <html>
 <head>
     <title>Uni Routing API Documentation</title>
-    <link rel="stylesheet" type="text/css" href="https://unpkg.com/swagger-ui-dist@4/swagger-ui.css" />
+    <link rel="stylesheet" type="text/css" href="https://unpkg.com/swagger-ui-dist@5/swagger-ui.css" />
 </head>
 <body>
     <div id="swagger-ui"></div>
-    <script src="https://unpkg.com/swagger-ui-dist@4/swagger-ui-bundle.js"></script>
+    <script src="https://unpkg.com/swagger-ui-dist@5/swagger-ui-bundle.js"></script>
     <script>
         SwaggerUIBundle({
             url: '/swagger/openapi.json',
@@ -0,0 +1,106 @@
+//! URL生成模块（反向路由），将参数代入路由模板生成请求路径
+//!
+//! ```ignore
+//! #[uni_routing(route = "/api/users/{id}", method = "GET")]
+//! async fn get_user_by_id(path: Path<u64>) {}
+//!
+//! // 宏为每个处理函数生成类型化的URL构建函数
+//! assert_eq!(get_user_by_id_url(42), "/api/users/42");
+//! // 按操作ID从注册表查找
+//! assert_eq!(registry.url_for("get_user_by_id", &[("id", "42")])?, "/api/users/42");
+//! ```
+//!
+//! 参数值按路径段进行百分号编码，通配参数中的 `/` 保留为路径分隔符。
+//!
+//! 类型化的构建函数在运行时按操作ID查找全局注册表，路由经分组或配置改变路径后生成注册后的路径；
+//! 分组前缀引入了处理函数签名中没有的参数（如 `/orgs/{org}`）时，应使用 [`RouteRegistry::url_for`]。
+//!
+//! [`RouteRegistry::url_for`]: crate::registry::RouteRegistry::url_for
+
+use std::fmt::Write;
+
+use crate::matcher::{RoutePattern, Segment};
+use crate::Error;
+
+/// 对单个路径段进行百分号编码，只保留 RFC 3986 中的非保留字符
+///
+/// `.` 与 `..` 会被客户端当作相对路径处理，因此整体编码。
+pub fn encode_segment(value: &str) -> String {
+    if value == "." || value == ".." {
+        return value.replace('.', "%2E");
+    }
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => encoded.push(byte as char),
+            _ => {
+                let _ = write!(encoded, "%{:02X}", byte);
+            }
+        }
+    }
+    encoded
+}
+
+/// 将参数代入路由模板，生成请求路径
+///
+/// 缺少必需参数或提供了模板中没有的参数时返回错误；省略的可选参数及其后的路径段不出现在结果中。
+pub fn build<S: AsRef<str>>(template: &str, params: &[(&str, S)]) -> Result<String, Error> {
+    let pattern = RoutePattern::parse(template)?;
+    let value = |name: &str| params.iter().find(|(key, _)| *key == name).map(|(_, value)| value.as_ref());
+
+    let mut errors: Vec<String> = params
+        .iter()
+        .filter(|(name, _)| !pattern.param_names().any(|param| param == *name))
+        .map(|(name, _)| format!("unknown path parameter `{}`", name))
+        .collect();
+    let mut path = String::new();
+    for segment in pattern.segments() {
+        match segment {
+            Segment::Static(value) => {
+                path.push('/');
+                path.push_str(value);
+            }
+            Segment::Param { name, optional, .. } => match value(name) {
+                Some(value) => {
+                    path.push('/');
+                    path.push_str(&encode_segment(value));
+                }
+                None if *optional => {}
+                None => errors.push(format!("missing path parameter `{}`", name)),
+            },
+            Segment::CatchAll(name) => match value(name) {
+                Some(value) => {
+                    for part in value.split('/').filter(|part| !part.is_empty()) {
+                        path.push('/');
+                        path.push_str(&encode_segment(part));
+                    }
+                }
+                None => errors.push(format!("missing path parameter `{}`", name)),
+            },
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err(Error::Other(format!("Cannot build URL for `{}`: {}", template, errors.join(", "))));
+    }
+    if path.is_empty() {
+        path.push('/');
+    }
+    Ok(path)
+}
+
+/// 宏生成的URL构建函数使用：按操作ID查找全局注册表中的路由模板，未注册时使用声明的模板
+///
+/// # Panics
+///
+/// 注册后的模板需要构建函数无法提供的参数（如分组前缀中的参数）时 panic。
+#[doc(hidden)]
+pub fn build_registered(operation_id: &str, declared: &str, params: &[(&str, String)]) -> String {
+    let registered = crate::registry::get_global_registry()
+        .get_route_by_operation(operation_id)
+        .map(|route| route.full_path());
+    let template = registered.as_deref().unwrap_or(declared);
+    build(template, params).unwrap_or_else(|err| {
+        panic!("{}; use RouteRegistry::url_for to supply parameters added by route groups", err)
+    })
+}
@@ -0,0 +1,168 @@
+//! API版本模块，支持 URL 前缀、请求头和媒体类型三种版本协商方式
+//!
+//! 带有 `version` 的路由在框架中统一挂载到 `/{version}` 前缀下（见 [`RouteInfo::full_path`]），
+//! 使用请求头或媒体类型协商时，由中间件链将请求改写到对应版本的路径。
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::group::join_path;
+use crate::openapi::{OpenApi, OpenApiBuilder, Parameter};
+use crate::routing::RouteInfo;
+
+/// 默认的版本请求头
+pub const VERSION_HEADER: &str = "Accept-Version";
+
+/// 版本协商方式
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum VersioningStrategy {
+    /// 版本作为路径前缀，例如 `/v2/users`
+    #[default]
+    UrlPrefix,
+    /// 版本由请求头指定，例如 `Accept-Version: v2`
+    Header(String),
+    /// 版本由 `Accept` 中的厂商媒体类型指定，例如 `application/vnd.acme.v2+json`
+    MediaType(String),
+}
+
+impl VersioningStrategy {
+    /// 使用 [`VERSION_HEADER`] 请求头
+    pub fn header() -> Self {
+        Self::Header(VERSION_HEADER.to_string())
+    }
+
+    /// 使用指定厂商名的媒体类型
+    pub fn media_type(vendor: &str) -> Self {
+        Self::MediaType(vendor.to_string())
+    }
+}
+
+/// API版本配置
+#[derive(Debug, Clone, Default)]
+pub struct Versioning {
+    strategy: VersioningStrategy,
+    default_version: Option<String>,
+}
+
+impl Versioning {
+    /// 使用版本协商方式创建配置
+    pub fn new(strategy: VersioningStrategy) -> Self {
+        Self {
+            strategy,
+            default_version: None,
+        }
+    }
+
+    /// 设置请求未指定版本时使用的版本（仅用于请求头和媒体类型协商）
+    pub fn default_version(mut self, version: &str) -> Self {
+        self.default_version = Some(version.to_string());
+        self
+    }
+
+    /// 版本协商方式
+    pub fn strategy(&self) -> &VersioningStrategy {
+        &self.strategy
+    }
+
+    /// 从请求头中解析客户端请求的版本，未指定时返回默认版本
+    ///
+    /// 使用 URL 前缀时版本由路径决定，总是返回 `None`。
+    pub fn requested_version<'a, I>(&self, headers: I) -> Option<String>
+    where
+        I: IntoIterator<Item = (&'a str, &'a str)>,
+    {
+        let requested = match &self.strategy {
+            VersioningStrategy::UrlPrefix => return None,
+            VersioningStrategy::Header(header) => headers
+                .into_iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case(header))
+                .map(|(_, value)| value.trim().to_string())
+                .filter(|value| !value.is_empty()),
+            VersioningStrategy::MediaType(vendor) => headers
+                .into_iter()
+                .filter(|(name, _)| name.eq_ignore_ascii_case("accept"))
+                .flat_map(|(_, value)| value.split(','))
+                .find_map(|media_type| media_type_version(vendor, media_type)),
+        };
+        requested.or_else(|| self.default_version.clone())
+    }
+
+    /// 请求头或媒体类型协商时，请求应分派到的版本路径
+    pub fn dispatch_path(&self, path: &str, version: &str) -> String {
+        join_path(version, path)
+    }
+
+    /// 为每个版本生成一份OpenAPI文档，未声明版本的路由出现在所有文档中
+    pub fn openapi_documents(&self, builder: &OpenApiBuilder, routes: &[RouteInfo]) -> BTreeMap<String, OpenApi> {
+        versions(routes)
+            .into_iter()
+            .map(|version| {
+                let spec = self.openapi(builder, routes, &version);
+                (version, spec)
+            })
+            .collect()
+    }
+
+    /// 生成指定版本的OpenAPI文档
+    pub fn openapi(&self, builder: &OpenApiBuilder, routes: &[RouteInfo], version: &str) -> OpenApi {
+        let routes = routes
+            .iter()
+            .filter(|route| route.version.as_deref().is_none_or(|v| v == version))
+            .cloned();
+        // 请求头与媒体类型协商时，文档中的路径不带版本前缀
+        let routes: Vec<RouteInfo> = match self.strategy {
+            VersioningStrategy::UrlPrefix => routes.collect(),
+            _ => routes.map(|route| RouteInfo { version: None, ..route }).collect(),
+        };
+        let mut spec = builder.clone().version(version).routes(routes).build();
+
+        // 请求头与媒体类型协商需要在文档中说明如何选择版本
+        let operations = spec.paths.values_mut().flat_map(|item| item.operations.values_mut());
+        match &self.strategy {
+            VersioningStrategy::UrlPrefix => {}
+            VersioningStrategy::Header(header) => {
+                for operation in operations {
+                    operation.parameters.push(Parameter {
+                        name: header.clone(),
+                        location: "header".to_string(),
+                        required: self.default_version.is_none(),
+                        schema: serde_json::json!({ "type": "string", "enum": [version] }),
+                    });
+                }
+            }
+            VersioningStrategy::MediaType(vendor) => {
+                let media_type = format!("application/vnd.{}.{}+json", vendor, version);
+                for operation in operations {
+                    let request = operation.request_body.iter_mut().map(|body| &mut body.content);
+                    let responses = operation.responses.values_mut().map(|response| &mut response.content);
+                    for content in request.chain(responses) {
+                        if let Some(media) = content.remove("application/json") {
+                            content.insert(media_type.clone(), media);
+                        }
+                    }
+                }
+            }
+        }
+        spec
+    }
+}
+
+/// 路由中声明的所有版本，按字典序排列
+pub fn versions(routes: &[RouteInfo]) -> Vec<String> {
+    routes
+        .iter()
+        .filter_map(|route| route.version.clone())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+/// 从 `application/vnd.{vendor}.{version}+json` 中解析版本
+fn media_type_version(vendor: &str, media_type: &str) -> Option<String> {
+    let essence = media_type.split(';').next()?.trim();
+    let version = essence
+        .strip_prefix("application/vnd.")?
+        .strip_prefix(vendor)?
+        .strip_prefix('.')?;
+    let version = version.split_once('+').map_or(version, |(version, _)| version);
+    (!version.is_empty()).then(|| version.to_string())
+}
@@ -0,0 +1,18 @@
+//! 集成测试共用的模拟提取器和路由构造函数
+
+#![allow(dead_code)]
+
+use uni_routing::routing::{HttpMethod, RouteInfo};
+
+/// 模拟框架提供的 `Path<T>` 提取器
+pub struct Path<T>(pub T);
+
+/// 只设置方法和路径的路由，操作ID为 `<方法> <路径>`
+pub fn route(method: HttpMethod, path: &str) -> RouteInfo {
+    RouteInfo {
+        path: path.to_string(),
+        operation_id: Some(format!("{} {}", method, path)),
+        method,
+        ..Default::default()
+    }
+}
@@ -0,0 +1,7 @@
+//! 宏编译错误的测试，预期的错误信息见 `tests/ui/*.stderr`
+
+#[test]
+fn test_macro_compile_errors() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,297 @@
+//! 路由覆盖配置的单元测试
+
+#![allow(dead_code)]
+
+mod common;
+
+use common::Path;
+use std::sync::Arc;
+use uni_routing::auth::JwtToken;
+use uni_routing::config::{ConfigFormat, RouteConfig};
+use uni_routing::middleware::{MiddlewareChain, MiddlewareFactory, MiddlewareSpec};
+use uni_routing::registry::RouteRegistry;
+use uni_routing::routing::{HttpMethod, RouteInfo};
+use uni_routing::Error;
+use uni_routing_macros::uni_routing;
+
+#[uni_routing(route = "/api/users", method = "GET", middleware = [rate_limit("10/s")])]
+async fn list_users() {}
+
+#[uni_routing(route = "/api/users", method = "POST", auth_policy = "role:admin")]
+async fn create_user() {}
+
+#[uni_routing(route = "/api/users/{id}", method = "GET", auth_policy = "role:user")]
+async fn get_user(_path: Path<u64>) {}
+
+#[uni_routing(route = "/api/users/{id}", method = "DELETE", auth_policy = "role:admin")]
+async fn delete_user(_path: Path<u64>) {}
+
+fn routes() -> Vec<RouteInfo> {
+    vec![
+        __register_route_list_users(),
+        __register_route_create_user(),
+        __register_route_get_user(),
+        __register_route_delete_user(),
+    ]
+}
+
+const TOML_CONFIG: &str = r#"
+[[routes]]
+operation_id = "list_users"
+rate_limit = "100/min"
+rate_limit_key = "subject"
+
+[[routes]]
+operation_id = "create_user"
+disabled = true
+
+[[routes]]
+method = "GET"
+path = "/api/users/{id}"
+public = true
+remap = "/api/people/{id}"
+
+[[routes]]
+operation_id = "delete_user"
+auth_policy = "role:superuser"
+"#;
+
+#[test]
+fn test_apply_overrides() {
+    let applied = RouteConfig::from_toml(TOML_CONFIG).unwrap().apply(routes()).unwrap();
+
+    let paths: Vec<String> = applied.routes.iter().map(|route| format!("{} {}", route.method, route.path)).collect();
+    assert_eq!(paths, vec!["GET /api/users", "GET /api/people/{id}", "DELETE /api/users/{id}"]);
+    assert_eq!(applied.routes[0].middleware, vec![MiddlewareSpec::new(
+        "rate_limit",
+        vec!["100/min".to_string(), "subject".to_string()]
+    )]);
+    assert!(applied.routes[1].auth_policy.is_none());
+    assert_eq!(applied.routes[2].auth_policy.as_ref().unwrap().policy_expr, "role:superuser");
+
+    assert_eq!(applied.disabled.len(), 1);
+    assert_eq!(applied.disabled[0].operation_id.as_deref(), Some("create_user"));
+    assert_eq!(applied.remapped.len(), 1);
+    assert_eq!(applied.remapped[0].original.path, "/api/users/{id}");
+    // 宏记录的来源保留在重映射后的路由上
+    assert_eq!(applied.remapped[0].route.source, applied.remapped[0].original.source);
+}
+
+#[test]
+fn test_yaml_config() {
+    let config = RouteConfig::from_yaml(
+        r#"
+routes:
+  - operation_id: create_user
+    disabled: true
+  - method: DELETE
+    path: /api/users/{id}
+    public: true
+"#,
+    )
+    .unwrap();
+    assert_eq!(config, RouteConfig::from_toml(r#"
+        [[routes]]
+        operation_id = "create_user"
+        disabled = true
+
+        [[routes]]
+        method = "DELETE"
+        path = "/api/users/{id}"
+        public = true
+    "#).unwrap());
+    assert_eq!(config.apply(routes()).unwrap().routes.len(), 3);
+    assert_eq!(ConfigFormat::from_path("routes.yml"), ConfigFormat::Yaml);
+}
+
+#[test]
+fn test_validation_reports_all_errors() {
+    // 未知字段在解析时被拒绝
+    assert!(RouteConfig::from_toml("[[routes]]\noperation_id = \"list_users\"\ndisable = true").is_err());
+
+    let config = RouteConfig::from_toml(
+        r#"
+        [[routes]]
+        operation_id = "missing"
+        disabled = true
+
+        [[routes]]
+        operation_id = "list_users"
+        rate_limit = "lots"
+
+        [[routes]]
+        method = "GET"
+        path = "/api/users/{id}"
+        remap = "/api/people/{person_id}"
+
+        [[routes]]
+        operation_id = "delete_user"
+        remap = "/api/users"
+
+        [[routes]]
+        operation_id = "create_user"
+
+        [[routes]]
+        path = "/api/users"
+        disabled = true
+        "#,
+    )
+    .unwrap();
+    let Err(Error::Other(message)) = config.apply(routes()) else {
+        panic!("invalid configuration must be rejected");
+    };
+    let lines: Vec<&str> = message.lines().collect();
+    assert_eq!(lines[0], "Invalid route configuration:");
+    assert_eq!(lines[1], "  - operation `missing`: matches no route");
+    assert_eq!(lines[2], "  - operation `list_users`: Invalid rate limit: lots");
+    assert_eq!(lines[3], "  - operation `create_user`: override does not change anything");
+    assert_eq!(lines[4], "  - ? /api/users: operation_id or both method and path are required");
+    assert_eq!(lines[5], "  - GET /api/users/{id}: remap `/api/people/{person_id}` must use the same parameters as `/api/users/{id}`");
+    assert_eq!(lines[6], "  - operation `delete_user`: remap `/api/users` must use the same parameters as `/api/users/{id}`");
+    assert_eq!(lines.len(), 7);
+}
+
+#[test]
+fn test_remap_conflicts_are_rejected() {
+    // 重映射后的路由表作为整体检查冲突
+    let config = RouteConfig::from_toml(
+        r#"
+        [[routes]]
+        operation_id = "delete_user"
+        remap = "/api/accounts/{id}"
+
+        [[routes]]
+        operation_id = "get_user"
+        remap = "/api/users/{id}"
+        "#,
+    )
+    .unwrap();
+    assert!(config.apply(routes()).is_ok());
+
+    let mut duplicated = routes();
+    duplicated.push(RouteInfo {
+        path: "/api/people/{id}".to_string(),
+        method: HttpMethod::GET,
+        ..Default::default()
+    });
+    let config = RouteConfig::from_toml("[[routes]]\noperation_id = \"get_user\"\nremap = \"/api/people/{id}\"").unwrap();
+    let Err(Error::Other(message)) = config.apply(duplicated) else {
+        panic!("remapping onto an existing route must fail");
+    };
+    assert!(message.contains("duplicate routes: GET /api/people/{id}"));
+}
+
+#[test]
+fn test_registry_apply_config() {
+    let registry = RouteRegistry::new();
+    for route in routes() {
+        registry.register_route(route).unwrap();
+    }
+    let config = RouteConfig::from_toml(TOML_CONFIG).unwrap();
+
+    // 无效配置不修改注册表
+    assert!(registry.apply_config(&RouteConfig::from_toml("[[routes]]\noperation_id = \"missing\"\ndisabled = true").unwrap()).is_err());
+    assert_eq!(registry.get_routes().len(), 4);
+
+    registry.apply_config(&config).unwrap();
+    assert_eq!(registry.get_routes().len(), 3);
+    assert!(registry.get_route("/api/people/1", HttpMethod::GET).is_some());
+    assert!(registry.get_route("/api/users", HttpMethod::POST).is_none());
+
+    registry.freeze().unwrap();
+    assert!(registry.apply_config(&config).is_err());
+}
+
+#[test]
+fn test_chain_blocks_and_rewrites() {
+    let applied = RouteConfig::from_toml(TOML_CONFIG).unwrap().apply(routes()).unwrap();
+    let chain = MiddlewareChain::new().with_config(&applied, &MiddlewareFactory::default()).unwrap();
+
+    // 重映射的路由改写到处理函数挂载的原路径
+    let mut context = chain.build_context("GET", "/api/people/42", []);
+    assert_eq!(context.path, "/api/users/42");
+    assert_eq!(context.route.as_ref().unwrap().path, "/api/people/{id}");
+    assert!(chain.process_request(&mut context).is_none());
+
+    // 原路径与被禁用的路由返回 404
+    for (method, path) in [("GET", "/api/users/42"), ("POST", "/api/users")] {
+        let mut context = chain.build_context(method, path, []);
+        assert!(context.route.is_none());
+        assert_eq!(chain.process_request(&mut context).unwrap().status, 404);
+    }
+
+    // 其他路由不改写，仍按覆盖后的认证策略认证
+    let mut context = chain.build_context("DELETE", "/api/users/42", []);
+    assert_eq!(context.path, "/api/users/42");
+    assert_eq!(chain.process_request(&mut context).unwrap().status, 401);
+}
+
+#[test]
+fn test_chain_enforces_auth_overrides() {
+    let decode = |token: &str| {
+        let payload = [("sub".to_string(), serde_json::json!(token))].into_iter().collect();
+        Some(JwtToken { payload })
+    };
+    let config = RouteConfig::from_toml(
+        r#"
+[[routes]]
+operation_id = "list_users"
+auth_policy = "user:alice"
+
+[[routes]]
+operation_id = "get_user"
+public = true
+"#,
+    )
+    .unwrap();
+    let status = |chain: &MiddlewareChain, method: &str, path: &str, token: Option<&str>| {
+        let authorization = token.map(|token| format!("Bearer {}", token));
+        let headers = authorization.as_deref().map(|value| ("Authorization", value));
+        let mut context = chain.build_context(method, path, headers);
+        chain.process_request(&mut context).map_or(200, |response| response.status)
+    };
+
+    let chain = MiddlewareChain::new()
+        .with_token_decoder(Arc::new(decode))
+        .with_routes(routes(), &MiddlewareFactory::default())
+        .unwrap();
+    assert_eq!(status(&chain, "GET", "/api/users", None), 200);
+    assert_eq!(status(&chain, "GET", "/api/users/1", None), 401);
+
+    // 覆盖配置添加的认证策略在运行时生效，`public` 取消宏声明的认证
+    let applied = config.apply(routes()).unwrap();
+    let chain = MiddlewareChain::new()
+        .with_token_decoder(Arc::new(decode))
+        .with_config(&applied, &MiddlewareFactory::default())
+        .unwrap();
+    assert_eq!(status(&chain, "GET", "/api/users", None), 401);
+    assert_eq!(status(&chain, "GET", "/api/users", Some("bob")), 403);
+    assert_eq!(status(&chain, "GET", "/api/users", Some("alice")), 200);
+    assert_eq!(status(&chain, "GET", "/api/users/1", None), 200);
+}
+
+#[actix_web::test]
+async fn test_actix_disabled_and_remapped_routes() {
+    use actix_web::{test, web, App, HttpResponse};
+    use uni_routing::frameworks::actix::UniMiddleware;
+
+    let applied = RouteConfig::from_toml(TOML_CONFIG).unwrap().apply(routes()).unwrap();
+    let chain = MiddlewareChain::new().with_config(&applied, &MiddlewareFactory::default()).unwrap();
+    let app = test::init_service(
+        App::new()
+            .wrap(UniMiddleware::new(chain))
+            .route("/api/users", web::post().to(|| async { HttpResponse::Created().finish() }))
+            .route("/api/users/{id}", web::get().to(|id: web::Path<u64>| async move {
+                HttpResponse::Ok().body(format!("user:{}", id))
+            })),
+    )
+    .await;
+
+    let req = test::TestRequest::get().uri("/api/people/5?verbose=1").to_request();
+    assert_eq!(test::call_and_read_body(&app, req).await, "user:5");
+
+    let req = test::TestRequest::get().uri("/api/users/5").to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 404);
+    let req = test::TestRequest::post().uri("/api/users").to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 404);
+}
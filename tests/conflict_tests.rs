@@ -0,0 +1,105 @@
+//! 路由冲突检测的单元测试
+
+#![allow(dead_code)]
+
+mod common;
+
+use common::{Path, route};
+use uni_routing::conflict::{detect_conflicts, ConflictKind};
+use uni_routing::registry::RouteRegistry;
+use uni_routing::routing::{HttpMethod, RouteInfo};
+use uni_routing::Error;
+use uni_routing_macros::uni_routing;
+
+/// `get_user` 的路由属性所在的行
+const GET_USER_LINE: u32 = line!() + 1;
+#[uni_routing(route = "/users/{id}", method = "GET")]
+async fn get_user(_path: Path<u64>) {}
+
+#[uni_routing(route = "/users/<user_id>", method = "GET")]
+async fn get_user_again(_path: Path<u64>) {}
+
+#[uni_routing(route = "/users/me", method = "GET")]
+async fn get_current_user() {}
+
+fn kinds(routes: &[RouteInfo]) -> Vec<ConflictKind> {
+    detect_conflicts(routes).iter().map(|conflict| conflict.kind).collect()
+}
+
+#[test]
+fn test_macro_records_source() {
+    let source = __register_route_get_user().source.unwrap();
+    assert_eq!(source.handler, "conflict_tests::get_user");
+    assert!(source.file.ends_with("conflict_tests.rs"));
+    assert_eq!(source.line, GET_USER_LINE);
+}
+
+#[test]
+fn test_conflict_kinds() {
+    // 参数名不同的等价模板
+    assert_eq!(kinds(&[route(HttpMethod::GET, "/users/{id}"), route(HttpMethod::GET, "/users/{name}")]), vec![ConflictKind::Duplicate]);
+    // 由优先级规则确定的重叠
+    assert_eq!(kinds(&[route(HttpMethod::GET, "/users/{id}"), route(HttpMethod::GET, "/users/me")]), vec![ConflictKind::Overlap]);
+    assert_eq!(kinds(&[route(HttpMethod::GET, "/files/{*path}"), route(HttpMethod::GET, "/files/{name}")]), vec![ConflictKind::Overlap]);
+    assert_eq!(kinds(&[route(HttpMethod::GET, "/users/{id:u64}"), route(HttpMethod::GET, "/users/{name}")]), vec![ConflictKind::Overlap]);
+    // 由注册顺序决定
+    assert_eq!(kinds(&[route(HttpMethod::GET, "/users/{id:u32}"), route(HttpMethod::GET, "/users/{id:i64}")]), vec![ConflictKind::Ambiguous]);
+    assert_eq!(kinds(&[route(HttpMethod::GET, "/archive/{year?}"), route(HttpMethod::GET, "/archive")]), vec![ConflictKind::Ambiguous]);
+
+    // 不重叠
+    assert!(kinds(&[route(HttpMethod::GET, "/users/{id}"), route(HttpMethod::POST, "/users/{id}")]).is_empty());
+    assert!(kinds(&[route(HttpMethod::GET, "/users/{id:u64}"), route(HttpMethod::GET, "/users/{flag:bool}")]).is_empty());
+    assert!(kinds(&[route(HttpMethod::GET, "/users/{id}"), route(HttpMethod::GET, "/users/{id}/posts")]).is_empty());
+    assert!(kinds(&[
+        RouteInfo { version: Some("v1".to_string()), ..route(HttpMethod::GET, "/users/{id}") },
+        RouteInfo { version: Some("v2".to_string()), ..route(HttpMethod::GET, "/users/{id}") },
+    ])
+    .is_empty());
+}
+
+#[test]
+fn test_freeze_fails_fast_on_duplicates() {
+    let registry = RouteRegistry::new();
+    registry.register_route(__register_route_get_user()).unwrap();
+    registry.register_route(__register_route_get_user_again()).unwrap();
+    registry.register_route(__register_route_get_current_user()).unwrap();
+
+    let Err(Error::Other(message)) = registry.freeze() else {
+        panic!("conflicting routes must not freeze");
+    };
+    assert!(message.contains("duplicate routes: GET /users/{id} [conflict_tests::get_user (tests"));
+    assert!(message.contains("conflict_tests::get_user_again (tests"));
+    let location = format!("conflict_tests.rs:{})]", GET_USER_LINE);
+    assert!(message.contains(&location), "{}", message);
+    // 只作为警告的重叠不出现在错误中
+    assert!(!message.contains("get_current_user"));
+    assert!(!registry.is_frozen());
+
+    let conflicts = registry.conflicts();
+    assert_eq!(conflicts.len(), 3);
+    assert_eq!(conflicts.iter().filter(|conflict| conflict.kind.is_fatal()).count(), 1);
+}
+
+#[test]
+fn test_freeze_succeeds_with_overlaps() {
+    let registry = RouteRegistry::new();
+    registry.register_route(__register_route_get_user()).unwrap();
+    registry.register_route(__register_route_get_current_user()).unwrap();
+    assert_eq!(registry.freeze().unwrap().len(), 2);
+    assert_eq!(registry.get_route("/users/me", HttpMethod::GET).unwrap().operation_id.as_deref(), Some("get_current_user"));
+}
+
+#[test]
+fn test_duplicate_registration_reports_sources() {
+    let registry = RouteRegistry::new();
+    registry.register_route(__register_route_get_user()).unwrap();
+    let duplicate = RouteInfo {
+        path: "/users/{id}".to_string(),
+        ..__register_route_get_user_again()
+    };
+    let Err(Error::Other(message)) = registry.register_route(duplicate) else {
+        panic!("duplicate registration must fail");
+    };
+    assert!(message.contains("get_user_again"));
+    assert!(message.contains("conflict_tests::get_user ("));
+}
@@ -0,0 +1,174 @@
+//! CORS中间件的单元测试
+
+use uni_routing::middleware::cors::AllowedOrigin;
+use uni_routing::middleware::{AuthMiddleware, CorsMiddleware, Middleware, MiddlewareChain, MiddlewareContext};
+use uni_routing::auth::AuthPolicy;
+use uni_routing::routing::{HttpMethod, RouteInfo};
+use std::sync::Arc;
+
+fn header<'a>(headers: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case(name))
+        .map(|(_, value)| value.as_str())
+}
+
+fn user_routes() -> Vec<RouteInfo> {
+    vec![
+        RouteInfo { path: "/api/users".to_string(), method: HttpMethod::GET, ..Default::default() },
+        RouteInfo { path: "/api/users".to_string(), method: HttpMethod::POST, ..Default::default() },
+        RouteInfo { path: "/api/users/{id}".to_string(), method: HttpMethod::DELETE, ..Default::default() },
+    ]
+}
+
+#[test]
+fn test_allowed_origin_parse() {
+    assert!(AllowedOrigin::parse("*").allows("https://anything.dev"));
+    assert!(AllowedOrigin::parse("https://a.com").allows("https://a.com"));
+    assert!(!AllowedOrigin::parse("https://a.com").allows("https://b.com"));
+
+    let wildcard = AllowedOrigin::parse("https://*.example.com");
+    assert!(wildcard.allows("https://api.example.com"));
+    assert!(!wildcard.allows("https://example.com"));
+    assert!(!wildcard.allows("https://evil.com/.example.com"));
+}
+
+#[test]
+fn test_cors_ignores_requests_without_origin() {
+    let middleware = CorsMiddleware::default();
+    let mut context = MiddlewareContext::new("GET", "/api/users");
+
+    assert!(middleware.handle(&mut context).is_ok());
+    assert!(context.response_headers.is_empty());
+    assert!(context.response.is_none());
+}
+
+#[test]
+fn test_cors_simple_request_headers() {
+    let middleware = CorsMiddleware::new()
+        .allow_origin("https://app.example.com")
+        .allow_credentials(true)
+        .expose_headers(&["X-Request-Id"]);
+    let mut context = MiddlewareContext::new("GET", "/api/users");
+    context.headers.insert("origin".to_string(), "https://app.example.com".to_string());
+
+    middleware.handle(&mut context).unwrap();
+    let headers = &context.response_headers;
+    assert_eq!(header(headers, "Access-Control-Allow-Origin"), Some("https://app.example.com"));
+    assert_eq!(header(headers, "Access-Control-Allow-Credentials"), Some("true"));
+    assert_eq!(header(headers, "Access-Control-Expose-Headers"), Some("X-Request-Id"));
+    assert_eq!(header(headers, "Vary"), Some("Origin"));
+}
+
+#[test]
+fn test_cors_any_origin_never_gets_credentials() {
+    let middleware = CorsMiddleware::new()
+        .allow_origin("*")
+        .allow_origin("https://app.example.com")
+        .allow_credentials(true);
+    let headers = |origin: &str| {
+        let mut context = MiddlewareContext::new("GET", "/api/users");
+        context.headers.insert("origin".to_string(), origin.to_string());
+        middleware.handle(&mut context).unwrap();
+        context.response_headers
+    };
+
+    // 只被 `*` 允许的来源不回显，也不携带凭据
+    let any = headers("https://evil.example.net");
+    assert_eq!(header(&any, "Access-Control-Allow-Origin"), Some("*"));
+    assert_eq!(header(&any, "Access-Control-Allow-Credentials"), None);
+
+    let explicit = headers("https://app.example.com");
+    assert_eq!(header(&explicit, "Access-Control-Allow-Origin"), Some("https://app.example.com"));
+    assert_eq!(header(&explicit, "Access-Control-Allow-Credentials"), Some("true"));
+}
+
+#[test]
+fn test_cors_disallowed_origin() {
+    let middleware = CorsMiddleware::new().allow_origin("https://app.example.com");
+
+    let mut context = MiddlewareContext::new("GET", "/api/users");
+    context.headers.insert("origin".to_string(), "https://evil.com".to_string());
+    middleware.handle(&mut context).unwrap();
+    assert!(context.response_headers.is_empty());
+
+    let mut context = MiddlewareContext::new("OPTIONS", "/api/users");
+    context.headers.insert("origin".to_string(), "https://evil.com".to_string());
+    context.headers.insert("access-control-request-method".to_string(), "POST".to_string());
+    middleware.handle(&mut context).unwrap();
+    assert_eq!(context.response.unwrap().status, 403);
+}
+
+#[test]
+fn test_cors_preflight_uses_route_methods() {
+    let middleware = CorsMiddleware::default()
+        .with_routes(user_routes())
+        .allow_any_header()
+        .max_age(600);
+
+    let mut context = MiddlewareContext::new("OPTIONS", "/api/users");
+    context.headers.insert("origin".to_string(), "https://app.example.com".to_string());
+    context.headers.insert("access-control-request-method".to_string(), "POST".to_string());
+    context.headers.insert("access-control-request-headers".to_string(), "content-type".to_string());
+    middleware.handle(&mut context).unwrap();
+
+    let response = context.response.expect("preflight should be answered");
+    assert_eq!(response.status, 204);
+    assert_eq!(header(&response.headers, "Access-Control-Allow-Origin"), Some("*"));
+    // GET 路由同时处理 HEAD 请求
+    assert_eq!(header(&response.headers, "Access-Control-Allow-Methods"), Some("GET, HEAD, POST"));
+    assert_eq!(header(&response.headers, "Access-Control-Allow-Headers"), Some("content-type"));
+    assert_eq!(header(&response.headers, "Access-Control-Max-Age"), Some("600"));
+
+    let mut context = MiddlewareContext::new("OPTIONS", "/api/users/42");
+    context.headers.insert("origin".to_string(), "https://app.example.com".to_string());
+    context.headers.insert("access-control-request-method".to_string(), "DELETE".to_string());
+    middleware.handle(&mut context).unwrap();
+    let response = context.response.unwrap();
+    assert_eq!(header(&response.headers, "Access-Control-Allow-Methods"), Some("DELETE"));
+
+    // 未注册的路径不应答预检请求
+    let mut context = MiddlewareContext::new("OPTIONS", "/unknown");
+    context.headers.insert("origin".to_string(), "https://app.example.com".to_string());
+    context.headers.insert("access-control-request-method".to_string(), "GET".to_string());
+    middleware.handle(&mut context).unwrap();
+    assert!(context.response.is_none());
+}
+
+#[test]
+fn test_cors_preflight_skips_later_middleware() {
+    let chain = MiddlewareChain::new()
+        .add_middleware(Arc::new(CorsMiddleware::default().with_routes(user_routes())))
+        .add_middleware(Arc::new(AuthMiddleware::new(AuthPolicy::new("role:admin"))));
+
+    let mut context = chain.build_context(
+        "OPTIONS",
+        "/api/users",
+        vec![("Origin", "https://app.example.com"), ("Access-Control-Request-Method", "GET")],
+    );
+    assert_eq!(chain.process_request(&mut context).unwrap().status, 204);
+
+    // 非预检请求仍需认证，错误响应中保留CORS头
+    let mut context = chain.build_context("GET", "/api/users", vec![("Origin", "https://app.example.com")]);
+    let response = chain.process_request(&mut context).unwrap();
+    assert_eq!(response.status, 401);
+    assert_eq!(header(&response.headers, "Access-Control-Allow-Origin"), Some("*"));
+}
+
+#[test]
+fn test_cors_request_headers_are_not_echoed_by_default() {
+    let preflight = |middleware: &CorsMiddleware| {
+        let mut context = MiddlewareContext::new("OPTIONS", "/api/users/7");
+        context.headers.insert("origin".to_string(), "https://app.example.com".to_string());
+        context.headers.insert("access-control-request-method".to_string(), "DELETE".to_string());
+        context.headers.insert("access-control-request-headers".to_string(), "x-anything".to_string());
+        middleware.handle(&mut context).unwrap();
+        context.response.expect("preflight should be answered")
+    };
+
+    let response = preflight(&CorsMiddleware::default().with_routes(user_routes()));
+    assert_eq!(header(&response.headers, "Access-Control-Allow-Headers"), None);
+
+    let response = preflight(&CorsMiddleware::default().with_routes(user_routes()).allow_headers(&["content-type"]));
+    assert_eq!(header(&response.headers, "Access-Control-Allow-Headers"), Some("content-type"));
+}
@@ -0,0 +1,230 @@
+//! 接口变更检测的单元测试
+
+use serde_json::json;
+use uni_routing::auth::AuthPolicy;
+use uni_routing::diff::{diff_openapi, diff_routes, diff_snapshots, ChangeKind};
+use uni_routing::openapi::OpenApiBuilder;
+use uni_routing::routing::{HttpMethod, PathParam, ResponseInfo, RouteInfo};
+use uni_routing::schema::{self, TypeSchema};
+use uni_routing::snapshot::RouteSnapshot;
+
+fn user_schema(properties: serde_json::Value, required: &[&str]) -> TypeSchema {
+    let mut components = std::collections::BTreeMap::new();
+    components.insert(
+        "User".to_string(),
+        json!({ "type": "object", "properties": properties, "required": required }),
+    );
+    TypeSchema {
+        schema: json!({ "$ref": "#/components/schemas/User" }),
+        components,
+    }
+}
+
+fn get_user(schema: TypeSchema) -> RouteInfo {
+    RouteInfo {
+        path: "/users/{id}".to_string(),
+        method: HttpMethod::GET,
+        path_params: vec![PathParam { name: "id".to_string(), schema: schema::integer("int64", true) }],
+        responses: vec![ResponseInfo::new(200, Some(schema))],
+        ..Default::default()
+    }
+}
+
+fn old_routes() -> Vec<RouteInfo> {
+    vec![
+        get_user(user_schema(json!({ "id": { "type": "integer" }, "email": { "type": "string" } }), &["id", "email"])),
+        RouteInfo {
+            path: "/users".to_string(),
+            method: HttpMethod::POST,
+            auth_policy: Some(AuthPolicy::new("role:admin")),
+            request_body: Some(TypeSchema::inline(json!({
+                "type": "object",
+                "properties": { "name": { "type": "string" } },
+                "required": ["name"],
+            }))),
+            ..Default::default()
+        },
+        RouteInfo {
+            path: "/users/{id}".to_string(),
+            method: HttpMethod::DELETE,
+            ..Default::default()
+        },
+    ]
+}
+
+fn kinds(changes: &[uni_routing::diff::Change], breaking: bool) -> Vec<ChangeKind> {
+    changes.iter().filter(|change| change.breaking == breaking).map(|change| change.kind).collect()
+}
+
+#[test]
+fn test_identical_routes_have_no_changes() {
+    let report = diff_routes(&old_routes(), &old_routes());
+    assert!(report.is_empty());
+    assert!(!report.is_breaking());
+    assert_eq!(report.to_string(), "No changes");
+}
+
+#[test]
+fn test_breaking_changes() {
+    let mut new = old_routes();
+    // 响应中删除字段、请求中新增必需字段、新增认证要求、删除端点
+    new[0] = get_user(user_schema(json!({ "id": { "type": "integer" } }), &["id"]));
+    new[1].request_body = Some(TypeSchema::inline(json!({
+        "type": "object",
+        "properties": { "name": { "type": "string" }, "email": { "type": "string" } },
+        "required": ["name", "email"],
+    })));
+    new[1].auth_policy = Some(AuthPolicy::new("role:admin, permission:users.write"));
+    new.remove(2);
+
+    let report = diff_routes(&old_routes(), &new);
+    assert!(report.is_breaking());
+    assert_eq!(
+        kinds(&report.changes, true),
+        vec![ChangeKind::RequestBodyChanged, ChangeKind::AuthTightened, ChangeKind::EndpointRemoved, ChangeKind::ResponseSchemaChanged]
+    );
+    let messages: Vec<String> = report.breaking().map(ToString::to_string).collect();
+    assert_eq!(messages, vec![
+        "[breaking] POST /users: request body: required property `email` added",
+        "[breaking] POST /users: auth policy changed from `role:admin` to `permission:users.write, role:admin`",
+        "[breaking] DELETE /users/{id}: endpoint removed",
+        "[breaking] GET /users/{id}: response 200: property `email` removed",
+    ]);
+}
+
+#[test]
+fn test_non_breaking_changes() {
+    let mut new = old_routes();
+    // 响应中新增字段、请求中新增可选字段、放宽认证、新增端点、弃用端点、修改参数名
+    new[0] = get_user(user_schema(
+        json!({ "id": { "type": "integer" }, "email": { "type": "string" }, "name": { "type": "string" } }),
+        &["id", "email"],
+    ));
+    new[0].path = "/users/{user_id}".to_string();
+    new[1].auth_policy = None;
+    new[1].request_body = Some(TypeSchema::inline(json!({
+        "type": "object",
+        "properties": { "name": { "type": "string" }, "nickname": { "type": "string" } },
+        "required": ["name"],
+    })));
+    new[2].deprecated = true;
+    new.push(RouteInfo {
+        path: "/users".to_string(),
+        method: HttpMethod::GET,
+        ..Default::default()
+    });
+
+    let report = diff_routes(&old_routes(), &new);
+    assert!(!report.is_breaking(), "{}", report);
+    assert_eq!(
+        kinds(&report.changes, false),
+        vec![
+            ChangeKind::EndpointAdded,
+            ChangeKind::RequestBodyChanged,
+            ChangeKind::AuthRelaxed,
+            ChangeKind::Deprecated,
+            ChangeKind::ResponseSchemaChanged,
+        ]
+    );
+}
+
+#[test]
+fn test_schema_type_and_enum_changes() {
+    let route = |status: &str| RouteInfo {
+        path: "/orders".to_string(),
+        method: HttpMethod::GET,
+        responses: vec![ResponseInfo::new(200, Some(TypeSchema::inline(json!({
+            "type": "array",
+            "items": { "type": "object", "properties": { "status": { "type": "string", "enum": ["open", status] } } },
+        }))))],
+        ..Default::default()
+    };
+    let report = diff_routes(&[route("closed")], &[route("cancelled")]);
+    let messages: Vec<String> = report.changes.iter().map(ToString::to_string).collect();
+    assert_eq!(messages, vec![
+        "[non-breaking] GET /orders: response 200: `[].status` enum values removed: \"closed\"",
+        "[breaking] GET /orders: response 200: `[].status` enum values added: \"cancelled\"",
+    ]);
+
+    let mut changed = route("closed");
+    changed.responses[0].schema = Some(schema::array(schema::string()));
+    let report = diff_routes(&[route("closed")], &[changed]);
+    assert_eq!(report.changes[0].message, "response 200: `[]` type changed from object to string");
+    assert!(report.is_breaking());
+}
+
+#[test]
+fn test_diff_openapi_documents() {
+    let old = OpenApiBuilder::new("API", "1.0.0").routes(old_routes()).build().to_json();
+    let mut new = old.clone();
+    // 新增必需的查询参数，删除成功响应
+    new["paths"]["/users/{id}"]["get"]["parameters"]
+        .as_array_mut()
+        .unwrap()
+        .push(json!({ "name": "fields", "in": "query", "required": true, "schema": { "type": "string" } }));
+    new["paths"]["/users"]["post"]["responses"] = json!({ "201": { "description": "Created" } });
+
+    let report = diff_openapi(&old, &new);
+    let messages: Vec<String> = report.changes.iter().map(ToString::to_string).collect();
+    assert_eq!(messages, vec![
+        "[breaking] POST /users: response 200 removed",
+        "[non-breaking] POST /users: response 201 added",
+        "[breaking] GET /users/{id}: required query parameter `fields` added",
+    ]);
+
+    // 公开端点加上安全要求
+    let mut secured = old.clone();
+    secured["paths"]["/users/{id}"]["delete"]["security"] = json!([{ "bearerAuth": [] }]);
+    let report = diff_openapi(&old, &secured);
+    assert_eq!(report.changes[0].kind, ChangeKind::AuthTightened);
+    assert_eq!(report.changes[0].message, "authentication now required (bearerAuth)");
+}
+
+#[test]
+fn test_diff_snapshots_and_cli() {
+    let old = RouteSnapshot::new(old_routes());
+    let mut new = old.clone();
+    new.routes.remove(2);
+    assert!(diff_snapshots(&old, &new).is_breaking());
+
+    let dir = std::env::temp_dir();
+    let old_path = dir.join(format!("uni_routing_diff_old_{}.toml", std::process::id()));
+    let new_path = dir.join(format!("uni_routing_diff_new_{}.json", std::process::id()));
+    let openapi_path = dir.join(format!("uni_routing_diff_openapi_{}.json", std::process::id()));
+    old.save(&old_path).unwrap();
+    new.save(&new_path).unwrap();
+    std::fs::write(&openapi_path, OpenApiBuilder::new("API", "1.0.0").routes(old_routes()).build().to_json_string()).unwrap();
+
+    let run = |old: &std::path::Path, new: &std::path::Path, extra: &[&str]| {
+        std::process::Command::new(env!("CARGO_BIN_EXE_uni-routing"))
+            .arg("diff")
+            .arg(old)
+            .arg(new)
+            .args(extra)
+            .output()
+            .unwrap()
+    };
+
+    let output = run(&old_path, &new_path, &[]);
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("[breaking] DELETE /users/{id}: endpoint removed"));
+    assert!(stdout.ends_with("1 changes, 1 breaking\n"));
+
+    let output = run(&new_path, &old_path, &["--format", "json"]);
+    assert_eq!(output.status.code(), Some(0));
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["changes"][0]["kind"], json!("endpoint_added"));
+
+    // 快照与由同一组路由生成的 OpenAPI 文档可以直接比较
+    let output = run(&openapi_path, &old_path, &[]);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "No changes\n");
+
+    let output = run(&old_path, &dir.join("uni_routing_missing.json"), &[]);
+    assert_eq!(output.status.code(), Some(2));
+
+    for path in [old_path, new_path, openapi_path] {
+        std::fs::remove_file(path).unwrap();
+    }
+}
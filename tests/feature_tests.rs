@@ -0,0 +1,104 @@
+//! 功能开关的单元测试
+
+#![allow(dead_code)]
+
+use std::sync::Arc;
+
+use uni_routing::feature::{is_route_enabled, EnvFeatureFlags, FeatureFlagProvider, JsonFileFeatureFlags, StaticFeatureFlags};
+use uni_routing::middleware::{MiddlewareChain, MiddlewareFactory};
+use uni_routing::openapi::OpenApiBuilder;
+use uni_routing::routing::RouteInfo;
+use uni_routing_macros::uni_routing;
+
+#[uni_routing(route = "/api/billing", method = "GET", feature_flag = "new_billing")]
+async fn get_billing() {}
+
+#[uni_routing(route = "/api/users", method = "GET")]
+async fn list_users() {}
+
+fn routes() -> Vec<RouteInfo> {
+    vec![__register_route_get_billing(), __register_route_list_users()]
+}
+
+fn status(chain: &MiddlewareChain, path: &str) -> Option<u16> {
+    let mut context = chain.build_context("GET", path, []);
+    chain.process_request(&mut context).map(|response| response.status)
+}
+
+#[test]
+fn test_macro_records_feature_flag() {
+    assert_eq!(__register_route_get_billing().feature_flag.as_deref(), Some("new_billing"));
+    assert_eq!(__register_route_list_users().feature_flag, None);
+
+    let route = __register_route_get_billing();
+    assert!(!is_route_enabled(&route, None));
+    assert!(is_route_enabled(&route, Some(&StaticFeatureFlags::new().enable("new_billing"))));
+    assert!(is_route_enabled(&__register_route_list_users(), None));
+}
+
+#[test]
+fn test_chain_returns_404_when_flag_is_off() {
+    let flags = Arc::new(JsonFileFeatureFlags::load(write_flags("chain", r#"{ "new_billing": false }"#)).unwrap());
+    let chain = MiddlewareChain::new()
+        .with_feature_flags(flags.clone())
+        .with_routes(routes(), &MiddlewareFactory::default())
+        .unwrap();
+    assert_eq!(status(&chain, "/api/billing"), Some(404));
+    assert_eq!(status(&chain, "/api/users"), None);
+
+    // 开关在每个请求时查询
+    std::fs::write(flags_path("chain"), r#"{ "new_billing": true }"#).unwrap();
+    flags.reload().unwrap();
+    let context = chain.build_context("GET", "/api/billing", []);
+    assert_eq!(context.route.unwrap().feature_flag.as_deref(), Some("new_billing"));
+    assert_eq!(status(&chain, "/api/billing"), None);
+
+    // 无效文件不影响当前开关
+    std::fs::write(flags_path("chain"), "not json").unwrap();
+    assert!(flags.reload().is_err());
+    assert!(flags.is_enabled("new_billing"));
+    std::fs::remove_file(flags_path("chain")).unwrap();
+
+    // 未配置开关提供者时带开关的路由关闭
+    let chain = MiddlewareChain::new().with_routes(routes(), &MiddlewareFactory::default()).unwrap();
+    assert_eq!(status(&chain, "/api/billing"), Some(404));
+}
+
+#[test]
+fn test_openapi_hides_disabled_routes() {
+    let spec = OpenApiBuilder::new("API", "1.0.0").routes(routes()).build().to_json();
+    assert!(spec["paths"].get("/api/billing").is_none());
+    assert!(spec["paths"].get("/api/users").is_some());
+
+    let spec = OpenApiBuilder::new("API", "1.0.0")
+        .feature_flags(Arc::new(StaticFeatureFlags::new().enable("new_billing")))
+        .routes(routes())
+        .build()
+        .to_json();
+    assert!(spec["paths"].get("/api/billing").is_some());
+}
+
+#[test]
+fn test_env_feature_flags() {
+    let flags = EnvFeatureFlags::with_prefix("UNI_ROUTING_TEST_FEATURE_");
+    assert_eq!(flags.variable("new-billing.v2"), "UNI_ROUTING_TEST_FEATURE_NEW_BILLING_V2");
+    assert!(!flags.is_enabled("new_billing"));
+
+    std::env::set_var("UNI_ROUTING_TEST_FEATURE_NEW_BILLING", "On");
+    assert!(flags.is_enabled("new_billing"));
+    std::env::set_var("UNI_ROUTING_TEST_FEATURE_NEW_BILLING", "0");
+    assert!(!flags.is_enabled("new_billing"));
+    std::env::remove_var("UNI_ROUTING_TEST_FEATURE_NEW_BILLING");
+
+    assert_eq!(EnvFeatureFlags::new().variable("beta"), "FEATURE_BETA");
+}
+
+fn flags_path(name: &str) -> std::path::PathBuf {
+    std::env::temp_dir().join(format!("uni_routing_flags_{}_{}.json", name, std::process::id()))
+}
+
+fn write_flags(name: &str, content: &str) -> std::path::PathBuf {
+    let path = flags_path(name);
+    std::fs::write(&path, content).unwrap();
+    path
+}
@@ -0,0 +1,174 @@
+//! 路由分组的单元测试
+
+#![allow(dead_code)]
+
+mod common;
+
+use common::Path;
+use std::sync::Arc;
+use uni_routing::auth::JwtToken;
+use uni_routing::group::{join_path, template_with, RouteGroup};
+use uni_routing::middleware::{MiddlewareChain, MiddlewareFactory, MiddlewareSpec};
+use uni_routing::openapi::OpenApiBuilder;
+use uni_routing::registry::RouteRegistry;
+use uni_routing::routing::HttpMethod;
+use uni_routing_macros::uni_routing;
+
+#[uni_routing(route = "/", method = ["GET", "POST"], description = "Users")]
+async fn users() {}
+
+#[uni_routing(route = "/{id}", method = "GET", tags = ["detail"], description = "Get user")]
+async fn get_user_by_id(_path: Path<u64>) {}
+
+#[uni_routing(route = "/{id}", method = "DELETE", auth_policy = "role:owner", middleware = [cache(60)])]
+async fn delete_user(_path: Path<u64>) {}
+
+#[uni_routing(route = "/stats", method = "GET")]
+async fn user_stats() {}
+
+fn users_group() -> RouteGroup {
+    RouteGroup::new("/api/users/")
+        .auth_policy("role:admin")
+        .tag("users")
+        .middleware(MiddlewareSpec::new("rate_limit", vec!["10/s".to_string()]))
+        .routes(__register_routes_users())
+        .route(__register_route_get_user_by_id())
+        .route(__register_route_delete_user())
+}
+
+#[test]
+fn test_group_prefixes_paths() {
+    let routes = users_group().build();
+    let paths: Vec<(HttpMethod, &str)> = routes.iter().map(|r| (r.method.clone(), r.path.as_str())).collect();
+    assert_eq!(
+        paths,
+        vec![
+            (HttpMethod::GET, "/api/users"),
+            (HttpMethod::POST, "/api/users"),
+            (HttpMethod::GET, "/api/users/{id}"),
+            (HttpMethod::DELETE, "/api/users/{id}"),
+        ]
+    );
+    assert_eq!(users_group().prefix(), "/api/users");
+    assert!(routes[2].matches("GET", "/api/users/42"));
+}
+
+#[test]
+fn test_group_defaults_and_merging() {
+    let routes = users_group().build();
+
+    // 路由未声明认证策略时使用分组默认值，已声明的保持不变
+    assert_eq!(routes[0].auth_policy.as_ref().unwrap().policy_expr, "role:admin");
+    assert_eq!(routes[3].auth_policy.as_ref().unwrap().policy_expr, "role:owner");
+
+    // 分组标签在前，路由自身标签在后
+    assert_eq!(routes[0].tags, vec!["users"]);
+    assert_eq!(routes[2].tags, vec!["users", "detail"]);
+
+    // 分组中间件在路由中间件之前执行
+    let names: Vec<&str> = routes[3].middleware.iter().map(|spec| spec.name.as_str()).collect();
+    assert_eq!(names, vec!["rate_limit", "cache"]);
+}
+
+#[test]
+fn test_group_auth_policy_is_enforced() {
+    let mut routes = RouteGroup::new("/api/users").auth_policy("role:admin").routes(__register_routes_users()).build();
+    routes.extend(RouteGroup::new("/api/public").route(__register_route_user_stats()).build());
+    let chain = MiddlewareChain::new()
+        .with_token_decoder(Arc::new(|_token: &str| Some(JwtToken { payload: Default::default() })))
+        .with_routes(routes, &MiddlewareFactory::default())
+        .unwrap();
+    let status = |method: &str, path: &str, headers: &[(&str, &str)]| {
+        let mut context = chain.build_context(method, path, headers.iter().copied());
+        chain.process_request(&mut context).map(|response| response.status)
+    };
+
+    // 分组的默认认证策略在运行时生效
+    assert_eq!(status("POST", "/api/users", &[]), Some(401));
+    assert_eq!(status("POST", "/api/users", &[("Authorization", "Bearer abc")]), None);
+    assert_eq!(status("GET", "/api/public/stats", &[]), None);
+}
+
+#[test]
+fn test_nested_groups() {
+    let api = RouteGroup::new("/v1")
+        .tag("v1")
+        .auth_policy("role:user")
+        .group(users_group())
+        .group(RouteGroup::new("/admin").route(__register_route_user_stats()));
+    let routes = api.build();
+
+    assert_eq!(routes.len(), 5);
+    assert_eq!(routes[2].path, "/v1/api/users/{id}");
+    assert_eq!(routes[2].tags, vec!["v1", "users", "detail"]);
+    // 内层分组的认证策略优先于外层
+    assert_eq!(routes[0].auth_policy.as_ref().unwrap().policy_expr, "role:admin");
+
+    let stats = &routes[4];
+    assert_eq!(stats.path, "/v1/admin/stats");
+    assert_eq!(stats.auth_policy.as_ref().unwrap().policy_expr, "role:user");
+    assert_eq!(stats.tags, vec!["v1"]);
+}
+
+#[test]
+fn test_group_prefix_params() {
+    let org = RouteGroup::new("/orgs/{org}").group(RouteGroup::new("/teams/{team:u32}").route(__register_route_get_user_by_id()));
+    let route = &org.build()[0];
+    assert_eq!(route.path, "/orgs/{org}/teams/{team:u32}/{id}");
+    let params: Vec<&str> = route.path_params.iter().map(|param| param.name.as_str()).collect();
+    assert_eq!(params, vec!["org", "team", "id"]);
+    assert_eq!(route.path_params[0].schema.schema, serde_json::json!({"type": "string"}));
+    assert_eq!(route.path_params[1].schema.schema["format"], "int32");
+
+    let spec = OpenApiBuilder::new("Test", "1.0.0").route(route.clone()).build().to_json();
+    let parameters = &spec["paths"]["/orgs/{org}/teams/{team}/{id}"]["get"]["parameters"];
+    assert_eq!(parameters.as_array().unwrap().len(), 3);
+    assert_eq!(parameters[0]["name"], "org");
+    assert_eq!(parameters[0]["in"], "path");
+}
+
+#[test]
+fn test_join_path_and_templates() {
+    assert_eq!(join_path("", "/"), "/");
+    assert_eq!(join_path("/", "/health"), "/health");
+    assert_eq!(join_path("/api/", "/"), "/api");
+    assert_eq!(join_path("api", "users"), "/api/users");
+
+    assert_eq!(template_with("/tenants/{tenant}/users", |name| format!(":{}", name)), "/tenants/:tenant/users");
+    assert_eq!(template_with("/tenants/<tenant>", |name| format!("<{}>", name)), "/tenants/<tenant>");
+    assert_eq!(template_with("", |name| name.to_string()), "/");
+}
+
+#[test]
+fn test_register_group_and_openapi() {
+    let registry = RouteRegistry::new();
+    registry.register_group(&users_group()).unwrap();
+    assert_eq!(registry.get_routes().len(), 4);
+    assert!(registry.get_route("/api/users/{id}", HttpMethod::DELETE).is_some());
+
+    // 重复注册同一分组会被拒绝
+    assert!(registry.register_group(&users_group()).is_err());
+
+    let spec = OpenApiBuilder::new("Test", "1.0.0").registry(&registry).build().to_json();
+    assert_eq!(spec["paths"]["/api/users/{id}"]["get"]["tags"], serde_json::json!(["users", "detail"]));
+}
+
+#[actix_web::test]
+async fn test_actix_scope() {
+    use actix_web::{test, web, App, HttpResponse};
+    use uni_routing::frameworks::actix::scope;
+
+    let group = users_group();
+    let app = test::init_service(
+        App::new().service(
+            scope(&group).route("/{id}", web::get().to(|id: web::Path<u64>| async move {
+                HttpResponse::Ok().body(id.to_string())
+            })),
+        ),
+    )
+    .await;
+
+    let req = test::TestRequest::get().uri("/api/users/7").to_request();
+    let body = test::call_and_read_body(&app, req).await;
+    assert_eq!(body, "7");
+}
@@ -1,5 +1,7 @@
 //! 集成测试，验证整个项目的功能
 
+use std::str::FromStr;
+
 use uni_routing::auth::{AuthPolicy, AuthCondition, JwtToken};
 use uni_routing::routing::{RouteInfo, HttpMethod};
 use uni_routing::middleware::{AuthMiddleware, MiddlewareContext, Middleware};
@@ -42,6 +44,7 @@ fn test_middleware_chain_integration() {
         token: None,
         path: "/api/users".to_string(),
         method: "GET".to_string(),
+        ..Default::default()
     };
     
     let result = middleware.handle(&mut context);
@@ -64,6 +67,7 @@ fn test_route_info_comprehensive() {
         method: HttpMethod::GET,
         auth_policy: Some(AuthPolicy::new("role:admin,permission:read")),
         description: Some("Get user by ID".to_string()),
+        ..Default::default()
     };
     
     // 验证路由信息
@@ -92,18 +96,19 @@ fn test_error_handling() {
 #[test]
 fn test_http_method_conversions() {
     // 测试字符串到 HTTP 方法的转换
-    assert!(matches!(HttpMethod::from_str("GET"), HttpMethod::GET));
-    assert!(matches!(HttpMethod::from_str("POST"), HttpMethod::POST));
-    assert!(matches!(HttpMethod::from_str("PUT"), HttpMethod::PUT));
-    assert!(matches!(HttpMethod::from_str("DELETE"), HttpMethod::DELETE));
-    assert!(matches!(HttpMethod::from_str("PATCH"), HttpMethod::PATCH));
+    assert!(matches!(HttpMethod::from_str("GET"), Ok(HttpMethod::GET)));
+    assert!(matches!(HttpMethod::from_str("POST"), Ok(HttpMethod::POST)));
+    assert!(matches!(HttpMethod::from_str("PUT"), Ok(HttpMethod::PUT)));
+    assert!(matches!(HttpMethod::from_str("DELETE"), Ok(HttpMethod::DELETE)));
+    assert!(matches!(HttpMethod::from_str("PATCH"), Ok(HttpMethod::PATCH)));
     
     // 测试大小写不敏感
-    assert!(matches!(HttpMethod::from_str("get"), HttpMethod::GET));
-    assert!(matches!(HttpMethod::from_str("post"), HttpMethod::POST));
+    assert!(matches!(HttpMethod::from_str("get"), Ok(HttpMethod::GET)));
+    assert!(matches!(HttpMethod::from_str("post"), Ok(HttpMethod::POST)));
     
-    // 测试默认情况
-    assert!(matches!(HttpMethod::from_str("INVALID"), HttpMethod::GET));
+    // 测试扩展方法与非法方法名
+    assert!(matches!(HttpMethod::from_str("INVALID"), Ok(HttpMethod::Other(_))));
+    assert!(HttpMethod::from_str("IN VALID").is_err());
 }
 
 #[test]
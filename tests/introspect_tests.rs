@@ -0,0 +1,123 @@
+//! 路由自省的单元测试
+
+use uni_routing::auth::AuthPolicy;
+use uni_routing::introspect::{self, Format, RouteRow};
+use uni_routing::routing::{HttpMethod, RouteInfo, RouteSource};
+
+fn routes() -> Vec<RouteInfo> {
+    vec![
+        RouteInfo {
+            path: "/users/{id}".to_string(),
+            method: HttpMethod::GET,
+            auth_policy: Some(AuthPolicy::new("role:admin || role:user")),
+            tags: vec!["users".to_string(), "admin".to_string()],
+            version: Some("v2".to_string()),
+            source: Some(RouteSource {
+                handler: "app::get_user".to_string(),
+                file: "src/main.rs".to_string(),
+                line: 12,
+            }),
+            ..Default::default()
+        },
+        RouteInfo {
+            path: "/health".to_string(),
+            method: HttpMethod::GET,
+            operation_id: Some("health_check".to_string()),
+            ..Default::default()
+        },
+    ]
+}
+
+#[test]
+fn test_rows() {
+    let rows = introspect::rows(&routes());
+    assert_eq!(rows, vec![
+        RouteRow {
+            method: "GET".to_string(),
+            path: "/health".to_string(),
+            auth_policy: None,
+            handler: Some("health_check".to_string()),
+            tags: vec![],
+        },
+        RouteRow {
+            method: "GET".to_string(),
+            path: "/v2/users/{id}".to_string(),
+            auth_policy: Some("role:admin || role:user".to_string()),
+            handler: Some("app::get_user".to_string()),
+            tags: vec!["users".to_string(), "admin".to_string()],
+        },
+    ]);
+}
+
+#[test]
+fn test_render_formats() {
+    let table = introspect::render(&routes(), Format::Table);
+    let lines: Vec<&str> = table.lines().collect();
+    assert_eq!(lines.len(), 3);
+    assert!(lines[0].starts_with("METHOD  PATH            AUTH"));
+    assert!(lines[1].starts_with("GET     /health         -"));
+    assert!(lines[2].ends_with("app::get_user  users,admin"));
+
+    let markdown = introspect::render(&routes(), Format::Markdown);
+    assert!(markdown.starts_with("| Method | Path | Auth | Handler | Tags |\n| --- |"));
+    assert!(markdown.contains("| `GET` | `/v2/users/{id}` | `role:admin \\|\\| role:user` | `app::get_user` | `users,admin` |"));
+
+    // JSON 输出可以作为导出文件重新读取
+    let json = introspect::render(&routes(), Format::Json);
+    let rows: Vec<RouteRow> = serde_json::from_str(&json).unwrap();
+    assert_eq!(rows, introspect::rows(&routes()));
+}
+
+#[test]
+fn test_format_parsing() {
+    assert_eq!("Markdown".parse::<Format>().unwrap(), Format::Markdown);
+    assert_eq!("md".parse::<Format>().unwrap(), Format::Markdown);
+    assert!("yaml".parse::<Format>().is_err());
+    assert_eq!(Format::from_query(None).unwrap(), Format::Table);
+    assert_eq!(Format::from_query(Some("verbose=1&format=json")).unwrap(), Format::Json);
+    assert!(Format::from_query(Some("format=xml")).is_err());
+}
+
+#[actix_web::test]
+async fn test_actix_routes_resource() {
+    use actix_web::{test, App};
+    use uni_routing::frameworks::actix::routes_resource;
+
+    let app = test::init_service(App::new().service(routes_resource("/_routes", &routes()))).await;
+
+    let req = test::TestRequest::get().uri("/_routes?format=json").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.headers().get("content-type").unwrap(), "application/json");
+    let rows: Vec<RouteRow> = test::read_body_json(resp).await;
+    assert_eq!(rows.len(), 2);
+
+    let req = test::TestRequest::get().uri("/_routes").to_request();
+    let body = test::call_and_read_body(&app, req).await;
+    assert!(String::from_utf8(body.to_vec()).unwrap().starts_with("METHOD"));
+
+    let req = test::TestRequest::get().uri("/_routes?format=xml").to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 400);
+}
+
+#[test]
+fn test_cli_renders_dump() {
+    let dump = std::env::temp_dir().join(format!("uni_routing_routes_{}.json", std::process::id()));
+    std::fs::write(&dump, introspect::render(&routes(), Format::Json)).unwrap();
+
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_uni-routing"))
+        .args(["routes", dump.to_str().unwrap(), "--format", "markdown"])
+        .output()
+        .unwrap();
+    std::fs::remove_file(&dump).unwrap();
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap().trim_end(),
+        introspect::render(&routes(), Format::Markdown)
+    );
+
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_uni-routing"))
+        .args(["routes", "missing.json"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+}
@@ -3,9 +3,13 @@
 #![allow(dead_code)]
 #![allow(non_snake_case)]
 
+mod common;
+
+use common::Path;
 use uni_routing_macros::uni_routing;
 use uni_routing::routing::{RouteInfo, HttpMethod};
 use uni_routing::auth::AuthPolicy;
+use uni_routing::middleware::MiddlewareSpec;
 
 #[test]
 fn test_macro_generated_constants() {
@@ -32,6 +36,7 @@ fn test_macro_generated_constants() {
         method: HttpMethod::GET,
         auth_policy: Some(AuthPolicy::new("roles:admin")),
         description: Some("Test endpoint".to_string()),
+        ..Default::default()
     };
     
     // 验证各个字段
@@ -43,9 +48,14 @@ fn test_macro_generated_constants() {
 
 #[test]
 fn test_macro_route_info() {
-    // 这个测试验证宏生成的路由信息常量
-    // 由于宏生成的常量名称是内部的，我们主要测试宏是否能正确编译
-    assert!(true);
+    // 这个测试验证宏生成的路由注册函数
+    #[uni_routing(route = "/api/items", method = "POST")]
+    async fn create_item() {}
+
+    let route = __register_route_create_item();
+    assert_eq!(route.path, "/api/items");
+    assert!(matches!(route.method, HttpMethod::POST));
+    assert!(route.auth_policy.is_none());
 }
 
 #[test]
@@ -55,10 +65,221 @@ fn test_route_info_manual() {
         method: HttpMethod::GET,
         auth_policy: Some(AuthPolicy::new("roles:admin")),
         description: Some("Test endpoint".to_string()),
+        ..Default::default()
     };
     
     assert_eq!(route.path, "/api/test");
     assert!(matches!(route.method, HttpMethod::GET));
     assert_eq!(route.auth_policy.unwrap().policy_expr, "roles:admin");
     assert_eq!(route.description.unwrap(), "Test endpoint");
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_macro_route_middleware() {
+    #[uni_routing(
+        route = "/api/reports",
+        method = "GET",
+        middleware = [rate_limit("10/s"), cache(60), logging],
+        description = "Reports"
+    )]
+    async fn get_reports() {}
+
+    let route = __register_route_get_reports();
+    assert_eq!(route.path, "/api/reports");
+    assert_eq!(route.description.as_deref(), Some("Reports"));
+    assert_eq!(route.middleware, vec![
+        MiddlewareSpec::new("rate_limit", vec!["10/s".to_string()]),
+        MiddlewareSpec::new("cache", vec!["60".to_string()]),
+        MiddlewareSpec::new("logging", Vec::new()),
+    ]);
+}
+
+#[test]
+fn test_macro_path_params() {
+    #[uni_routing(route = "/api/users/{id}", method = "GET")]
+    async fn get_user(_path: Path<u64>) {}
+
+    #[uni_routing(route = "/api/users/{user_id}/posts/{slug}", method = "GET")]
+    async fn get_post(Path((_user_id, _slug)): Path<(i32, String)>) {}
+
+    #[uni_routing(route = "/api/users/<id>", method = "DELETE")]
+    async fn delete_user(id: u64) -> u64 {
+        id
+    }
+
+    let route = __register_route_get_user();
+    assert_eq!(route.path_params.len(), 1);
+    assert_eq!(route.path_params[0].name, "id");
+    assert_eq!(route.path_params[0].schema.schema["format"], "int64");
+
+    let route = __register_route_get_post();
+    let names: Vec<_> = route.path_params.iter().map(|param| param.name.as_str()).collect();
+    assert_eq!(names, vec!["user_id", "slug"]);
+    assert_eq!(route.path_params[0].schema.schema["format"], "int32");
+    assert_eq!(route.path_params[1].schema.schema["type"], "string");
+
+    let route = __register_route_delete_user();
+    assert_eq!(route.path_params[0].name, "id");
+}
+
+#[test]
+fn test_openapi_path_parameters() {
+    #[uni_routing(route = "/api/users/<id>", method = "GET")]
+    async fn find_user(id: u64) -> u64 {
+        id
+    }
+
+    let registry = uni_routing::registry::RouteRegistry::new();
+    registry.register_route(__register_route_find_user()).unwrap();
+    let spec = registry.generate_openapi("Test API", "1.0.0");
+
+    let parameters = &spec["paths"]["/api/users/{id}"]["get"]["parameters"];
+    assert_eq!(parameters[0]["name"], "id");
+    assert_eq!(parameters[0]["in"], "path");
+    assert_eq!(parameters[0]["required"], true);
+    assert_eq!(parameters[0]["schema"]["type"], "integer");
+}
+
+#[test]
+fn test_macro_operation_metadata() {
+    #[uni_routing(
+        route = "/api/users/{id}",
+        method = "PUT",
+        auth_policy = "role:admin,permission:users.write",
+        description = "Update a user, keeping unspecified fields",
+        summary = "Update user",
+        tags = ["users", "admin"],
+        operation_id = "updateUser",
+        deprecated,
+        request_body = Vec<String>,
+        responses(200 = u64, 404 = String, 204),
+        examples(request = r#"["alice", "bob"]"#, 404 = r#""user not found""#),
+        external_docs(url = "https://example.com/docs/users", description = "User guide")
+    )]
+    async fn update_user(_path: Path<u64>) {}
+
+    #[uni_routing(route = "/api/health", method = "GET")]
+    async fn health_check() {}
+
+    let route = __register_route_update_user();
+    assert_eq!(route.auth_policy.unwrap().policy_expr, "role:admin,permission:users.write");
+    assert_eq!(route.description.as_deref(), Some("Update a user, keeping unspecified fields"));
+    assert_eq!(route.summary.as_deref(), Some("Update user"));
+    assert_eq!(route.tags, vec!["users", "admin"]);
+    assert_eq!(route.operation_id.as_deref(), Some("updateUser"));
+    assert!(route.deprecated);
+    assert_eq!(route.request_body.unwrap().schema["type"], "array");
+    assert_eq!(route.request_example, Some(serde_json::json!(["alice", "bob"])));
+
+    let statuses: Vec<_> = route.responses.iter().map(|response| response.status).collect();
+    assert_eq!(statuses, vec![200, 404, 204]);
+    assert_eq!(route.responses[0].schema.as_ref().unwrap().schema["format"], "int64");
+    assert_eq!(route.responses[1].description, "Not Found");
+    assert_eq!(route.responses[1].example, Some(serde_json::json!("user not found")));
+    assert!(route.responses[2].schema.is_none());
+
+    let docs = route.external_docs.unwrap();
+    assert_eq!(docs.url, "https://example.com/docs/users");
+    assert_eq!(docs.description.as_deref(), Some("User guide"));
+
+    // 未声明时操作ID默认为处理函数名
+    let route = __register_route_health_check();
+    assert_eq!(route.operation_id.as_deref(), Some("health_check"));
+    assert!(!route.deprecated);
+    assert!(route.tags.is_empty());
+}
+
+#[test]
+fn test_openapi_operation_metadata() {
+    #[uni_routing(
+        route = "/api/users",
+        method = "POST",
+        summary = "Create user",
+        tags = ["users"],
+        deprecated = true,
+        request_body = String,
+        responses(201 = u64),
+        examples(request = r#""alice""#, 201 = "42"),
+        external_docs(url = "https://example.com/docs")
+    )]
+    async fn create_user() {}
+
+    let registry = uni_routing::registry::RouteRegistry::new();
+    registry.register_route(__register_route_create_user()).unwrap();
+    let spec = registry.generate_openapi("Test API", "1.0.0");
+
+    let operation = &spec["paths"]["/api/users"]["post"];
+    assert_eq!(operation["summary"], "Create user");
+    assert_eq!(operation["tags"], serde_json::json!(["users"]));
+    assert_eq!(operation["operationId"], "create_user");
+    assert_eq!(operation["deprecated"], true);
+    assert_eq!(operation["externalDocs"]["url"], "https://example.com/docs");
+    assert_eq!(operation["requestBody"]["content"]["application/json"]["example"], "alice");
+    assert_eq!(operation["responses"]["201"]["description"], "Created");
+    assert_eq!(operation["responses"]["201"]["content"]["application/json"]["example"], 42);
+}
+
+#[test]
+fn test_macro_arguments_with_commas() {
+    #[uni_routing(
+        route = "/api/stats",
+        method = "POST",
+        auth_policy = "role:admin,permission:stats.write",
+        description = "Record counters, gauges, and timers",
+        request_body = Vec<(String, u64)>,
+        tags = ["stats", "metrics, internal"],
+        middleware = [rate_limit("10/s", "api_key:x-key")],
+    )]
+    async fn record_stats() {}
+
+    let route = __register_route_record_stats();
+    assert_eq!(route.description.as_deref(), Some("Record counters, gauges, and timers"));
+    assert_eq!(route.auth_policy.unwrap().parse().len(), 2);
+    assert_eq!(route.tags, vec!["stats", "metrics, internal"]);
+    assert_eq!(route.middleware, vec![MiddlewareSpec::new(
+        "rate_limit",
+        vec!["10/s".to_string(), "api_key:x-key".to_string()],
+    )]);
+    assert_eq!(route.request_body.unwrap().schema["type"], "array");
+}
+
+#[test]
+fn test_macro_multiple_methods() {
+    #[uni_routing(route = "/api/health", method = ["GET", "head", "PROPFIND"])]
+    async fn health_probe() {}
+
+    let routes = __register_routes_health_probe();
+    let methods: Vec<_> = routes.iter().map(|route| route.method.clone()).collect();
+    assert_eq!(methods, vec![
+        HttpMethod::GET,
+        HttpMethod::HEAD,
+        HttpMethod::Other("PROPFIND".to_string()),
+    ]);
+    let operation_ids: Vec<_> = routes.iter().map(|route| route.operation_id.clone().unwrap()).collect();
+    assert_eq!(operation_ids, vec!["health_probe", "health_probe_head", "health_probe_propfind"]);
+    assert!(routes.iter().all(|route| route.path == "/api/health"));
+    assert_eq!(__register_route_health_probe().method, HttpMethod::GET);
+
+    // 扩展方法不出现在OpenAPI文档中
+    let registry = uni_routing::registry::RouteRegistry::new();
+    for route in routes {
+        registry.register_route(route).unwrap();
+    }
+    let spec = registry.generate_openapi("Test API", "1.0.0");
+    let mut operations: Vec<_> = spec["paths"]["/api/health"].as_object().unwrap().keys().cloned().collect();
+    operations.sort();
+    assert_eq!(operations, vec!["get", "head"]);
+}
+
+#[test]
+fn test_macro_typed_and_catch_all_params() {
+    #[uni_routing(route = "/files/{id:u64}/{*path}", method = "GET")]
+    async fn get_file(_path: Path<(u64, String)>) {}
+
+    let route = __register_route_get_file();
+    let names: Vec<_> = route.path_params.iter().map(|param| param.name.as_str()).collect();
+    assert_eq!(names, vec!["id", "path"]);
+    assert_eq!(route.openapi_path(), "/files/{id}/{path}");
+    assert!(route.matches("GET", "/files/7/docs/readme.md"));
+    assert!(!route.matches("GET", "/files/seven/readme.md"));
+}
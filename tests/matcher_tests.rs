@@ -0,0 +1,219 @@
+//! 路由匹配器的单元测试
+
+mod common;
+
+use common::route;
+use uni_routing::matcher::{ParamType, RouteMatcher, RoutePattern, Segment};
+use uni_routing::registry::RouteRegistry;
+use uni_routing::routing::{HttpMethod, PathSegment, RouteInfo};
+
+fn matched(matcher: &RouteMatcher, method: &str, path: &str) -> Option<(String, Vec<(String, String)>)> {
+    matcher
+        .find(method, path)
+        .map(|found| (found.route.path.clone(), found.params))
+}
+
+fn params(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+    pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+}
+
+#[test]
+fn test_parse_pattern() {
+    let pattern = RoutePattern::parse("/files/{id:u64}/<name>/{page?}").unwrap();
+    assert_eq!(pattern.segments(), &[
+        Segment::Static("files".to_string()),
+        Segment::Param { name: "id".to_string(), ty: ParamType::U64, optional: false },
+        Segment::Param { name: "name".to_string(), ty: ParamType::Any, optional: false },
+        Segment::Param { name: "page".to_string(), ty: ParamType::Any, optional: true },
+    ]);
+    assert_eq!(RoutePattern::parse("/static/{*rest}").unwrap().segments()[1], Segment::CatchAll("rest".to_string()));
+    assert_eq!(RoutePattern::parse("/static/<rest..>").unwrap().segments()[1], Segment::CatchAll("rest".to_string()));
+
+    for invalid in [
+        "/files/{*rest}/tail",
+        "/files/{id:number}",
+        "/files/{page?}/tail",
+        "/files/{id}/{id}",
+        "/files/x{id}",
+        "/files/{1id}",
+    ] {
+        assert!(RoutePattern::parse(invalid).is_err(), "{} should be rejected", invalid);
+    }
+}
+
+#[test]
+fn test_path_segments_use_pattern_rules() {
+    // `PathSegment` 与 `RoutePattern` 使用同一个路径段解析器
+    assert_eq!(
+        PathSegment::parse_template("/files/{id:u64}/<rest..>/{page?}/x{id}"),
+        vec![
+            PathSegment::Static("files".to_string()),
+            PathSegment::Param("id".to_string()),
+            PathSegment::Param("rest".to_string()),
+            PathSegment::Param("page".to_string()),
+            PathSegment::Static("x{id}".to_string()),
+        ]
+    );
+
+    // 宏在常量求值时使用同一张类型表
+    const U64: Option<ParamType> = ParamType::from_name("u64");
+    assert_eq!(U64, Some(ParamType::U64));
+    assert_eq!(ParamType::from_name("number"), None);
+}
+
+#[test]
+fn test_match_captures_params() {
+    let matcher = RouteMatcher::new([
+        route(HttpMethod::GET, "/api/users/{id}"),
+        route(HttpMethod::GET, "/api/users/{id}/posts/<post_id>"),
+    ])
+    .unwrap();
+
+    assert_eq!(
+        matched(&matcher, "GET", "/api/users/123"),
+        Some(("/api/users/{id}".to_string(), params(&[("id", "123")])))
+    );
+    let found = matcher.find("GET", "/api/users/7/posts/9/").unwrap();
+    assert_eq!(found.param("id"), Some("7"));
+    assert_eq!(found.param("post_id"), Some("9"));
+    assert!(matcher.find("GET", "/api/users").is_none());
+    assert!(matcher.find("POST", "/api/users/1").is_none());
+}
+
+#[test]
+fn test_precedence_and_backtracking() {
+    let matcher = RouteMatcher::new([
+        route(HttpMethod::GET, "/files/{*path}"),
+        route(HttpMethod::GET, "/files/{name}"),
+        route(HttpMethod::GET, "/files/{id:u64}"),
+        route(HttpMethod::GET, "/files/latest"),
+        route(HttpMethod::DELETE, "/files/{name}"),
+        route(HttpMethod::GET, "/files/{name}/meta"),
+    ])
+    .unwrap();
+
+    // 静态段 > 带类型的参数 > 参数 > 通配参数
+    assert_eq!(matched(&matcher, "GET", "/files/latest").unwrap().0, "/files/latest");
+    assert_eq!(matched(&matcher, "GET", "/files/42").unwrap().0, "/files/{id:u64}");
+    assert_eq!(matched(&matcher, "GET", "/files/report").unwrap().0, "/files/{name}");
+    assert_eq!(
+        matched(&matcher, "GET", "/files/a/b/c.txt"),
+        Some(("/files/{*path}".to_string(), params(&[("path", "a/b/c.txt")])))
+    );
+
+    // 高优先级分支的方法不匹配时回溯到下一个分支
+    assert_eq!(matched(&matcher, "DELETE", "/files/latest").unwrap().0, "/files/{name}");
+    // 更深的分支匹配失败时回溯到通配参数
+    assert_eq!(matched(&matcher, "GET", "/files/42/other").unwrap().0, "/files/{*path}");
+    assert_eq!(matched(&matcher, "GET", "/files/42/meta").unwrap().0, "/files/{name}/meta");
+}
+
+#[test]
+fn test_typed_params() {
+    let matcher = RouteMatcher::new([
+        route(HttpMethod::GET, "/orders/{id:u8}"),
+        route(HttpMethod::GET, "/flags/{on:bool}"),
+        route(HttpMethod::GET, "/objects/{key:uuid}"),
+        route(HttpMethod::GET, "/offsets/{delta:i32}"),
+    ])
+    .unwrap();
+
+    assert!(matcher.find("GET", "/orders/255").is_some());
+    assert!(matcher.find("GET", "/orders/256").is_none());
+    assert!(matcher.find("GET", "/flags/true").is_some());
+    assert!(matcher.find("GET", "/flags/yes").is_none());
+    assert!(matcher.find("GET", "/objects/67e55044-10b1-426f-9247-bb680e5fe0c8").is_some());
+    assert!(matcher.find("GET", "/objects/67e55044").is_none());
+    assert!(matcher.find("GET", "/offsets/-3").is_some());
+}
+
+#[test]
+fn test_optional_and_catch_all() {
+    let matcher = RouteMatcher::new([
+        route(HttpMethod::GET, "/archive/{year:u16?}/{month:u8?}"),
+        route(HttpMethod::GET, "/static/{*rest}"),
+    ])
+    .unwrap();
+
+    assert_eq!(matched(&matcher, "GET", "/archive").unwrap().1, params(&[]));
+    assert_eq!(matched(&matcher, "GET", "/archive/2024").unwrap().1, params(&[("year", "2024")]));
+    assert_eq!(
+        matched(&matcher, "GET", "/archive/2024/5").unwrap().1,
+        params(&[("year", "2024"), ("month", "5")])
+    );
+    assert!(matcher.find("GET", "/archive/latest").is_none());
+
+    assert_eq!(matched(&matcher, "GET", "/static").unwrap().1, params(&[("rest", "")]));
+    assert_eq!(matched(&matcher, "GET", "/static/css/site.css").unwrap().1, params(&[("rest", "css/site.css")]));
+
+    let route = route(HttpMethod::GET, "/archive/{year:u16?}");
+    assert!(route.matches("GET", "/archive"));
+    assert!(route.matches("GET", "/archive/2024"));
+    assert_eq!(route.openapi_path(), "/archive/{year}");
+}
+
+#[test]
+fn test_head_falls_back_to_get_and_first_registration_wins() {
+    let matcher = RouteMatcher::new([
+        route(HttpMethod::GET, "/api/users/{id}"),
+        RouteInfo {
+            description: Some("shadowed".to_string()),
+            ..route(HttpMethod::GET, "/api/users/<user_id>")
+        },
+    ])
+    .unwrap();
+
+    let found = matcher.find("HEAD", "/api/users/1").unwrap();
+    assert_eq!(found.route.method, HttpMethod::GET);
+    assert_eq!(found.route.path, "/api/users/{id}");
+}
+
+#[test]
+fn test_matching_returns_all_methods() {
+    let matcher = RouteMatcher::new([
+        route(HttpMethod::GET, "/api/users/{id:u64}"),
+        route(HttpMethod::GET, "/api/users/me"),
+        route(HttpMethod::DELETE, "/api/users/{id}"),
+        route(HttpMethod::PUT, "/api/{*rest}"),
+    ])
+    .unwrap();
+
+    let described = |path: &str| -> Vec<String> { matcher.matching(path).iter().map(|route| route.describe()).collect() };
+    assert_eq!(described("/api/users/1"), vec!["GET /api/users/{id:u64}", "DELETE /api/users/{id}", "PUT /api/{*rest}"]);
+    assert_eq!(described("/api/users/me"), vec!["GET /api/users/me", "DELETE /api/users/{id}", "PUT /api/{*rest}"]);
+    assert!(described("/other").is_empty());
+}
+
+#[test]
+fn test_registry_matches_request_paths() {
+    let registry = RouteRegistry::new();
+    registry.register_route(route(HttpMethod::GET, "/api/users")).unwrap();
+    registry.register_route(route(HttpMethod::GET, "/api/users/{id:u64}")).unwrap();
+    assert!(registry.register_route(route(HttpMethod::GET, "/api/{*rest}/tail")).is_err());
+
+    // 冻结前后都能按请求路径查找
+    assert_eq!(registry.get_route("/api/users/123", HttpMethod::GET).unwrap().path, "/api/users/{id:u64}");
+    assert!(registry.matcher().is_none());
+    registry.freeze().unwrap();
+    let matcher = registry.matcher().unwrap();
+    assert_eq!(matcher.find("GET", "/api/users/9").unwrap().param("id"), Some("9"));
+    assert_eq!(registry.get_route("/api/users/123", HttpMethod::GET).unwrap().path, "/api/users/{id:u64}");
+    assert!(registry.get_route("/api/users/abc", HttpMethod::GET).is_none());
+}
+
+#[test]
+fn test_large_route_table() {
+    let routes: Vec<RouteInfo> = (0..2000)
+        .flat_map(|index| {
+            [
+                route(HttpMethod::GET, &format!("/api/r{}", index)),
+                route(HttpMethod::GET, &format!("/api/r{}/{{id:u64}}", index)),
+            ]
+        })
+        .collect();
+    let matcher = RouteMatcher::new(routes).unwrap();
+    assert_eq!(matcher.routes().len(), 4000);
+    let found = matcher.find("GET", "/api/r1999/77").unwrap();
+    assert_eq!(found.route.path, "/api/r1999/{id:u64}");
+    assert_eq!(found.param("id"), Some("77"));
+}
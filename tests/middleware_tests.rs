@@ -1,17 +1,39 @@
 //! 中间件模块的单元测试
 
-use uni_routing::middleware::{LoggingMiddleware, CorsMiddleware, AuthMiddleware, MiddlewareContext, Middleware};
+use uni_routing::middleware::{LogFormat, LoggingMiddleware, CorsMiddleware, AuthMiddleware, MiddlewareChain, MiddlewareContext, MiddlewareFactory, MiddlewareSpec, Middleware};
 use uni_routing::auth::{AuthPolicy, JwtToken};
+use uni_routing::routing::{HttpMethod, RouteInfo};
 use uni_routing::Error;
 use serde_json::json;
+use std::sync::Arc;
+
+/// 在响应阶段添加响应头的测试中间件
+struct HeaderMiddleware(&'static str);
+
+impl Middleware for HeaderMiddleware {
+    fn handle(&self, _context: &mut MiddlewareContext) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn on_response(&self, context: &mut MiddlewareContext, status: u16) {
+        context.response_headers.push((self.0.to_string(), status.to_string()));
+    }
+}
+
+fn decode_test_token(token: &str) -> Option<JwtToken> {
+    let mut payload = std::collections::HashMap::new();
+    payload.insert("sub".to_string(), json!(token));
+    Some(JwtToken { payload })
+}
 
 #[test]
 fn test_logging_middleware() {
-    let middleware = LoggingMiddleware;
+    let middleware = LoggingMiddleware::default();
     let mut context = MiddlewareContext {
         token: None,
         path: "/api/users".to_string(),
         method: "GET".to_string(),
+        ..Default::default()
     };
     
     let result = middleware.handle(&mut context);
@@ -25,6 +47,7 @@ fn test_cors_middleware() {
         token: None,
         path: "/api/users".to_string(),
         method: "GET".to_string(),
+        ..Default::default()
     };
     
     let result = middleware.handle(&mut context);
@@ -39,6 +62,7 @@ fn test_auth_middleware_without_token() {
         token: None,
         path: "/api/users".to_string(),
         method: "GET".to_string(),
+        ..Default::default()
     };
     
     let result = middleware.handle(&mut context);
@@ -58,6 +82,7 @@ fn test_auth_middleware_with_token() {
         token: Some(token),
         path: "/api/users".to_string(),
         method: "GET".to_string(),
+        ..Default::default()
     };
     
     let result = middleware.handle(&mut context);
@@ -72,8 +97,294 @@ fn test_auth_middleware_empty_policy() {
         token: None,
         path: "/api/users".to_string(),
         method: "GET".to_string(),
+        ..Default::default()
     };
     
     let result = middleware.handle(&mut context);
     assert!(result.is_ok());
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_chain_build_context_decodes_bearer_token() {
+    let chain = MiddlewareChain::new().with_token_decoder(Arc::new(decode_test_token));
+    let context = chain.build_context(
+        "GET",
+        "/api/users",
+        vec![("Authorization", "Bearer abc"), ("X-Custom", "1")],
+    );
+
+    assert_eq!(context.header("x-custom"), Some("1"));
+    assert_eq!(context.bearer_token(), Some("abc"));
+    assert_eq!(context.token.unwrap().payload["sub"], json!("abc"));
+}
+
+#[test]
+fn test_chain_process_request_maps_errors() {
+    let chain = MiddlewareChain::new()
+        .add_middleware(Arc::new(AuthMiddleware::new(AuthPolicy::new("role:admin"))));
+    let mut context = chain.build_context("GET", "/api/users", Vec::new());
+
+    let response = chain.process_request(&mut context).expect("request should be rejected");
+    assert_eq!(response.status, 401);
+    assert!(response.body.contains("Unauthorized"));
+}
+
+#[test]
+fn test_chain_process_response_runs_in_reverse_order() {
+    let chain = MiddlewareChain::new()
+        .add_middleware(Arc::new(HeaderMiddleware("X-First")))
+        .add_middleware(Arc::new(HeaderMiddleware("X-Second")));
+    let mut context = chain.build_context("GET", "/api/users", Vec::new());
+
+    assert!(chain.process_request(&mut context).is_none());
+    let headers = chain.process_response(&mut context, 200);
+    assert_eq!(headers, vec![
+        ("X-Second".to_string(), "200".to_string()),
+        ("X-First".to_string(), "200".to_string()),
+    ]);
+}
+
+#[test]
+fn test_chain_applies_route_middleware() {
+    let routes = vec![
+        RouteInfo {
+            path: "/api/users/{id}".to_string(),
+            method: HttpMethod::DELETE,
+            middleware: vec![
+                MiddlewareSpec::new("auth", vec!["role:admin".to_string()]),
+                MiddlewareSpec::new("marker", Vec::new()),
+            ],
+            ..Default::default()
+        },
+        RouteInfo {
+            path: "/api/health".to_string(),
+            method: HttpMethod::GET,
+            ..Default::default()
+        },
+    ];
+    let factory = MiddlewareFactory::default()
+        .register("marker", Arc::new(|_| Ok(Arc::new(HeaderMiddleware("X-Route")) as Arc<dyn Middleware>)));
+    let chain = MiddlewareChain::new()
+        .add_middleware(Arc::new(HeaderMiddleware("X-Global")))
+        .with_routes(routes, &factory)
+        .unwrap();
+
+    // 路由级认证中间件只作用于匹配的路由
+    let mut context = chain.build_context("DELETE", "/api/users/1", Vec::new());
+    assert_eq!(context.route.as_ref().unwrap().path, "/api/users/{id}");
+    assert_eq!(chain.process_request(&mut context).unwrap().status, 401);
+
+    let mut context = chain.build_context("GET", "/api/health", Vec::new());
+    assert!(chain.process_request(&mut context).is_none());
+    let headers = chain.process_response(&mut context, 200);
+    assert_eq!(headers, vec![("X-Global".to_string(), "200".to_string())]);
+
+    // 路由级中间件在全局中间件之后执行，响应阶段逆序
+    let mut payload = std::collections::HashMap::new();
+    payload.insert("sub".to_string(), json!("1"));
+    let mut context = chain.build_context("DELETE", "/api/users/1", Vec::new());
+    context.token = Some(JwtToken { payload });
+    assert!(chain.process_request(&mut context).is_none());
+    let headers = chain.process_response(&mut context, 204);
+    assert_eq!(headers, vec![
+        ("X-Route".to_string(), "204".to_string()),
+        ("X-Global".to_string(), "204".to_string()),
+    ]);
+}
+
+#[test]
+fn test_chain_rejects_unknown_route_middleware() {
+    let routes = vec![RouteInfo {
+        path: "/api/reports".to_string(),
+        middleware: vec![MiddlewareSpec::new("cache", vec!["60".to_string()])],
+        ..Default::default()
+    }];
+
+    let result = MiddlewareChain::new().with_routes(routes, &MiddlewareFactory::default());
+    assert!(matches!(result, Err(Error::Other(message)) if message.contains("cache")));
+}
+
+#[cfg(feature = "actix")]
+#[actix_web::test]
+async fn test_actix_adapter_runs_chain() {
+    use actix_web::{test, web, App, HttpResponse};
+    use uni_routing::frameworks::actix::UniMiddleware;
+
+    let chain = MiddlewareChain::new()
+        .add_middleware(Arc::new(HeaderMiddleware("X-Status")))
+        .add_middleware(Arc::new(AuthMiddleware::new(AuthPolicy::new("role:admin"))))
+        .with_token_decoder(Arc::new(decode_test_token));
+    let app = test::init_service(
+        App::new()
+            .wrap(UniMiddleware::new(chain))
+            .route("/api/users", web::get().to(|| async { HttpResponse::Ok().finish() })),
+    )
+    .await;
+
+    let req = test::TestRequest::get().uri("/api/users").to_request();
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 401);
+
+    let req = test::TestRequest::get()
+        .uri("/api/users")
+        .insert_header(("Authorization", "Bearer abc"))
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200);
+    assert_eq!(res.headers().get("X-Status").unwrap(), "200");
+}
+
+#[cfg(feature = "rocket")]
+#[rocket::post("/api/users")]
+fn rocket_create_user() -> &'static str {
+    "created"
+}
+
+#[cfg(feature = "rocket")]
+#[test]
+fn test_rocket_adapter_halts_any_method() {
+    use rocket::http::Status;
+    use rocket::local::blocking::Client;
+    use uni_routing::frameworks::rocket::UniFairing;
+
+    let chain = MiddlewareChain::new()
+        .add_middleware(Arc::new(AuthMiddleware::new(AuthPolicy::new("role:admin"))))
+        .with_token_decoder(Arc::new(decode_test_token));
+    let rocket = rocket::build().attach(UniFairing::new(chain)).mount("/", rocket::routes![rocket_create_user]);
+    let client = Client::untracked(rocket).unwrap();
+
+    // 被中间件拒绝的非 GET 请求返回中间件的响应，而不是 404
+    assert_eq!(client.post("/api/users").dispatch().status(), Status::Unauthorized);
+    assert_eq!(client.delete("/api/users").dispatch().status(), Status::Unauthorized);
+    let response = client.post("/api/users").header(rocket::http::Header::new("Authorization", "Bearer abc")).dispatch();
+    assert_eq!(response.into_string().as_deref(), Some("created"));
+}
+
+#[cfg(feature = "actix")]
+#[actix_web::test]
+async fn test_actix_adapter_derives_head_from_get() {
+    use actix_web::http::Method;
+    use actix_web::{test, web, App, HttpResponse};
+    use uni_routing::frameworks::actix::UniMiddleware;
+
+    let routes = vec![RouteInfo {
+        path: "/api/users".to_string(),
+        method: HttpMethod::GET,
+        middleware: vec![MiddlewareSpec::new("auth", vec!["role:admin".to_string()])],
+        ..Default::default()
+    }];
+    let chain = MiddlewareChain::new()
+        .with_token_decoder(Arc::new(decode_test_token))
+        .with_routes(routes, &MiddlewareFactory::default())
+        .unwrap();
+    let app = test::init_service(
+        App::new()
+            .wrap(UniMiddleware::new(chain))
+            .route("/api/users", web::get().to(|| async { HttpResponse::Ok().body("users") })),
+    )
+    .await;
+
+    // HEAD 请求同样经过 GET 路由的认证中间件
+    let req = test::TestRequest::default().method(Method::HEAD).uri("/api/users").to_request();
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 401);
+
+    let req = test::TestRequest::default()
+        .method(Method::HEAD)
+        .uri("/api/users")
+        .insert_header(("Authorization", "Bearer abc"))
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status().as_u16(), 200);
+}
+
+#[test]
+fn test_logging_middleware_request_id() {
+    let middleware = LoggingMiddleware::default();
+
+    // 沿用请求中的请求ID
+    let mut context = MiddlewareContext::new("GET", "/api/users");
+    context.headers.insert("x-request-id".to_string(), "abc-123".to_string());
+    middleware.handle(&mut context).unwrap();
+    assert_eq!(context.request_id.as_deref(), Some("abc-123"));
+    assert_eq!(context.response_headers, vec![("X-Request-Id".to_string(), "abc-123".to_string())]);
+
+    // 缺失时生成新的请求ID
+    let mut first = MiddlewareContext::new("GET", "/api/users");
+    let mut second = MiddlewareContext::new("GET", "/api/users");
+    middleware.handle(&mut first).unwrap();
+    middleware.handle(&mut second).unwrap();
+    assert!(first.request_id.is_some());
+    assert_ne!(first.request_id, second.request_id);
+}
+
+#[test]
+fn test_logging_middleware_formats() {
+    let mut payload = std::collections::HashMap::new();
+    payload.insert("sub".to_string(), json!("alice"));
+    let mut context = MiddlewareContext::new("GET", "/api/users/1");
+    context.token = Some(JwtToken { payload });
+    context.client_ip = Some("10.0.0.1".to_string());
+    context.request_id = Some("req-1".to_string());
+    context.route = Some(RouteInfo {
+        path: "/api/users/{id}".to_string(),
+        version: Some("v1".to_string()),
+        ..Default::default()
+    });
+
+    let plain = LoggingMiddleware::new(LogFormat::Plain).format_entry(&context, 200);
+    assert!(plain.starts_with("GET /api/users/1 200 "));
+    assert!(plain.ends_with("route=/v1/api/users/{id} user=alice request_id=req-1"));
+
+    let entry: serde_json::Value =
+        serde_json::from_str(&LoggingMiddleware::new(LogFormat::Json).format_entry(&context, 404)).unwrap();
+    assert_eq!(entry["status"], json!(404));
+    assert_eq!(entry["route"], json!("/v1/api/users/{id}"));
+    assert_eq!(entry["user"], json!("alice"));
+    assert_eq!(entry["request_id"], json!("req-1"));
+    assert!(entry["latency_ms"].is_number());
+    let timestamp = regex::Regex::new(r"^\d{4}-\d{2}-\d{2}T\d{2}:\d{2}:\d{2}\.\d{3}Z$").unwrap();
+    assert!(timestamp.is_match(entry["timestamp"].as_str().unwrap()), "{}", entry);
+
+    let common = LoggingMiddleware::new(LogFormat::Common).format_entry(&context, 201);
+    let pattern = regex::Regex::new(
+        r#"^10\.0\.0\.1 - alice \[\d{2}/[A-Z][a-z]{2}/\d{4}:\d{2}:\d{2}:\d{2} \+0000\] "GET /api/users/1 HTTP/1\.1" 201 -$"#,
+    )
+    .unwrap();
+    assert!(pattern.is_match(&common), "{}", common);
+
+    assert_eq!("json".parse::<LogFormat>().unwrap(), LogFormat::Json);
+    assert!("xml".parse::<LogFormat>().is_err());
+}
+
+#[cfg(feature = "axum")]
+#[tokio::test]
+async fn test_axum_layer_runs_chain() {
+    use axum::body::Body;
+    use axum::http::Request;
+    use tower_service::Service;
+    use uni_routing::frameworks::axum::UniMiddlewareLayer;
+
+    let chain = MiddlewareChain::new()
+        .add_middleware(Arc::new(HeaderMiddleware("X-Status")))
+        .add_middleware(Arc::new(AuthMiddleware::new(AuthPolicy::new("role:admin"))))
+        .with_token_decoder(Arc::new(decode_test_token));
+    let mut app = axum::Router::new()
+        .route("/api/users", axum::routing::get(|| async { "users" }))
+        .layer(UniMiddlewareLayer::new(chain));
+
+    // 被中间件拒绝的请求直接返回中间件的响应，不会进入处理函数
+    let req = Request::get("/api/users").body(Body::empty()).unwrap();
+    let res = app.call(req).await.unwrap();
+    assert_eq!(res.status().as_u16(), 401);
+
+    let req = Request::get("/api/users")
+        .header("Authorization", "Bearer abc")
+        .body(Body::empty())
+        .unwrap();
+    let res = app.call(req).await.unwrap();
+    assert_eq!(res.status().as_u16(), 200);
+    assert_eq!(res.headers().get("X-Status").unwrap(), "200");
+    let body = axum::body::to_bytes(res.into_body(), usize::MAX).await.unwrap();
+    assert_eq!(&body[..], b"users");
+}
@@ -0,0 +1,125 @@
+//! OpenAPI文档构建器的单元测试
+
+use uni_routing::auth::AuthPolicy;
+use uni_routing::openapi::{OpenApiBuilder, SecurityScheme, OPENAPI_VERSION};
+use uni_routing::registry::RouteRegistry;
+use uni_routing::routing::{HttpMethod, PathParam, ResponseInfo, RouteInfo};
+use uni_routing::schema;
+
+fn user_routes() -> Vec<RouteInfo> {
+    vec![
+        RouteInfo {
+            path: "/api/users".to_string(),
+            method: HttpMethod::GET,
+            auth_policy: Some(AuthPolicy::new("role:admin")),
+            description: Some("List users".to_string()),
+            responses: vec![ResponseInfo {
+                status: 200,
+                description: "Successful response".to_string(),
+                schema: Some(schema::array(schema::string())),
+                ..Default::default()
+            }],
+            ..Default::default()
+        },
+        RouteInfo {
+            path: "/api/users".to_string(),
+            method: HttpMethod::POST,
+            request_body: Some(schema::string()),
+            ..Default::default()
+        },
+    ]
+}
+
+#[test]
+fn test_builder_document_metadata() {
+    let spec = OpenApiBuilder::new("Users API", "2.0.0")
+        .description("User management")
+        .server("https://api.example.com", Some("Production"))
+        .tag("users", None)
+        .security_scheme("apiKey", SecurityScheme::api_key("X-API-Key"))
+        .build();
+
+    assert_eq!(spec.openapi, OPENAPI_VERSION);
+    let json = spec.to_json();
+    assert_eq!(json["openapi"], "3.1.0");
+    assert_eq!(json["info"]["title"], "Users API");
+    assert_eq!(json["info"]["version"], "2.0.0");
+    assert_eq!(json["info"]["description"], "User management");
+    assert_eq!(json["servers"][0]["url"], "https://api.example.com");
+    assert_eq!(json["servers"][0]["description"], "Production");
+    assert_eq!(json["tags"], serde_json::json!([{ "name": "users" }]));
+    assert_eq!(
+        json["components"]["securitySchemes"]["bearerAuth"],
+        serde_json::json!({ "type": "http", "scheme": "bearer", "bearerFormat": "JWT" })
+    );
+    assert_eq!(
+        json["components"]["securitySchemes"]["apiKey"],
+        serde_json::json!({ "type": "apiKey", "name": "X-API-Key", "in": "header" })
+    );
+}
+
+#[test]
+fn test_builder_operations() {
+    let spec = OpenApiBuilder::new("Users API", "1.0.0").routes(user_routes()).build();
+
+    let item = &spec.paths["/api/users"];
+    assert_eq!(item.operations.len(), 2);
+
+    let list = &item.operations["get"];
+    assert_eq!(list.summary.as_deref(), Some("List users"));
+    assert_eq!(list.security.len(), 1);
+    assert!(list.security[0].contains_key("bearerAuth"));
+
+    let create = &item.operations["post"];
+    assert!(create.security.is_empty());
+    assert!(create.request_body.as_ref().unwrap().required);
+    assert_eq!(create.responses["200"].description, "Successful response");
+
+    let json = spec.to_json();
+    assert_eq!(
+        json["paths"]["/api/users"]["get"]["responses"]["200"]["content"]["application/json"]["schema"],
+        serde_json::json!({ "type": "array", "items": { "type": "string" } })
+    );
+    assert!(json["paths"]["/api/users"]["post"].get("security").is_none());
+}
+
+#[test]
+fn test_optional_path_params_expand_to_path_variants() {
+    let route = RouteInfo {
+        path: "/archive/{year:u16?}/{month:u8?}".to_string(),
+        operation_id: Some("archive".to_string()),
+        path_params: ["year", "month"]
+            .into_iter()
+            .map(|name| PathParam {
+                name: name.to_string(),
+                schema: schema::integer("int32", true),
+            })
+            .collect(),
+        ..Default::default()
+    };
+    let spec = OpenApiBuilder::new("API", "1.0.0").route(route).build().to_json();
+
+    let operation = |path: &str| spec["paths"][path]["get"].clone();
+    assert_eq!(operation("/archive")["operationId"], "archive_without_year_month");
+    assert!(operation("/archive")["parameters"].as_array().is_none_or(Vec::is_empty));
+    assert_eq!(operation("/archive/{year}")["operationId"], "archive_without_month");
+    assert_eq!(operation("/archive/{year}")["parameters"].as_array().unwrap().len(), 1);
+    assert_eq!(operation("/archive/{year}/{month}")["operationId"], "archive");
+    for parameter in operation("/archive/{year}/{month}")["parameters"].as_array().unwrap() {
+        assert_eq!(parameter["required"], true);
+    }
+}
+
+#[test]
+fn test_registry_delegates_to_builder() {
+    let registry = RouteRegistry::new();
+    for route in user_routes() {
+        registry.register_route(route).unwrap();
+    }
+
+    let expected = OpenApiBuilder::new("Users API", "1.0.0")
+        .registry(&registry)
+        .build()
+        .to_json();
+    assert_eq!(registry.generate_openapi("Users API", "1.0.0"), expected);
+}
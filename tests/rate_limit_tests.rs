@@ -0,0 +1,192 @@
+//! 限流中间件的单元测试
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde_json::json;
+use uni_routing::auth::JwtToken;
+use uni_routing::middleware::rate_limit::{
+    InMemoryRateLimitStore, RateLimit, RateLimitKey, RateLimitStore,
+};
+use uni_routing::middleware::{Middleware, MiddlewareChain, MiddlewareContext, MiddlewareFactory, RateLimitMiddleware};
+use uni_routing::routing::{HttpMethod, RouteInfo};
+use uni_routing::Error;
+use uni_routing_macros::uni_routing;
+
+fn header<'a>(headers: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case(name))
+        .map(|(_, value)| value.as_str())
+}
+
+fn context_for_ip(ip: &str) -> MiddlewareContext {
+    let mut context = MiddlewareContext::new("GET", "/api/users");
+    context.client_ip = Some(ip.to_string());
+    context
+}
+
+#[test]
+fn test_rate_limit_parse() {
+    assert_eq!("100/min".parse::<RateLimit>().unwrap(), RateLimit::new(100, Duration::from_secs(60)));
+    assert_eq!("10/s".parse::<RateLimit>().unwrap(), RateLimit::new(10, Duration::from_secs(1)));
+    assert_eq!("5/30s".parse::<RateLimit>().unwrap(), RateLimit::new(5, Duration::from_secs(30)));
+    assert_eq!("1000/hour".parse::<RateLimit>().unwrap(), RateLimit::new(1000, Duration::from_secs(3600)));
+
+    assert!("100".parse::<RateLimit>().is_err());
+    assert!("0/min".parse::<RateLimit>().is_err());
+    assert!("10/fortnight".parse::<RateLimit>().is_err());
+}
+
+#[test]
+fn test_rate_limit_key_parse() {
+    assert_eq!("ip".parse::<RateLimitKey>().unwrap(), RateLimitKey::ClientIp);
+    assert_eq!("subject".parse::<RateLimitKey>().unwrap(), RateLimitKey::Subject);
+    assert_eq!("api_key:x-token".parse::<RateLimitKey>().unwrap(), RateLimitKey::ApiKey("x-token".to_string()));
+    assert_eq!("tenant:org".parse::<RateLimitKey>().unwrap(), RateLimitKey::Tenant("org".to_string()));
+    assert!("cookie".parse::<RateLimitKey>().is_err());
+}
+
+#[test]
+fn test_rate_limit_key_extract() {
+    let mut payload = std::collections::HashMap::new();
+    payload.insert("sub".to_string(), json!("alice"));
+    payload.insert("org".to_string(), json!("acme"));
+    let mut context = context_for_ip("10.0.0.1");
+    context.token = Some(JwtToken { payload });
+    context.headers.insert("x-api-key".to_string(), "k1".to_string());
+
+    assert_eq!(RateLimitKey::ClientIp.extract(&context), "ip:10.0.0.1");
+    assert_eq!(RateLimitKey::Subject.extract(&context), "sub:alice");
+    assert_eq!(RateLimitKey::ApiKey("X-Api-Key".to_string()).extract(&context), "key:k1");
+    assert_eq!(RateLimitKey::Tenant("org".to_string()).extract(&context), "tenant:acme");
+
+    // 缺失时回退到客户端IP
+    assert_eq!(RateLimitKey::Subject.extract(&context_for_ip("10.0.0.2")), "ip:10.0.0.2");
+}
+
+#[test]
+fn test_in_memory_store_token_bucket() {
+    let store = InMemoryRateLimitStore::new();
+    let limit = RateLimit::new(2, Duration::from_secs(60));
+
+    let first = store.acquire("a", &limit);
+    assert!(first.allowed);
+    assert_eq!(first.remaining, 1);
+
+    assert!(store.acquire("a", &limit).allowed);
+    let denied = store.acquire("a", &limit);
+    assert!(!denied.allowed);
+    assert_eq!(denied.remaining, 0);
+    assert!(denied.reset_after > Duration::from_secs(29));
+
+    // 不同的键互不影响
+    assert!(store.acquire("b", &limit).allowed);
+}
+
+#[test]
+fn test_in_memory_store_stays_bounded() {
+    let store = InMemoryRateLimitStore::new().with_max_keys(3);
+    let limit = RateLimit::new(2, Duration::from_secs(60));
+    for i in 0..100 {
+        store.acquire(&format!("client-{}", i), &limit);
+        assert!(store.len() <= 3);
+    }
+
+    // 超出上限时删除最久未使用的键，其余键的计数保留
+    assert!(store.acquire("client-99", &limit).allowed);
+    assert!(!store.acquire("client-99", &limit).allowed);
+
+    // 已补满的桶优先删除
+    let store = InMemoryRateLimitStore::new().with_max_keys(2);
+    let fast = RateLimit::new(1, Duration::from_millis(10));
+    store.acquire("idle", &fast);
+    store.acquire("busy", &limit);
+    std::thread::sleep(Duration::from_millis(20));
+    store.acquire("new", &limit);
+    assert_eq!(store.len(), 2);
+    assert!(store.acquire("busy", &limit).allowed);
+    assert!(!store.acquire("busy", &limit).allowed);
+
+    // 达到上限时一次腾出上限的 1/10
+    let store = InMemoryRateLimitStore::new().with_max_keys(20);
+    for i in 0..21 {
+        store.acquire(&format!("client-{}", i), &limit);
+    }
+    assert_eq!(store.len(), 19);
+    assert!(store.acquire("client-20", &limit).allowed);
+    assert!(!store.acquire("client-20", &limit).allowed);
+}
+
+#[test]
+fn test_rate_limit_middleware_headers_and_rejection() {
+    let middleware = RateLimitMiddleware::new("2/min".parse().unwrap());
+
+    let mut context = context_for_ip("10.0.0.1");
+    middleware.handle(&mut context).unwrap();
+    assert_eq!(header(&context.response_headers, "RateLimit-Limit"), Some("2"));
+    assert_eq!(header(&context.response_headers, "RateLimit-Remaining"), Some("1"));
+
+    middleware.handle(&mut context_for_ip("10.0.0.1")).unwrap();
+    let result = middleware.handle(&mut context_for_ip("10.0.0.1"));
+    assert!(matches!(result, Err(Error::TooManyRequests { retry_after }) if retry_after >= 30));
+
+    // 其他IP仍有配额
+    assert!(middleware.handle(&mut context_for_ip("10.0.0.2")).is_ok());
+}
+
+#[test]
+fn test_rate_limit_shared_store_scopes() {
+    let store: Arc<dyn RateLimitStore> = Arc::new(InMemoryRateLimitStore::new());
+    let limit = "1/min".parse().unwrap();
+    let users = RateLimitMiddleware::new(limit).scope("users").store(store.clone());
+    let reports = RateLimitMiddleware::new(limit).scope("reports").store(store);
+
+    assert!(users.handle(&mut context_for_ip("10.0.0.1")).is_ok());
+    assert!(reports.handle(&mut context_for_ip("10.0.0.1")).is_ok());
+    assert!(users.handle(&mut context_for_ip("10.0.0.1")).is_err());
+}
+
+#[test]
+fn test_rate_limit_per_route_response() {
+    #[uni_routing(route = "/api/reports", method = "GET", rate_limit = "1/min")]
+    #[allow(dead_code)]
+    async fn get_reports() {}
+
+    let route = __register_route_get_reports();
+    assert_eq!(route.middleware[0].name, "rate_limit");
+    assert_eq!(route.middleware[0].args, vec!["1/min".to_string()]);
+
+    let routes = vec![
+        route,
+        RouteInfo { path: "/api/health".to_string(), method: HttpMethod::GET, ..Default::default() },
+    ];
+    let chain = MiddlewareChain::new()
+        .with_routes(routes, &MiddlewareFactory::default())
+        .unwrap();
+
+    let mut context = chain.build_context("GET", "/api/reports", Vec::new());
+    assert!(chain.process_request(&mut context).is_none());
+
+    let mut context = chain.build_context("GET", "/api/reports", Vec::new());
+    let response = chain.process_request(&mut context).expect("second request should be limited");
+    assert_eq!(response.status, 429);
+    assert!(header(&response.headers, "Retry-After").is_some());
+    assert_eq!(header(&response.headers, "RateLimit-Remaining"), Some("0"));
+
+    // 未声明限流的路由不受影响
+    for _ in 0..3 {
+        let mut context = chain.build_context("GET", "/api/health", Vec::new());
+        assert!(chain.process_request(&mut context).is_none());
+    }
+}
+
+#[test]
+fn test_rate_limit_factory_rejects_invalid_limit() {
+    let routes = vec![RouteInfo {
+        path: "/api/reports".to_string(),
+        middleware: vec![uni_routing::middleware::MiddlewareSpec::new("rate_limit", vec!["often".to_string()])],
+        ..Default::default()
+    }];
+    assert!(MiddlewareChain::new().with_routes(routes, &MiddlewareFactory::default()).is_err());
+}
@@ -0,0 +1,173 @@
+//! 路由注册表的单元测试
+
+#![allow(dead_code)]
+
+mod common;
+
+use std::sync::Arc;
+
+use common::Path;
+use uni_routing::registry::{self, RouteRegistry};
+use uni_routing::routing::{HttpMethod, RouteInfo};
+use uni_routing::Error;
+use uni_routing_macros::uni_routing;
+
+#[uni_routing(route = "/api/users", method = "GET", auth_policy = "role:admin", description = "List users")]
+async fn get_users() {}
+
+#[uni_routing(route = "/api/users", method = "POST", auth_policy = "role:admin", description = "Create user")]
+async fn create_user() {}
+
+#[uni_routing(route = "/api/users/{id}", method = "GET", description = "Get user")]
+async fn get_user_by_id(_path: Path<u64>) {}
+
+#[uni_routing(route = "/api/users/{id}", method = "PUT", description = "Update user")]
+async fn update_user(_path: Path<u64>) {}
+
+#[uni_routing(route = "/api/users/{id}", method = "DELETE", description = "Delete user")]
+async fn delete_user(_path: Path<u64>) {}
+
+fn crud_registry() -> RouteRegistry {
+    let registry = RouteRegistry::new();
+    for route in [
+        __register_route_get_users(),
+        __register_route_create_user(),
+        __register_route_get_user_by_id(),
+        __register_route_update_user(),
+        __register_route_delete_user(),
+    ] {
+        registry.register_route(route).unwrap();
+    }
+    registry
+}
+
+#[test]
+fn test_openapi_documents_all_crud_methods() {
+    let spec = crud_registry().generate_openapi("Users API", "1.0.0");
+    let paths = spec["paths"].as_object().unwrap();
+    assert_eq!(paths.len(), 2);
+
+    let collection = paths["/api/users"].as_object().unwrap();
+    let mut methods: Vec<_> = collection.keys().cloned().collect();
+    methods.sort();
+    assert_eq!(methods, vec!["get", "post"]);
+    assert_eq!(collection["get"]["summary"], "List users");
+    assert_eq!(collection["post"]["summary"], "Create user");
+
+    let item = paths["/api/users/{id}"].as_object().unwrap();
+    let mut methods: Vec<_> = item.keys().cloned().collect();
+    methods.sort();
+    assert_eq!(methods, vec!["delete", "get", "put"]);
+    for method in ["get", "put", "delete"] {
+        assert_eq!(item[method]["parameters"][0]["name"], "id");
+    }
+}
+
+#[test]
+fn test_register_duplicate_route_is_rejected() {
+    let registry = crud_registry();
+
+    let result = registry.register_route(__register_route_get_users());
+    assert!(matches!(result, Err(Error::Other(message)) if message.contains("GET /api/users")));
+
+    // `<id>` 与 `{id}` 表示同一路径
+    let duplicate = RouteInfo {
+        path: "/api/users/<id>".to_string(),
+        method: HttpMethod::DELETE,
+        ..Default::default()
+    };
+    assert!(registry.register_route(duplicate).is_err());
+
+    // 同一路径的新方法可以注册
+    let patch = RouteInfo {
+        path: "/api/users/{id}".to_string(),
+        method: HttpMethod::PATCH,
+        ..Default::default()
+    };
+    assert!(registry.register_route(patch).is_ok());
+    assert_eq!(registry.get_routes().len(), 6);
+}
+
+#[test]
+fn test_frozen_registry_rejects_registration() {
+    let registry = crud_registry();
+    assert!(!registry.is_frozen());
+
+    let frozen = registry.freeze().unwrap();
+    assert_eq!(frozen.len(), 5);
+    assert!(registry.is_frozen());
+
+    let patch = RouteInfo {
+        path: "/api/users/{id}".to_string(),
+        method: HttpMethod::PATCH,
+        ..Default::default()
+    };
+    let result = registry.register_route(patch);
+    assert!(matches!(result, Err(Error::Other(message)) if message.contains("frozen")));
+
+    // 冻结后的读取与冻结时的路由表一致
+    assert_eq!(registry.freeze().unwrap().len(), 5);
+    assert_eq!(registry.get_routes().len(), 5);
+    assert!(registry.get_route("/api/users/{id}", HttpMethod::PUT).is_some());
+}
+
+#[test]
+fn test_lookup_before_freeze_sees_new_routes() {
+    let registry = RouteRegistry::new();
+    registry.register_route(RouteInfo { path: "/api/users/{id}".to_string(), ..Default::default() }).unwrap();
+    assert!(registry.get_route("/api/users/me", HttpMethod::GET).unwrap().path.ends_with("{id}"));
+
+    // 注册新路由后缓存的匹配器失效
+    registry.register_route(RouteInfo { path: "/api/users/me".to_string(), ..Default::default() }).unwrap();
+    assert_eq!(registry.get_route("/api/users/me", HttpMethod::GET).unwrap().path, "/api/users/me");
+    assert!(registry.get_route("/api/posts", HttpMethod::GET).is_none());
+}
+
+#[test]
+fn test_concurrent_registration() {
+    let registry = RouteRegistry::new();
+    std::thread::scope(|scope| {
+        for thread in 0..8 {
+            let registry = &registry;
+            scope.spawn(move || {
+                for index in 0..16 {
+                    let route = RouteInfo {
+                        path: format!("/api/{}/{}", thread, index),
+                        ..Default::default()
+                    };
+                    registry.register_route(route).unwrap();
+                }
+            });
+        }
+    });
+    assert_eq!(registry.freeze().unwrap().len(), 128);
+}
+
+#[test]
+fn test_isolated_registries_do_not_share_state() {
+    let handles: Vec<_> = (0..4)
+        .map(|thread| {
+            std::thread::spawn(move || {
+                registry::with_isolated_registry(|isolated| {
+                    for index in 0..=thread {
+                        let route = RouteInfo {
+                            path: format!("/api/items/{}", index),
+                            ..Default::default()
+                        };
+                        registry::register_route(route).unwrap();
+                    }
+                    assert!(Arc::ptr_eq(isolated, &registry::get_global_registry()));
+                    registry::get_all_routes().len()
+                })
+            })
+        })
+        .collect();
+    let counts: Vec<usize> = handles.into_iter().map(|handle| handle.join().unwrap()).collect();
+    assert_eq!(counts, vec![1, 2, 3, 4]);
+
+    // 隔离注册表结束后恢复全局注册表
+    registry::with_isolated_registry(|isolated| {
+        assert!(Arc::ptr_eq(isolated, &registry::get_global_registry()));
+    });
+    assert!(registry::get_all_routes().iter().all(|route| !route.path.starts_with("/api/items")));
+}
@@ -0,0 +1,202 @@
+//! 路由配置热加载的单元测试
+
+#![allow(dead_code)]
+
+mod common;
+
+use common::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use uni_routing::middleware::{MiddlewareChain, MiddlewareFactory};
+use uni_routing::reload::ConfigReloader;
+use uni_routing::routing::RouteInfo;
+use uni_routing_macros::uni_routing;
+
+#[uni_routing(route = "/api/users", method = "GET", middleware = [rate_limit("1/min")])]
+async fn list_users() {}
+
+#[uni_routing(route = "/api/users", method = "POST", auth_policy = "role:admin")]
+async fn create_user() {}
+
+#[uni_routing(route = "/api/users/{id}", method = "GET", auth_policy = "role:user")]
+async fn get_user(_path: Path<u64>) {}
+
+fn routes() -> Vec<RouteInfo> {
+    vec![
+        __register_route_list_users(),
+        __register_route_create_user(),
+        __register_route_get_user(),
+    ]
+}
+
+/// 临时配置文件，测试结束时删除
+struct ConfigFile(PathBuf);
+
+impl ConfigFile {
+    fn new(name: &str, content: &str) -> Self {
+        let path = std::env::temp_dir().join(format!("uni_routing_reload_{}_{}.toml", name, std::process::id()));
+        std::fs::write(&path, content).unwrap();
+        Self(path)
+    }
+
+    fn write(&self, content: &str) {
+        std::fs::write(&self.0, content).unwrap();
+    }
+}
+
+impl Drop for ConfigFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+const INITIAL: &str = r#"
+[[routes]]
+operation_id = "create_user"
+disabled = true
+"#;
+
+const UPDATED: &str = r#"
+[[routes]]
+operation_id = "get_user"
+public = true
+remap = "/api/people/{id}"
+
+[[routes]]
+operation_id = "list_users"
+rate_limit = "100/min"
+"#;
+
+fn status(chain: &MiddlewareChain, method: &str, path: &str) -> Option<u16> {
+    let mut context = chain.build_context(method, path, []);
+    chain.process_request(&mut context).map(|response| response.status)
+}
+
+fn reloader(file: &ConfigFile) -> (Arc<MiddlewareChain>, ConfigReloader) {
+    let chain = Arc::new(MiddlewareChain::new());
+    let reloader = ConfigReloader::new(&file.0, routes(), chain.clone(), MiddlewareFactory::default()).unwrap();
+    (chain, reloader)
+}
+
+#[test]
+fn test_reload_swaps_route_table() {
+    let file = ConfigFile::new("swap", INITIAL);
+    let (chain, reloader) = reloader(&file);
+    assert_eq!(status(&chain, "POST", "/api/users"), Some(404));
+    assert!(reloader.check().is_none());
+
+    file.write(UPDATED);
+    let change = reloader.check().unwrap().unwrap();
+    assert_eq!(
+        change.to_string(),
+        "3 routes changed: GET /api/users: rate limit `1/min` -> `100/min`; POST /api/users: enabled; \
+         GET /api/users/{id}: path `/api/users/{id}` -> `/api/people/{id}`, auth `role:user` -> public"
+    );
+    // 重新启用的路由按其认证策略认证
+    assert_eq!(status(&chain, "POST", "/api/users"), Some(401));
+    assert_eq!(status(&chain, "GET", "/api/users/1"), Some(404));
+    let context = chain.build_context("GET", "/api/people/1", []);
+    assert_eq!(context.path, "/api/users/1");
+
+    let stats = reloader.stats();
+    assert_eq!((stats.reloads, stats.failures), (1, 0));
+    assert!(stats.last_reload.is_some());
+}
+
+#[test]
+fn test_invalid_config_keeps_current_table() {
+    let file = ConfigFile::new("invalid", INITIAL);
+    let (chain, reloader) = reloader(&file);
+
+    file.write("[[routes]]\noperation_id = \"missing\"\ndisabled = true");
+    let error = reloader.check().unwrap().unwrap_err();
+    assert!(error.to_string().contains("operation `missing`: matches no route"));
+    assert_eq!(status(&chain, "POST", "/api/users"), Some(404));
+    assert_eq!(reloader.applied().disabled.len(), 1);
+    // 同一内容不重复报告
+    assert!(reloader.check().is_none());
+
+    let stats = reloader.stats();
+    assert_eq!((stats.reloads, stats.failures), (0, 1));
+    assert!(stats.last_error.unwrap().contains("matches no route"));
+
+    file.write("[[routes]]\noperation_id = \"list_users\"\nrate_limit = \"lots\"");
+    assert!(reloader.reload().is_err());
+    assert_eq!(reloader.stats().failures, 2);
+
+    // 启动时的无效配置直接返回错误
+    assert!(ConfigReloader::new(&file.0, routes(), Arc::new(MiddlewareChain::new()), MiddlewareFactory::default()).is_err());
+}
+
+#[test]
+fn test_rollback() {
+    let file = ConfigFile::new("rollback", INITIAL);
+    let (chain, reloader) = reloader(&file);
+    assert!(reloader.rollback().is_err());
+
+    file.write(UPDATED);
+    reloader.reload().unwrap();
+    assert_eq!(status(&chain, "POST", "/api/users"), Some(401));
+
+    let change = reloader.rollback().unwrap();
+    assert_eq!(change.changes.len(), 3);
+    assert_eq!(change.changes[1].message, "disabled");
+    assert_eq!(status(&chain, "POST", "/api/users"), Some(404));
+    assert_eq!(reloader.applied().remapped.len(), 0);
+}
+
+#[test]
+fn test_reload_preserves_middleware_state() {
+    let file = ConfigFile::new("state", INITIAL);
+    let (chain, reloader) = reloader(&file);
+    assert_eq!(status(&chain, "GET", "/api/users"), None);
+    assert_eq!(status(&chain, "GET", "/api/users"), Some(429));
+
+    // 限流声明未变的路由沿用原有的计数
+    file.write("[[routes]]\noperation_id = \"get_user\"\npublic = true");
+    assert_eq!(reloader.reload().unwrap().changes.len(), 2);
+    assert_eq!(status(&chain, "GET", "/api/users"), Some(429));
+
+    // 修改限流配额后重新计数
+    file.write("[[routes]]\noperation_id = \"list_users\"\nrate_limit = \"2/min\"");
+    reloader.reload().unwrap();
+    assert_eq!(status(&chain, "GET", "/api/users"), None);
+}
+
+#[test]
+fn test_reload_applies_auth_policy() {
+    let file = ConfigFile::new("auth", INITIAL);
+    let (chain, reloader) = reloader(&file);
+    assert_eq!(status(&chain, "GET", "/api/users"), None);
+
+    // 重新加载的认证策略对下一个请求生效
+    file.write("[[routes]]\noperation_id = \"list_users\"\nauth_policy = \"role:admin\"");
+    reloader.reload().unwrap();
+    assert_eq!(status(&chain, "GET", "/api/users"), Some(401));
+
+    file.write(INITIAL);
+    reloader.reload().unwrap();
+    assert_eq!(status(&chain, "GET", "/api/users/1"), Some(401));
+    file.write("[[routes]]\noperation_id = \"get_user\"\npublic = true");
+    reloader.reload().unwrap();
+    assert_eq!(status(&chain, "GET", "/api/users/1"), None);
+}
+
+#[test]
+fn test_watch_picks_up_changes() {
+    let file = ConfigFile::new("watch", INITIAL);
+    let (chain, reloader) = reloader(&file);
+    let reloader = Arc::new(reloader);
+    let watcher = reloader.clone().watch(Duration::from_millis(10));
+
+    file.write(UPDATED);
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while reloader.stats().reloads == 0 && Instant::now() < deadline {
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    watcher.stop();
+    assert_eq!(reloader.stats().reloads, 1);
+    assert_eq!(status(&chain, "POST", "/api/users"), Some(401));
+}
@@ -1,6 +1,8 @@
 //! 路由模块的单元测试
 
-use uni_routing::routing::{RouteInfo, HttpMethod};
+use std::str::FromStr;
+
+use uni_routing::routing::{find_route, RouteInfo, HttpMethod, PathSegment};
 use uni_routing::auth::AuthPolicy;
 
 #[test]
@@ -10,6 +12,7 @@ fn test_route_info_creation() {
         method: HttpMethod::GET,
         auth_policy: Some(AuthPolicy::new("roles:admin")),
         description: Some("Get all users".to_string()),
+        ..Default::default()
     };
     
     assert_eq!(route.path, "/api/users");
@@ -29,11 +32,110 @@ fn test_http_method_display() {
 
 #[test]
 fn test_http_method_from_str() {
-    assert!(matches!(HttpMethod::from_str("GET"), HttpMethod::GET));
-    assert!(matches!(HttpMethod::from_str("POST"), HttpMethod::POST));
-    assert!(matches!(HttpMethod::from_str("PUT"), HttpMethod::PUT));
-    assert!(matches!(HttpMethod::from_str("DELETE"), HttpMethod::DELETE));
-    assert!(matches!(HttpMethod::from_str("PATCH"), HttpMethod::PATCH));
-    // 默认情况
-    assert!(matches!(HttpMethod::from_str("INVALID"), HttpMethod::GET));
-}
\ No newline at end of file
+    assert!(matches!(HttpMethod::from_str("GET"), Ok(HttpMethod::GET)));
+    assert!(matches!(HttpMethod::from_str("POST"), Ok(HttpMethod::POST)));
+    assert!(matches!(HttpMethod::from_str("PUT"), Ok(HttpMethod::PUT)));
+    assert!(matches!(HttpMethod::from_str("DELETE"), Ok(HttpMethod::DELETE)));
+    assert!(matches!(HttpMethod::from_str("PATCH"), Ok(HttpMethod::PATCH)));
+    assert!(matches!(HttpMethod::from_str("head"), Ok(HttpMethod::HEAD)));
+    assert!(matches!(HttpMethod::from_str("OPTIONS"), Ok(HttpMethod::OPTIONS)));
+    assert!(matches!(HttpMethod::from_str("TRACE"), Ok(HttpMethod::TRACE)));
+    assert!(matches!(HttpMethod::from_str("CONNECT"), Ok(HttpMethod::CONNECT)));
+    // 其他合法的方法名为扩展方法
+    assert_eq!("PROPFIND".parse::<HttpMethod>().unwrap(), HttpMethod::Other("PROPFIND".to_string()));
+    assert_eq!(HttpMethod::Other("PROPFIND".to_string()).to_string(), "PROPFIND");
+    // 扩展方法必须大写，避免拼写错误的标准方法被当作扩展方法
+    assert!("propfind".parse::<HttpMethod>().is_err());
+    assert!("Gett".parse::<HttpMethod>().is_err());
+    // 非法的方法名返回错误
+    assert!(HttpMethod::from_str("").is_err());
+    assert!("GET /".parse::<HttpMethod>().is_err());
+}
+
+#[test]
+fn test_find_route_derives_head_from_get() {
+    let routes = vec![
+        RouteInfo {
+            path: "/api/users".to_string(),
+            method: HttpMethod::GET,
+            ..Default::default()
+        },
+        RouteInfo {
+            path: "/api/health".to_string(),
+            method: HttpMethod::GET,
+            ..Default::default()
+        },
+        RouteInfo {
+            path: "/api/health".to_string(),
+            method: HttpMethod::HEAD,
+            description: Some("probe".to_string()),
+            ..Default::default()
+        },
+        RouteInfo {
+            path: "/files/{name}".to_string(),
+            method: HttpMethod::Other("PROPFIND".to_string()),
+            ..Default::default()
+        },
+    ];
+
+    let route = find_route(&routes, "HEAD", "/api/users").unwrap();
+    assert_eq!(route.method, HttpMethod::GET);
+
+    // 显式注册的 HEAD 路由优先
+    let route = find_route(&routes, "HEAD", "/api/health").unwrap();
+    assert_eq!(route.description.as_deref(), Some("probe"));
+
+    assert!(find_route(&routes, "POST", "/api/users").is_none());
+    assert!(find_route(&routes, "PROPFIND", "/files/a.txt").is_some());
+    // 扩展方法区分大小写
+    assert!(find_route(&routes, "propfind", "/files/a.txt").is_none());
+}
+
+#[test]
+fn test_route_info_matches() {
+    let route = RouteInfo {
+        path: "/api/users/{id}".to_string(),
+        method: HttpMethod::GET,
+        ..Default::default()
+    };
+
+    assert!(route.matches("GET", "/api/users/123"));
+    assert!(route.matches("get", "/api/users/123/"));
+    assert!(!route.matches("POST", "/api/users/123"));
+    assert!(!route.matches("GET", "/api/users"));
+    assert!(!route.matches("GET", "/api/users/123/posts"));
+
+    let rocket_route = RouteInfo {
+        path: "/api/users/<id>".to_string(),
+        method: HttpMethod::DELETE,
+        ..Default::default()
+    };
+    assert!(rocket_route.matches("DELETE", "/api/users/7"));
+}
+
+#[test]
+fn test_path_segment_parse_template() {
+    assert_eq!(PathSegment::parse_template("/api/users/{id}/posts/<post_id>"), vec![
+        PathSegment::Static("api".to_string()),
+        PathSegment::Static("users".to_string()),
+        PathSegment::Param("id".to_string()),
+        PathSegment::Static("posts".to_string()),
+        PathSegment::Param("post_id".to_string()),
+    ]);
+    assert!(PathSegment::parse_template("/").is_empty());
+}
+
+#[test]
+fn test_route_info_openapi_path() {
+    let route = RouteInfo {
+        path: "/api/users/<id>".to_string(),
+        ..Default::default()
+    };
+    assert_eq!(route.openapi_path(), "/api/users/{id}");
+
+    let root = RouteInfo {
+        path: "/".to_string(),
+        ..Default::default()
+    };
+    assert_eq!(root.openapi_path(), "/");
+}
@@ -0,0 +1,188 @@
+//! 请求/响应Schema推断的单元测试
+
+#![allow(dead_code)]
+
+mod common;
+
+use common::Path;
+use serde_json::json;
+#[cfg(feature = "swagger")]
+use uni_routing::registry::RouteRegistry;
+use uni_routing::schema;
+use uni_routing_macros::uni_routing;
+
+/// 模拟框架提供的 `Json<T>` 提取器
+struct Json<T>(T);
+
+#[test]
+fn test_primitive_schemas() {
+    assert_eq!(schema::integer("int64", true).schema, json!({"type": "integer", "format": "int64", "minimum": 0}));
+    assert_eq!(schema::string().schema, json!({"type": "string"}));
+    assert_eq!(
+        schema::array(schema::boolean()).schema,
+        json!({"type": "array", "items": {"type": "boolean"}})
+    );
+}
+
+#[test]
+fn test_macro_infers_primitive_body_and_response() {
+    #[uni_routing(route = "/api/tags", method = "POST")]
+    async fn create_tags(_body: Json<Vec<String>>) -> Result<(u16, Json<Vec<u64>>), String> {
+        Ok((201, Json(vec![1])))
+    }
+
+    let route = __register_route_create_tags();
+    assert_eq!(
+        route.request_body.unwrap().schema,
+        json!({"type": "array", "items": {"type": "string"}})
+    );
+    assert_eq!(route.responses.len(), 1);
+    assert_eq!(route.responses[0].status, 201);
+    assert_eq!(
+        route.responses[0].schema.as_ref().unwrap().schema,
+        json!({"type": "array", "items": {"type": "integer", "format": "int64", "minimum": 0}})
+    );
+}
+
+#[test]
+fn test_macro_infers_status_from_tuple() {
+    struct StatusCode;
+    impl StatusCode {
+        const ACCEPTED: u16 = 202;
+    }
+
+    #[uni_routing(route = "/api/jobs", method = "POST")]
+    async fn create_job() -> (u16, Json<u64>) {
+        (StatusCode::ACCEPTED, Json(1))
+    }
+
+    #[uni_routing(route = "/api/jobs/{id}", method = "PUT")]
+    async fn upsert_job(_path: Path<u64>, created: bool) -> (u16, Json<u64>) {
+        if created {
+            (201, Json(1))
+        } else {
+            (200, Json(1))
+        }
+    }
+
+    assert_eq!(__register_route_create_job().responses[0].status, 202);
+    // 状态码不唯一时回退到200
+    assert_eq!(__register_route_upsert_job().responses[0].status, 200);
+}
+
+#[test]
+fn test_macro_without_json_types() {
+    #[uni_routing(route = "/api/health", method = "GET")]
+    async fn health() -> String {
+        String::new()
+    }
+
+    let route = __register_route_health();
+    assert!(route.request_body.is_none());
+    assert!(route.responses[0].schema.is_none());
+}
+
+#[cfg(feature = "swagger")]
+#[derive(utoipa::ToSchema)]
+struct CreateUserRequest {
+    name: String,
+    email: String,
+}
+
+#[cfg(feature = "swagger")]
+#[derive(utoipa::ToSchema)]
+struct User {
+    id: u64,
+    name: String,
+}
+
+#[cfg(feature = "swagger")]
+#[test]
+fn test_openapi_components_from_handler_signature() {
+    #[uni_routing(route = "/api/users", method = "POST", description = "Create user")]
+    async fn create_user(_body: Json<CreateUserRequest>) -> Json<Vec<User>> {
+        Json(Vec::new())
+    }
+
+    let registry = RouteRegistry::new();
+    registry.register_route(__register_route_create_user()).unwrap();
+    let spec = registry.generate_openapi("Test API", "1.0.0");
+
+    let operation = &spec["paths"]["/api/users"]["post"];
+    assert_eq!(
+        operation["requestBody"]["content"]["application/json"]["schema"],
+        json!({"$ref": "#/components/schemas/CreateUserRequest"})
+    );
+    assert_eq!(
+        operation["responses"]["200"]["content"]["application/json"]["schema"],
+        json!({"type": "array", "items": {"$ref": "#/components/schemas/User"}})
+    );
+
+    let schemas = &spec["components"]["schemas"];
+    assert_eq!(schemas["CreateUserRequest"]["type"], json!("object"));
+    assert!(schemas["CreateUserRequest"]["properties"]["email"].is_object());
+    assert!(schemas["User"]["properties"]["id"].is_object());
+}
+
+#[cfg(feature = "swagger")]
+#[derive(utoipa::ToSchema)]
+struct Profile {
+    nickname: Option<String>,
+    manager: Option<User>,
+}
+
+#[cfg(feature = "swagger")]
+#[test]
+fn test_component_nullable_uses_openapi_31_type_union() {
+    let schema = schema::component::<Profile>();
+    let profile = &schema.components["Profile"];
+    let nickname = &profile["properties"]["nickname"];
+    assert_eq!(nickname["type"], json!(["string", "null"]));
+    assert!(nickname.get("nullable").is_none());
+
+    let manager = &profile["properties"]["manager"];
+    assert!(manager.get("nullable").is_none());
+    assert!(manager["oneOf"].as_array().unwrap().contains(&json!({"type": "null"})));
+    assert!(!profile.to_string().contains("nullable"));
+}
+
+#[cfg(feature = "swagger")]
+#[derive(utoipa::ToSchema)]
+struct Team {
+    lead: Profile,
+    members: Vec<User>,
+}
+
+#[cfg(feature = "swagger")]
+#[test]
+fn test_component_collects_nested_schemas() {
+    #[uni_routing(route = "/api/teams", method = "POST")]
+    async fn create_team(_body: Json<Team>) {}
+
+    let registry = RouteRegistry::new();
+    registry.register_route(__register_route_create_team()).unwrap();
+    let spec = registry.generate_openapi("Test API", "1.0.0");
+
+    // 每个 `$ref` 都指向 components 中存在的Schema
+    let schemas = spec["components"]["schemas"].as_object().unwrap();
+    for name in ["Team", "Profile", "User"] {
+        assert!(schemas.contains_key(name), "missing component {}", name);
+    }
+    let document = spec.to_string();
+    for reference in document.split("\"$ref\":\"#/components/schemas/").skip(1) {
+        let name = &reference[..reference.find('"').unwrap()];
+        assert!(schemas.contains_key(name), "dangling reference to {}", name);
+    }
+}
+
+#[cfg(not(feature = "swagger"))]
+#[test]
+fn test_component_title_strips_generic_arguments() {
+    mod api {
+        pub struct User;
+    }
+    struct Page<T>(T);
+
+    assert_eq!(schema::component::<Page<api::User>>().schema["title"], json!("Page"));
+    assert_eq!(schema::component::<api::User>().schema["title"], json!("User"));
+}
@@ -0,0 +1,132 @@
+//! 路由快照序列化的单元测试
+
+#![allow(dead_code)]
+
+mod common;
+
+use common::Path;
+use serde_json::json;
+use uni_routing::auth::AuthPolicy;
+use uni_routing::registry::RouteRegistry;
+use uni_routing::routing::{HttpMethod, RouteInfo};
+use uni_routing::snapshot::{RouteSnapshot, SnapshotFormat};
+use uni_routing_macros::uni_routing;
+
+/// 模拟框架提供的 `Json<T>` 提取器
+struct Json<T>(T);
+
+#[uni_routing(
+    route = "/users",
+    method = "POST",
+    auth_policy = "role:admin",
+    tags = ["users"],
+    middleware = [rate_limit("10/s"), logging],
+    version = "v2",
+    description = "Create user"
+)]
+async fn create_user(_body: Json<Vec<String>>) -> Json<Vec<u64>> {
+    Json(Vec::new())
+}
+
+#[uni_routing(route = "/users/{id:u64}", method = "GET")]
+async fn get_user(_id: Path<u64>) -> String {
+    String::new()
+}
+
+fn registry() -> RouteRegistry {
+    let registry = RouteRegistry::new();
+    registry.register_route(__register_route_create_user()).unwrap();
+    registry.register_route(__register_route_get_user()).unwrap();
+    registry
+        .register_route(RouteInfo {
+            path: "/dav/{*path}".to_string(),
+            method: "PROPFIND".parse().unwrap(),
+            deprecated: true,
+            request_example: Some(json!({"depth": 1})),
+            ..Default::default()
+        })
+        .unwrap();
+    registry
+}
+
+#[test]
+fn test_json_round_trip() {
+    let snapshot = registry().snapshot();
+    let json = snapshot.to_json().unwrap();
+    assert_eq!(RouteSnapshot::from_json(&json).unwrap(), snapshot);
+
+    // 方法与认证策略序列化为字符串，空字段省略
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(value["routes"][0]["method"], json!("POST"));
+    assert_eq!(value["routes"][0]["auth_policy"], json!("role:admin"));
+    assert_eq!(value["routes"][0]["middleware"][1], json!({"name": "logging"}));
+    assert_eq!(value["routes"][2]["method"], json!("PROPFIND"));
+    assert!(value["routes"][1].get("auth_policy").is_none());
+    assert!(value["routes"][1].get("deprecated").is_none());
+}
+
+#[test]
+fn test_toml_round_trip() {
+    let snapshot = registry().snapshot();
+    let toml = snapshot.to_toml().unwrap();
+    assert!(toml.contains("[[routes]]"));
+    assert!(toml.contains("auth_policy = \"role:admin\""));
+    assert_eq!(RouteSnapshot::from_toml(&toml).unwrap(), snapshot);
+}
+
+#[test]
+fn test_deserialize_minimal_routes() {
+    let snapshot = RouteSnapshot::from_toml(
+        r#"
+        [[routes]]
+        path = "/api/health"
+        method = "get"
+
+        [[routes]]
+        path = "/api/users"
+        method = "POST"
+        auth_policy = "role:admin"
+        "#,
+    )
+    .unwrap();
+    assert_eq!(snapshot.routes[0].method, HttpMethod::GET);
+    assert_eq!(snapshot.routes[1].auth_policy, Some(AuthPolicy::new("role:admin")));
+
+    assert!(RouteSnapshot::from_json(r#"{"routes": [{"path": "/", "method": "BAD METHOD"}]}"#).is_err());
+}
+
+#[test]
+fn test_snapshot_files_and_registry() {
+    let snapshot = registry().snapshot();
+    for extension in ["json", "toml"] {
+        let path = std::env::temp_dir().join(format!("uni_routing_snapshot_{}.{}", std::process::id(), extension));
+        snapshot.save(&path).unwrap();
+        let loaded = RouteSnapshot::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(loaded, snapshot);
+    }
+    assert_eq!(SnapshotFormat::from_path("routes.TOML"), SnapshotFormat::Toml);
+
+    // 快照可以重新构建注册表
+    let registry = snapshot.clone().into_registry().unwrap();
+    assert_eq!(registry.get_route("/v2/users", HttpMethod::POST).unwrap().operation_id.as_deref(), Some("create_user"));
+
+    let mut duplicated = snapshot;
+    duplicated.routes.push(duplicated.routes[0].clone());
+    assert!(duplicated.into_registry().is_err());
+}
+
+#[test]
+fn test_cli_reads_snapshots() {
+    let path = std::env::temp_dir().join(format!("uni_routing_cli_snapshot_{}.toml", std::process::id()));
+    registry().snapshot().save(&path).unwrap();
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_uni-routing"))
+        .args(["routes", path.to_str().unwrap()])
+        .output()
+        .unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("/v2/users"));
+    assert!(stdout.contains("snapshot_tests::create_user"));
+}
@@ -24,6 +24,7 @@ fn test_swagger_generator_add_route() {
         method: HttpMethod::GET,
         auth_policy: Some(AuthPolicy::new("roles:admin")),
         description: Some("Get all users".to_string()),
+        ..Default::default()
     };
     
     generator.add_route(route);
@@ -37,7 +38,7 @@ fn test_generate_openapi_spec() {
     let spec = generator.generate_openapi_spec();
     
     assert!(spec.contains("openapi"));
-    assert!(spec.contains("3.0.0"));
+    assert!(spec.contains("3.1.0"));
     assert!(spec.contains("Uni Routing API"));
 }
 
@@ -50,4 +51,21 @@ fn test_generate_swagger_ui() {
     assert!(ui_html.contains("swagger-ui"));
     assert!(ui_html.contains("SwaggerUIBundle"));
     assert!(ui_html.contains("/swagger/openapi.json"));
-}
\ No newline at end of file
+}
+
+#[test]
+#[cfg(feature = "swagger")]
+fn test_generate_openapi_spec_includes_routes() {
+    let mut generator = SwaggerGenerator::new();
+    generator.add_route(RouteInfo {
+        path: "/api/users".to_string(),
+        method: HttpMethod::GET,
+        auth_policy: Some(AuthPolicy::new("roles:admin")),
+        description: Some("Get all users".to_string()),
+        ..Default::default()
+    });
+
+    let spec: serde_json::Value = serde_json::from_str(&generator.generate_openapi_spec()).unwrap();
+    assert_eq!(spec["paths"]["/api/users"]["get"]["summary"], "Get all users");
+    assert_eq!(spec["paths"]["/api/users"]["get"]["security"][0]["bearerAuth"], serde_json::json!([]));
+}
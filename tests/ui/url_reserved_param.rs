@@ -0,0 +1,8 @@
+use uni_routing_macros::uni_routing;
+
+struct Path<T>(T);
+
+#[uni_routing(route = "/items/{self}", method = "GET")]
+async fn get_item(_path: Path<u64>) {}
+
+fn main() {}
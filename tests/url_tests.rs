@@ -0,0 +1,123 @@
+//! URL生成（反向路由）的单元测试
+
+#![allow(dead_code)]
+
+mod common;
+
+use common::Path;
+use uni_routing::config::RouteConfig;
+use uni_routing::group::RouteGroup;
+use uni_routing::registry::{self, RouteRegistry};
+use uni_routing::url::{build, encode_segment};
+use uni_routing::Error;
+use uni_routing_macros::uni_routing;
+
+/// 按字段名提取的路径参数
+struct ReportPath {
+    year: u16,
+    name: String,
+}
+
+#[uni_routing(route = "/api/users/{id}", method = "GET")]
+async fn get_user_by_id(_path: Path<u64>) {}
+
+#[uni_routing(route = "/users/{user_id}/posts/{slug}", method = "GET")]
+async fn get_post(_path: Path<(i32, String)>) {}
+
+#[uni_routing(route = "/articles/{page?}", method = "GET")]
+async fn list_articles(_path: Path<Option<u32>>) {}
+
+#[uni_routing(route = "/files/<path..>", method = "GET")]
+async fn get_file(path: String) -> String {
+    path
+}
+
+#[uni_routing(route = "/reports/{year}/{name}", method = "GET", version = "v2")]
+async fn get_report(_path: Path<ReportPath>) {}
+
+#[uni_routing(route = "/api/health", method = "GET")]
+async fn health() {}
+
+#[test]
+fn test_generated_url_builders() {
+    assert_eq!(get_user_by_id_url(42), "/api/users/42");
+    assert_eq!(get_post_url(7, "hello world/é"), "/users/7/posts/hello%20world%2F%C3%A9");
+    assert_eq!(list_articles_url(None), "/articles");
+    assert_eq!(list_articles_url(Some(2)), "/articles/2");
+    assert_eq!(get_file_url("docs/a b.txt"), "/files/docs/a%20b.txt");
+    // 结构体提取器的参数接受任何实现 Display 的值，版本前缀包含在路径中
+    assert_eq!(get_report_url(2024, "q1 summary"), "/v2/reports/2024/q1%20summary");
+    assert_eq!(health_url(), "/api/health");
+}
+
+/// 与处理函数同名的模块不与URL构建函数冲突
+mod health {}
+
+#[test]
+fn test_generated_url_builders_use_registered_paths() {
+    registry::with_isolated_registry(|registry| {
+        // 分组前缀与配置重映射后的路径
+        registry.register_group(&RouteGroup::new("/admin").route(__register_route_get_user_by_id())).unwrap();
+        let config = RouteConfig::from_toml(
+            r#"
+            [[routes]]
+            operation_id = "health"
+            remap = "/healthz"
+            "#,
+        )
+        .unwrap();
+        registry.register_route(__register_route_health()).unwrap();
+        registry.apply_config(&config).unwrap();
+
+        assert_eq!(get_user_by_id_url(42), "/admin/api/users/42");
+        assert_eq!(health_url(), "/healthz");
+        // 未注册的路由使用声明的模板
+        assert_eq!(get_post_url(1, "intro"), "/users/1/posts/intro");
+    });
+    assert_eq!(get_user_by_id_url(42), "/api/users/42");
+}
+
+#[test]
+fn test_encode_segment() {
+    assert_eq!(encode_segment("AZaz09-._~"), "AZaz09-._~");
+    assert_eq!(encode_segment("a/b?c#d%e&f"), "a%2Fb%3Fc%23d%25e%26f");
+    assert_eq!(encode_segment(".."), "%2E%2E");
+    assert_eq!(encode_segment("..."), "...");
+}
+
+#[test]
+fn test_build_reports_parameter_errors() {
+    assert_eq!(build("/users/{id:u64}/{tab?}", &[("id", "1")]).unwrap(), "/users/1");
+    assert_eq!(build::<&str>("/", &[]).unwrap(), "/");
+
+    let Err(Error::Other(message)) = build("/users/{id}/{*rest}", &[("name", "x")]) else {
+        panic!("missing and unknown parameters must be rejected");
+    };
+    assert_eq!(
+        message,
+        "Cannot build URL for `/users/{id}/{*rest}`: unknown path parameter `name`, missing path parameter `id`, missing path parameter `rest`"
+    );
+}
+
+#[test]
+fn test_registry_url_for() {
+    let registry = RouteRegistry::new();
+    for route in [__register_route_get_user_by_id(), __register_route_get_post(), __register_route_get_report()] {
+        registry.register_route(route).unwrap();
+    }
+
+    assert_eq!(registry.url_for("get_user_by_id", &[("id", "a b")]).unwrap(), "/api/users/a%20b");
+    assert_eq!(
+        registry.url_for("get_report", &[("year", "2024".to_string()), ("name", "q1".to_string())]).unwrap(),
+        "/v2/reports/2024/q1"
+    );
+    assert!(registry.url_for("get_post", &[("user_id", "1")]).is_err());
+    assert_eq!(
+        registry.url_for("missing", &[("id", "1")]).unwrap_err().to_string(),
+        Error::Other("No route registered for operation `missing`".to_string()).to_string()
+    );
+
+    registry.freeze().unwrap();
+    assert_eq!(registry.get_route_by_operation("get_post").unwrap().path, "/users/{user_id}/posts/{slug}");
+    assert_eq!(registry.url_for("get_post", &[("user_id", "1"), ("slug", "intro")]).unwrap(), "/users/1/posts/intro");
+}
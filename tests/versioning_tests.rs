@@ -0,0 +1,173 @@
+//! API版本的单元测试
+
+#![allow(dead_code)]
+
+mod common;
+
+use common::Path;
+use uni_routing::middleware::{MiddlewareChain, MiddlewareFactory};
+use uni_routing::openapi::OpenApiBuilder;
+use uni_routing::registry::RouteRegistry;
+use uni_routing::routing::RouteInfo;
+use uni_routing::versioning::{versions, Versioning, VersioningStrategy};
+use uni_routing_macros::uni_routing;
+
+#[uni_routing(route = "/users/{id}", method = "GET", version = "v1", description = "Get user (v1)", responses(200 = String))]
+async fn get_user_v1(_path: Path<u64>) {}
+
+#[uni_routing(route = "/users/{id}", method = "GET", version = "v2", description = "Get user (v2)", responses(200 = String))]
+async fn get_user_v2(_path: Path<u64>) {}
+
+#[uni_routing(route = "/users/{id}", method = "PUT", version = "v2", request_body = String)]
+async fn update_user_v2(_path: Path<u64>) {}
+
+#[uni_routing(route = "/health", method = "GET")]
+async fn health() {}
+
+#[uni_routing(route = "/api/users", method = "GET")]
+async fn list_api_users() {}
+
+fn routes() -> Vec<RouteInfo> {
+    vec![__register_route_get_user_v1(), __register_route_get_user_v2(), __register_route_health()]
+}
+
+fn chain(versioning: Versioning) -> MiddlewareChain {
+    MiddlewareChain::new()
+        .with_versioning(versioning)
+        .with_routes(routes(), &MiddlewareFactory::default())
+        .unwrap()
+}
+
+#[test]
+fn test_versioned_route_paths() {
+    let v2 = __register_route_get_user_v2();
+    assert_eq!(v2.version.as_deref(), Some("v2"));
+    assert_eq!(v2.path, "/users/{id}");
+    assert_eq!(v2.full_path(), "/v2/users/{id}");
+    assert_eq!(v2.openapi_path(), "/v2/users/{id}");
+    assert!(v2.matches("GET", "/v2/users/1"));
+    assert!(!v2.matches("GET", "/users/1"));
+    assert_eq!(__register_route_health().full_path(), "/health");
+
+    assert_eq!(versions(&routes()), vec!["v1", "v2"]);
+}
+
+#[test]
+fn test_registry_accepts_same_path_in_different_versions() {
+    let registry = RouteRegistry::new();
+    for route in routes() {
+        registry.register_route(route).unwrap();
+    }
+    assert!(registry.register_route(__register_route_get_user_v2()).is_err());
+}
+
+#[test]
+fn test_url_prefix_dispatch() {
+    let chain = chain(Versioning::new(VersioningStrategy::UrlPrefix));
+    let context = chain.build_context("GET", "/v1/users/7", [("Accept-Version", "v2")]);
+    assert_eq!(context.path, "/v1/users/7");
+    assert_eq!(context.route.unwrap().version.as_deref(), Some("v1"));
+}
+
+#[test]
+fn test_header_dispatch() {
+    let chain = chain(Versioning::new(VersioningStrategy::header()).default_version("v1"));
+
+    let context = chain.build_context("GET", "/users/7", [("accept-version", "v2")]);
+    assert_eq!(context.path, "/v2/users/7");
+    assert_eq!(context.route.unwrap().description.as_deref(), Some("Get user (v2)"));
+
+    // 未指定版本时使用默认版本
+    let context = chain.build_context("GET", "/users/7", []);
+    assert_eq!(context.path, "/v1/users/7");
+
+    // 未分版本的路由与不存在的版本保持原路径
+    let context = chain.build_context("GET", "/health", [("Accept-Version", "v2")]);
+    assert_eq!(context.path, "/health");
+    assert!(context.route.is_some());
+    let context = chain.build_context("GET", "/users/7", [("Accept-Version", "v9")]);
+    assert_eq!(context.path, "/users/7");
+    assert!(context.route.is_none());
+}
+
+#[test]
+fn test_header_dispatch_ignores_unversioned_routes() {
+    let mut routes = routes();
+    routes.push(__register_route_list_api_users());
+    let chain = MiddlewareChain::new()
+        .with_versioning(Versioning::new(VersioningStrategy::header()))
+        .with_routes(routes, &MiddlewareFactory::default())
+        .unwrap();
+
+    // 版本号恰好是未分版本路由的路径前缀时不改写
+    let context = chain.build_context("GET", "/users", [("Accept-Version", "api")]);
+    assert_eq!(context.path, "/users");
+    assert!(context.route.is_none());
+}
+
+#[test]
+fn test_media_type_dispatch() {
+    let versioning = Versioning::new(VersioningStrategy::media_type("acme"));
+    assert_eq!(
+        versioning.requested_version([("Accept", "text/html, application/vnd.acme.v2+json; q=0.9")]),
+        Some("v2".to_string())
+    );
+    assert_eq!(versioning.requested_version([("Accept", "application/vnd.other.v2+json")]), None);
+
+    let chain = chain(versioning);
+    let context = chain.build_context("GET", "/users/7", [("Accept", "application/vnd.acme.v1+json")]);
+    assert_eq!(context.path, "/v1/users/7");
+}
+
+#[test]
+fn test_openapi_document_per_version() {
+    let builder = OpenApiBuilder::new("Test API", "1.0.0");
+
+    let documents = Versioning::new(VersioningStrategy::UrlPrefix).openapi_documents(&builder, &routes());
+    assert_eq!(documents.keys().cloned().collect::<Vec<_>>(), vec!["v1", "v2"]);
+    let v2 = documents["v2"].to_json();
+    assert_eq!(v2["info"]["version"], "v2");
+    assert!(v2["paths"]["/v2/users/{id}"]["get"].is_object());
+    assert!(v2["paths"]["/v1/users/{id}"].is_null());
+    assert!(v2["paths"]["/health"]["get"].is_object());
+
+    let header = Versioning::new(VersioningStrategy::header()).openapi(&builder, &routes(), "v1").to_json();
+    let parameters = &header["paths"]["/users/{id}"]["get"]["parameters"];
+    assert_eq!(parameters[1]["name"], "Accept-Version");
+    assert_eq!(parameters[1]["in"], "header");
+    assert_eq!(parameters[1]["required"], true);
+    assert_eq!(parameters[1]["schema"]["enum"], serde_json::json!(["v1"]));
+
+    let mut routes = routes();
+    routes.push(__register_route_update_user_v2());
+    let media = Versioning::new(VersioningStrategy::media_type("acme")).openapi(&builder, &routes, "v2").to_json();
+    assert!(media["paths"]["/users/{id}"]["get"]["responses"]["200"]["content"]["application/vnd.acme.v2+json"].is_object());
+    let request = &media["paths"]["/users/{id}"]["put"]["requestBody"]["content"];
+    assert!(request["application/vnd.acme.v2+json"].is_object());
+    assert!(request["application/json"].is_null());
+}
+
+#[actix_web::test]
+async fn test_actix_header_dispatch() {
+    use actix_web::{test, web, App, HttpResponse};
+    use uni_routing::frameworks::actix::UniMiddleware;
+
+    let chain = chain(Versioning::new(VersioningStrategy::header()).default_version("v1"));
+    let app = test::init_service(
+        App::new()
+            .wrap(UniMiddleware::new(chain))
+            .route("/v1/users/{id}", web::get().to(|id: web::Path<u64>| async move {
+                HttpResponse::Ok().body(format!("v1:{}", id))
+            }))
+            .route("/v2/users/{id}", web::get().to(|id: web::Path<u64>| async move {
+                HttpResponse::Ok().body(format!("v2:{}", id))
+            })),
+    )
+    .await;
+
+    let req = test::TestRequest::get().uri("/users/3?x=1").insert_header(("Accept-Version", "v2")).to_request();
+    assert_eq!(test::call_and_read_body(&app, req).await, "v2:3");
+
+    let req = test::TestRequest::get().uri("/users/3").to_request();
+    assert_eq!(test::call_and_read_body(&app, req).await, "v1:3");
+}
@@ -0,0 +1,192 @@
+//! 解析 `#[uni_routing(...)]` 的属性参数
+//!
+//! 参数是以逗号分隔的 `key = value`、`key = [...]` 或 `key(...)` 列表，
+//! 字符串字面量中的逗号不会被拆分，格式错误时在对应的位置报错。
+
+use proc_macro2::Span;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{Ident, Lit, LitBool, LitStr, Token, Type};
+
+use crate::metadata::{self, ExampleItem, ExternalDocsItem, ResponseItem};
+
+/// 路由级中间件声明，例如 `rate_limit("10/s")` 或 `cache(60)`
+pub struct MiddlewareItem {
+    pub name: Ident,
+    pub args: Vec<String>,
+}
+
+impl Parse for MiddlewareItem {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name: Ident = input.parse()?;
+        let mut args = Vec::new();
+        if input.peek(syn::token::Paren) {
+            let content;
+            syn::parenthesized!(content in input);
+            let lits = Punctuated::<Lit, Token![,]>::parse_terminated(&content)?;
+            args = lits.iter().map(lit_to_string).collect();
+        }
+        Ok(Self { name, args })
+    }
+}
+
+/// 将字面量转换为字符串参数
+fn lit_to_string(lit: &Lit) -> String {
+    match lit {
+        Lit::Str(s) => s.value(),
+        Lit::Int(i) => i.base10_digits().to_string(),
+        Lit::Float(f) => f.base10_digits().to_string(),
+        Lit::Bool(b) => b.value.to_string(),
+        other => quote!(#other).to_string(),
+    }
+}
+
+/// 外部文档参数
+pub struct ExternalDocs {
+    pub url: LitStr,
+    pub description: Option<LitStr>,
+}
+
+/// `#[uni_routing(...)]` 的全部参数
+#[derive(Default)]
+pub struct RouteArgs {
+    pub route: Option<LitStr>,
+    pub methods: Vec<LitStr>,
+    pub auth_policy: Option<LitStr>,
+    pub description: Option<LitStr>,
+    pub rate_limit: Option<LitStr>,
+    pub summary: Option<LitStr>,
+    pub operation_id: Option<LitStr>,
+    pub deprecated: bool,
+    pub request_body: Option<Type>,
+    pub middleware: Vec<MiddlewareItem>,
+    pub tags: Vec<LitStr>,
+    pub responses: Vec<ResponseItem>,
+    pub examples: Vec<ExampleItem>,
+    pub external_docs: Option<ExternalDocs>,
+    pub version: Option<LitStr>,
+    pub feature_flag: Option<LitStr>,
+}
+
+impl Parse for RouteArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut args = RouteArgs::default();
+        let mut seen: Vec<String> = Vec::new();
+
+        while !input.is_empty() {
+            let key: Ident = input.parse()?;
+            let name = key.to_string();
+            if seen.contains(&name) {
+                return Err(syn::Error::new(
+                    key.span(),
+                    format!("duplicate uni_routing attribute `{}`", name),
+                ));
+            }
+
+            match name.as_str() {
+                "route" => args.route = Some(parse_value(input)?),
+                "method" => args.methods = parse_methods(&key, input)?,
+                "auth_policy" => args.auth_policy = Some(parse_value(input)?),
+                "description" => args.description = Some(parse_value(input)?),
+                "rate_limit" => args.rate_limit = Some(parse_value(input)?),
+                "summary" => args.summary = Some(parse_value(input)?),
+                "operation_id" => args.operation_id = Some(parse_value(input)?),
+                "deprecated" => {
+                    // 单独的 `deprecated` 等价于 `deprecated = true`
+                    args.deprecated = if input.peek(Token![=]) {
+                        parse_value::<LitBool>(input)?.value
+                    } else {
+                        true
+                    };
+                }
+                "request_body" => args.request_body = Some(parse_value(input)?),
+                "middleware" => args.middleware = parse_list(input)?,
+                "tags" => args.tags = parse_list(input)?,
+                "responses" => {
+                    args.responses = parse_nested(input)?;
+                    metadata::check_unique_statuses(&args.responses)?;
+                }
+                "examples" => args.examples = parse_nested(input)?,
+                "external_docs" => args.external_docs = Some(parse_external_docs(&key, input)?),
+                "version" => args.version = Some(parse_value(input)?),
+                "feature_flag" => args.feature_flag = Some(parse_value(input)?),
+                _ => {
+                    return Err(syn::Error::new(
+                        key.span(),
+                        format!("unknown uni_routing attribute `{}`", name),
+                    ));
+                }
+            }
+            seen.push(name);
+
+            if input.is_empty() {
+                break;
+            }
+            input.parse::<Token![,]>()?;
+        }
+        Ok(args)
+    }
+}
+
+/// 解析 `= value`
+fn parse_value<T: Parse>(input: ParseStream) -> syn::Result<T> {
+    input.parse::<Token![=]>()?;
+    input.parse()
+}
+
+/// 解析 `= "GET"` 或 `= ["GET", "HEAD"]`
+fn parse_methods(key: &Ident, input: ParseStream) -> syn::Result<Vec<LitStr>> {
+    if !input.peek2(syn::token::Bracket) {
+        return Ok(vec![parse_value(input)?]);
+    }
+    let methods: Vec<LitStr> = parse_list(input)?;
+    if methods.is_empty() {
+        return Err(syn::Error::new(key.span(), "`method` requires at least one HTTP method"));
+    }
+    Ok(methods)
+}
+
+/// 解析 `= [a, b, ...]`
+fn parse_list<T: Parse>(input: ParseStream) -> syn::Result<Vec<T>> {
+    input.parse::<Token![=]>()?;
+    let content;
+    syn::bracketed!(content in input);
+    let items = Punctuated::<T, Token![,]>::parse_terminated(&content)?;
+    Ok(items.into_iter().collect())
+}
+
+/// 解析 `(a, b, ...)`
+fn parse_nested<T: Parse>(input: ParseStream) -> syn::Result<Vec<T>> {
+    let content;
+    syn::parenthesized!(content in input);
+    let items = Punctuated::<T, Token![,]>::parse_terminated(&content)?;
+    Ok(items.into_iter().collect())
+}
+
+/// 解析 `external_docs(url = "...", description = "...")`
+fn parse_external_docs(key: &Ident, input: ParseStream) -> syn::Result<ExternalDocs> {
+    let mut url = None;
+    let mut description = None;
+    for item in parse_nested::<ExternalDocsItem>(input)? {
+        let slot = if item.key == "url" { &mut url } else { &mut description };
+        if slot.is_some() {
+            return Err(syn::Error::new(
+                item.key.span(),
+                format!("duplicate `{}` in `external_docs`", item.key),
+            ));
+        }
+        *slot = Some(item.value);
+    }
+
+    let url = url.ok_or_else(|| syn::Error::new(key.span(), "`external_docs` requires `url`"))?;
+    Ok(ExternalDocs { url, description })
+}
+
+/// 字符串参数的值与位置，未设置时使用默认值和调用位置
+pub fn value_or(lit: &Option<LitStr>, default: &str) -> (String, Span) {
+    match lit {
+        Some(lit) => (lit.value(), lit.span()),
+        None => (default.to_string(), Span::call_site()),
+    }
+}
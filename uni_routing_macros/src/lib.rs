@@ -1,85 +1,317 @@
 //! 过程宏定义，实现uni_routing属性宏
 
 use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
 use quote::quote;
-use syn::parse_macro_input;
-use syn::ItemFn;
+use syn::{parse_macro_input, ItemFn, LitStr};
+
+mod args;
+mod metadata;
+mod path;
+mod schema;
+mod url;
+
+use args::RouteArgs;
 
 /// uni_routing属性宏，用于简化路由配置并自动注册
 #[proc_macro_attribute]
 pub fn uni_routing(args: TokenStream, input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(args as RouteArgs);
     let input_fn = parse_macro_input!(input as ItemFn);
-    
+
+    expand(args, input_fn)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// 生成原始函数与路由注册函数
+fn expand(args: RouteArgs, input_fn: ItemFn) -> syn::Result<TokenStream2> {
+    let fn_attrs = &input_fn.attrs;
     let fn_name = &input_fn.sig.ident;
     let fn_vis = &input_fn.vis;
     let fn_sig = &input_fn.sig;
     let fn_block = &input_fn.block;
-    
-    // 解析属性参数
-    let args_str = args.to_string();
-    let mut route = None;
-    let mut method = None;
-    let mut auth_policy = None;
-    let mut description = None;
-    
-    // 简单的字符串解析
-    for pair in args_str.split(',') {
-        let pair = pair.trim();
-        if let Some((key, value)) = pair.split_once('=') {
-            let key = key.trim();
-            let value = value.trim().trim_matches('\"');
-            
-            match key {
-                "route" => route = Some(value.to_string()),
-                "method" => method = Some(value.to_string()),
-                "auth_policy" => auth_policy = Some(value.to_string()),
-                "description" => description = Some(value.to_string()),
-                _ => {}
+
+    let (route_lit, route_span) = args::value_or(&args.route, "/");
+    // 将字符串转换为HttpMethod，一个处理函数可以绑定多个方法
+    let default_method = LitStr::new("GET", proc_macro2::Span::call_site());
+    let method_lits = if args.methods.is_empty() {
+        std::slice::from_ref(&default_method)
+    } else {
+        args.methods.as_slice()
+    };
+    let mut methods: Vec<(String, TokenStream2)> = Vec::new();
+    for lit in method_lits {
+        let (name, method_enum) = method_enum(lit)?;
+        if methods.iter().any(|(existing, _)| *existing == name) {
+            return Err(syn::Error::new(lit.span(), format!("duplicate HTTP method `{}`", name)));
+        }
+        methods.push((name, method_enum));
+    }
+
+    let auth_policy = match &args.auth_policy {
+        Some(policy) if !policy.value().is_empty() => {
+            quote!(Some(uni_routing::auth::AuthPolicy::new(#policy)))
+        }
+        _ => quote!(None),
+    };
+    let description = optional_string(&args.description);
+
+    // `rate_limit = "..."` 等价于在路由级中间件最前面声明 `rate_limit("...")`
+    let rate_limit_spec = args.rate_limit.iter().map(|limit| {
+        quote! {
+            uni_routing::middleware::MiddlewareSpec::new("rate_limit", vec![#limit.to_string()])
+        }
+    });
+    let middleware_specs = rate_limit_spec.chain(args.middleware.iter().map(|item| {
+        let name = item.name.to_string();
+        let args = &item.args;
+        quote! {
+            uni_routing::middleware::MiddlewareSpec::new(#name, vec![#(#args.to_string()),*])
+        }
+    }));
+
+    // 校验路由模板语法，以及模板中的参数均有对应的提取器
+    path::validate_route(&route_lit).map_err(|message| syn::Error::new(route_span, message))?;
+    let path_params = path::path_params(&route_lit, fn_sig)
+        .map_err(|message| syn::Error::new(route_span, message))?;
+    // 参数类型名与运行时使用同一张表（`ParamType::from_name`），在常量求值时校验
+    let param_type_checks = path::typed_params(&route_lit).into_iter().map(|(name, ty)| {
+        let message = format!("unknown type `{}` for path parameter `{}` in route `{}`", ty, name, route_lit)
+            .replace('{', "{{")
+            .replace('}', "}}");
+        quote::quote_spanned! {route_span=>
+            const _: () = assert!(uni_routing::matcher::ParamType::from_name(#ty).is_some(), #message);
+        }
+    });
+    let path_params = path_params.iter().map(|(name, schema)| {
+        quote! {
+            uni_routing::routing::PathParam {
+                name: #name.to_string(),
+                schema: #schema,
             }
         }
+    });
+
+    // 请求体Schema：显式声明的 `request_body` 优先，否则从处理函数的 `Json<T>` 参数推断
+    let request_body = match args.request_body.as_ref().or_else(|| schema::request_body(fn_sig)) {
+        Some(ty) => {
+            let schema = schema::schema_expr(ty);
+            quote!(Some(#schema))
+        }
+        None => quote!(None),
+    };
+
+    // 响应列表：从返回类型推断成功响应，`responses(...)` 中声明的状态码覆盖推断结果
+    let mut responses: Vec<(u16, Option<TokenStream2>, Option<String>)> = Vec::new();
+    let inferred_status = schema::response_status(fn_sig, fn_block);
+    if !args.responses.iter().any(|response| response.status == inferred_status) {
+        let schema = schema::response_body(fn_sig).map(schema::schema_expr);
+        responses.push((inferred_status, schema, None));
     }
-    
-    let route_lit = route.unwrap_or_else(|| "/".to_string());
-    let method_lit = method.unwrap_or_else(|| "GET".to_string());
-    let auth_policy_lit = auth_policy.unwrap_or_else(|| String::new());
-    let description_lit = description.unwrap_or_else(|| String::new());
-    
-    // 将字符串转换为HttpMethod
-    let method_enum = match method_lit.to_uppercase().as_str() {
-        "GET" => quote!(uni_routing::routing::HttpMethod::GET),
-        "POST" => quote!(uni_routing::routing::HttpMethod::POST),
-        "PUT" => quote!(uni_routing::routing::HttpMethod::PUT),
-        "DELETE" => quote!(uni_routing::routing::HttpMethod::DELETE),
-        "PATCH" => quote!(uni_routing::routing::HttpMethod::PATCH),
-        _ => quote!(uni_routing::routing::HttpMethod::GET),
+    for response in &args.responses {
+        let schema = response.ty.as_ref().map(schema::schema_expr);
+        responses.push((response.status, schema, None));
+    }
+
+    let mut request_example = None;
+    for example in &args.examples {
+        match example.target {
+            metadata::ExampleTarget::Request => request_example = Some(example.json.clone()),
+            metadata::ExampleTarget::Response(status) => {
+                let response = responses
+                    .iter_mut()
+                    .find(|(declared, _, _)| *declared == status)
+                    .ok_or_else(|| {
+                        syn::Error::new(
+                            example.span,
+                            format!("example for undeclared response status {}", status),
+                        )
+                    })?;
+                response.2 = Some(example.json.clone());
+            }
+        }
+    }
+
+    let json_expr = |json: &Option<String>| match json {
+        Some(json) => quote! {
+            Some(uni_routing::serde_json::from_str(#json).expect("example is validated at compile time"))
+        },
+        None => quote!(None),
     };
+    let request_example = json_expr(&request_example);
+    let responses = responses.iter().map(|(status, schema, example)| {
+        let schema = match schema {
+            Some(schema) => quote!(Some(#schema)),
+            None => quote!(None),
+        };
+        let example = json_expr(example);
+        quote! {
+            uni_routing::routing::ResponseInfo {
+                example: #example,
+                ..uni_routing::routing::ResponseInfo::new(#status, #schema)
+            }
+        }
+    });
+
+    // 操作元数据
+    let summary = optional_string(&args.summary);
+    let operation_id = match &args.operation_id {
+        Some(operation_id) => operation_id.value(),
+        None => fn_name.to_string(),
+    };
+    let deprecated = args.deprecated;
+    let version = match &args.version {
+        Some(version) => {
+            path::validate_version(&version.value()).map_err(|message| syn::Error::new(version.span(), message))?;
+            quote!(Some(#version.to_string()))
+        }
+        None => quote!(None),
+    };
+    let feature_flag = match &args.feature_flag {
+        Some(flag) => {
+            validate_feature_flag(&flag.value()).map_err(|message| syn::Error::new(flag.span(), message))?;
+            quote!(Some(#flag.to_string()))
+        }
+        None => quote!(None),
+    };
+    let tags = args.tags.iter();
+    let external_docs = match &args.external_docs {
+        Some(docs) => {
+            let url = &docs.url;
+            let description = optional_string(&docs.description);
+            quote! {
+                Some(uni_routing::routing::ExternalDocs {
+                    url: #url.to_string(),
+                    description: #description,
+                })
+            }
+        }
+        None => quote!(None),
+    };
+
+    // URL构建函数使用包含版本前缀的完整模板
+    let template = match &args.version {
+        Some(version) => quote!(&uni_routing::group::join_path(#version, #route_lit)),
+        None => quote!(#route_lit),
+    };
+    let url_fn = url::url_fn(fn_name, fn_vis, &operation_id, &route_lit, template, fn_sig)
+        .map_err(|message| syn::Error::new(route_span, message))?;
 
     // 生成路由注册函数
     let register_fn_name = syn::Ident::new(&format!("__register_route_{}", fn_name), fn_name.span());
-    
-    let expanded = quote! {
-        // 原始函数
+    let register_all_fn_name = syn::Ident::new(&format!("__register_routes_{}", fn_name), fn_name.span());
+
+    // 第一个方法使用声明的操作ID，其余方法追加方法名保证操作ID唯一
+    let (_, first_method) = &methods[0];
+    let other_routes = methods[1..].iter().map(|(name, method_enum)| {
+        let operation_id = format!("{}_{}", operation_id, name.to_lowercase());
+        quote! {
+            uni_routing::routing::RouteInfo {
+                method: #method_enum,
+                operation_id: Some(#operation_id.to_string()),
+                ..#register_fn_name()
+            }
+        }
+    });
+
+    Ok(quote! {
+        // 原始函数（保留其他属性，如 Rocket 的路由属性）
+        #(#fn_attrs)*
         #fn_vis #fn_sig #fn_block
-        
-        // 路由注册函数
+
+        // 路由注册函数，返回第一个方法的路由
         #[allow(dead_code)]
         fn #register_fn_name() -> uni_routing::routing::RouteInfo {
             uni_routing::routing::RouteInfo {
                 path: #route_lit.to_string(),
-                method: #method_enum,
-                auth_policy: if #auth_policy_lit.is_empty() { 
-                    None 
-                } else { 
-                    Some(uni_routing::auth::AuthPolicy::new(#auth_policy_lit)) 
-                },
-                description: if #description_lit.is_empty() { 
-                    None 
-                } else { 
-                    Some(#description_lit.to_string()) 
-                },
+                method: #first_method,
+                auth_policy: #auth_policy,
+                description: #description,
+                summary: #summary,
+                tags: vec![#(#tags.to_string()),*],
+                operation_id: Some(#operation_id.to_string()),
+                deprecated: #deprecated,
+                external_docs: #external_docs,
+                middleware: vec![#(#middleware_specs),*],
+                request_body: #request_body,
+                request_example: #request_example,
+                responses: vec![#(#responses),*],
+                path_params: vec![#(#path_params),*],
+                version: #version,
+                feature_flag: #feature_flag,
+                source: Some(uni_routing::routing::RouteSource {
+                    handler: concat!(module_path!(), "::", stringify!(#fn_name)).to_string(),
+                    file: file!().to_string(),
+                    line: line!(),
+                }),
             }
         }
+
+        // 编译期校验路径参数的类型名
+        #(#param_type_checks)*
+
+        // 路由注册函数，返回处理函数绑定的所有方法的路由
+        #[allow(dead_code)]
+        fn #register_all_fn_name() -> Vec<uni_routing::routing::RouteInfo> {
+            vec![#register_fn_name(), #(#other_routes),*]
+        }
+
+        #url_fn
+    })
+}
+
+/// 解析HTTP方法，返回规范化的方法名与 `HttpMethod` 表达式
+///
+/// 标准方法不区分大小写；扩展方法（如 `PROPFIND`）须为大写的合法方法名，
+/// 以免拼写错误的标准方法被当作扩展方法。
+fn method_enum(lit: &LitStr) -> syn::Result<(String, TokenStream2)> {
+    let method = lit.value();
+    let upper = method.to_uppercase();
+    let method_enum = match upper.as_str() {
+        "GET" => quote!(uni_routing::routing::HttpMethod::GET),
+        "POST" => quote!(uni_routing::routing::HttpMethod::POST),
+        "PUT" => quote!(uni_routing::routing::HttpMethod::PUT),
+        "DELETE" => quote!(uni_routing::routing::HttpMethod::DELETE),
+        "PATCH" => quote!(uni_routing::routing::HttpMethod::PATCH),
+        "HEAD" => quote!(uni_routing::routing::HttpMethod::HEAD),
+        "OPTIONS" => quote!(uni_routing::routing::HttpMethod::OPTIONS),
+        "TRACE" => quote!(uni_routing::routing::HttpMethod::TRACE),
+        "CONNECT" => quote!(uni_routing::routing::HttpMethod::CONNECT),
+        _ => {
+            let is_extension = !method.is_empty()
+                && method.bytes().all(|b| b.is_ascii_uppercase() || b.is_ascii_digit() || b == b'-' || b == b'_');
+            if !is_extension {
+                return Err(syn::Error::new(
+                    lit.span(),
+                    format!(
+                        "invalid HTTP method `{}`, expected GET, POST, PUT, DELETE, PATCH, HEAD, OPTIONS, TRACE, CONNECT or an uppercase extension method",
+                        method
+                    ),
+                ));
+            }
+            return Ok((method.clone(), quote!(uni_routing::routing::HttpMethod::Other(#method.to_string()))));
+        }
     };
-    
-    TokenStream::from(expanded)
-}
\ No newline at end of file
+    Ok((upper, method_enum))
+}
+
+/// 校验功能开关名称：非空，只能包含字母、数字、`.`、`-` 和 `_`
+fn validate_feature_flag(flag: &str) -> Result<(), String> {
+    let valid = !flag.is_empty() && flag.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_'));
+    if !valid {
+        return Err(format!(
+            "invalid feature flag `{}`, expected a name such as `new_billing`",
+            flag
+        ));
+    }
+    Ok(())
+}
+
+/// 生成 `Option<String>` 表达式，空字符串视为未设置
+fn optional_string(lit: &Option<LitStr>) -> TokenStream2 {
+    match lit {
+        Some(lit) if !lit.value().is_empty() => quote!(Some(#lit.to_string())),
+        _ => quote!(None),
+    }
+}
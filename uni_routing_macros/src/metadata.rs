@@ -0,0 +1,114 @@
+//! 解析操作元数据参数：`tags`、`responses`、`examples` 与 `external_docs`
+
+use proc_macro2::Span;
+use syn::parse::{Parse, ParseStream};
+use syn::{Ident, LitInt, LitStr, Token, Type};
+
+/// `responses(...)` 中的一项，例如 `404 = ProblemDetails` 或 `204`
+pub struct ResponseItem {
+    pub status: u16,
+    pub ty: Option<Type>,
+    pub span: Span,
+}
+
+impl Parse for ResponseItem {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let span = input.span();
+        let status = parse_status(input)?;
+        let ty = if input.peek(Token![=]) {
+            input.parse::<Token![=]>()?;
+            let ty: Type = input.parse()?;
+            // `()` 表示没有响应体
+            match &ty {
+                Type::Tuple(tuple) if tuple.elems.is_empty() => None,
+                _ => Some(ty),
+            }
+        } else {
+            None
+        };
+        Ok(Self { status, ty, span })
+    }
+}
+
+/// 校验 `responses(...)` 中的状态码没有重复
+pub fn check_unique_statuses(responses: &[ResponseItem]) -> syn::Result<()> {
+    for (index, response) in responses.iter().enumerate() {
+        if responses[..index].iter().any(|previous| previous.status == response.status) {
+            return Err(syn::Error::new(
+                response.span,
+                format!("duplicate response status {}", response.status),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// 示例的目标
+pub enum ExampleTarget {
+    /// 请求体
+    Request,
+    /// 指定状态码的响应
+    Response(u16),
+}
+
+/// `examples(...)` 中的一项，例如 `request = "{...}"` 或 `200 = "{...}"`
+pub struct ExampleItem {
+    pub target: ExampleTarget,
+    pub json: String,
+    pub span: Span,
+}
+
+impl Parse for ExampleItem {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let span = input.span();
+        let target = if input.peek(LitInt) {
+            ExampleTarget::Response(parse_status(input)?)
+        } else {
+            let ident: Ident = input.parse()?;
+            if ident != "request" {
+                return Err(syn::Error::new(
+                    ident.span(),
+                    "expected `request` or a response status code",
+                ));
+            }
+            ExampleTarget::Request
+        };
+        input.parse::<Token![=]>()?;
+
+        let lit: LitStr = input.parse()?;
+        let json = lit.value();
+        if let Err(err) = serde_json::from_str::<serde_json::Value>(&json) {
+            return Err(syn::Error::new(lit.span(), format!("invalid JSON example: {}", err)));
+        }
+        Ok(Self { target, json, span })
+    }
+}
+
+/// `external_docs(url = "...", description = "...")` 中的一项
+pub struct ExternalDocsItem {
+    pub key: Ident,
+    pub value: LitStr,
+}
+
+impl Parse for ExternalDocsItem {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let key: Ident = input.parse()?;
+        if key != "url" && key != "description" {
+            return Err(syn::Error::new(key.span(), "expected `url` or `description`"));
+        }
+        input.parse::<Token![=]>()?;
+        Ok(Self {
+            key,
+            value: input.parse()?,
+        })
+    }
+}
+
+/// 解析100到599之间的HTTP状态码
+fn parse_status(input: ParseStream) -> syn::Result<u16> {
+    let lit: LitInt = input.parse()?;
+    match lit.base10_parse::<u16>() {
+        Ok(status) if (100..600).contains(&status) => Ok(status),
+        _ => Err(syn::Error::new(lit.span(), "expected an HTTP status code")),
+    }
+}
@@ -0,0 +1,209 @@
+//! 路由模板中的路径参数解析与校验
+
+use proc_macro2::TokenStream as TokenStream2;
+use syn::{FnArg, Pat, Signature, Type};
+
+use crate::schema::{is_scalar, schema_expr, unwrap_extractor};
+
+/// 路由模板中的参数段
+struct TemplateParam<'a> {
+    name: &'a str,
+    /// `{id:u64}` 中的类型名，由生成的代码在编译期通过 `ParamType::from_name` 校验
+    ty: Option<&'a str>,
+    optional: bool,
+    catch_all: bool,
+}
+
+/// 提取路由模板中的参数名，支持 `{name}`、`{name:type}`、`{name?}`、`{*name}`
+/// 与 Rocket 风格的 `<name>`、`<name..>`
+pub fn template_params(route: &str) -> Vec<String> {
+    route
+        .split('/')
+        .filter_map(|segment| segment_param(route, segment).ok().flatten())
+        .map(|param| param.name.to_string())
+        .collect()
+}
+
+/// 路由模板中带类型的参数及其类型名，如 `{id:u64}` 返回 `("id", "u64")`
+pub fn typed_params(route: &str) -> Vec<(String, String)> {
+    route
+        .split('/')
+        .filter_map(|segment| segment_param(route, segment).ok().flatten())
+        .filter_map(|param| Some((param.name.to_string(), param.ty?.to_string())))
+        .collect()
+}
+
+/// 校验路由模板语法：以 `/` 开头、没有空路径段、参数占满整个路径段且不重名，
+/// 通配参数只能是最后一段，可选参数之后只能是可选参数
+pub fn validate_route(route: &str) -> Result<(), String> {
+    if !route.starts_with('/') {
+        return Err(format!("route `{}` must start with `/`", route));
+    }
+    if route.contains("//") {
+        return Err(format!("route `{}` contains an empty path segment", route));
+    }
+
+    let segments: Vec<&str> = route.split('/').skip(1).filter(|segment| !segment.is_empty()).collect();
+    let mut seen: Vec<&str> = Vec::new();
+    let mut after_optional = false;
+    for (index, segment) in segments.iter().enumerate() {
+        let param = segment_param(route, segment)?;
+        let optional = param.as_ref().is_some_and(|param| param.optional);
+        if after_optional && !optional {
+            return Err(format!(
+                "optional parameters in route `{}` may only be followed by optional parameters",
+                route
+            ));
+        }
+        after_optional = optional;
+
+        let Some(param) = param else {
+            continue;
+        };
+        if param.catch_all && index + 1 != segments.len() {
+            return Err(format!("catch-all parameter `{}` must be the last segment of route `{}`", param.name, route));
+        }
+        if seen.contains(&param.name) {
+            return Err(format!("duplicate path parameter `{}` in route `{}`", param.name, route));
+        }
+        seen.push(param.name);
+    }
+    Ok(())
+}
+
+/// 校验API版本：作为单个静态路径段使用，只能包含字母、数字、`.`、`-` 和 `_`
+pub fn validate_version(version: &str) -> Result<(), String> {
+    let valid = !version.is_empty()
+        && version.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_'));
+    if !valid {
+        return Err(format!(
+            "invalid API version `{}`, expected a single path segment such as `v2`",
+            version
+        ));
+    }
+    Ok(())
+}
+
+/// 解析单个路径段，参数段返回参数信息，静态段返回 `None`
+fn segment_param<'a>(route: &str, segment: &'a str) -> Result<Option<TemplateParam<'a>>, String> {
+    let braced = segment.strip_prefix('{').and_then(|rest| rest.strip_suffix('}'));
+    let angled = segment.strip_prefix('<').and_then(|rest| rest.strip_suffix('>'));
+
+    let param = match (braced, angled) {
+        (Some(inner), _) => match inner.strip_prefix('*') {
+            Some(name) => TemplateParam { name, ty: None, optional: false, catch_all: true },
+            None => {
+                let (inner, optional) = match inner.strip_suffix('?') {
+                    Some(inner) => (inner, true),
+                    None => (inner, false),
+                };
+                let (name, ty) = match inner.split_once(':') {
+                    Some((name, ty)) => (name, Some(ty)),
+                    None => (inner, None),
+                };
+                TemplateParam { name, ty, optional, catch_all: false }
+            }
+        },
+        (None, Some(inner)) => match inner.strip_suffix("..") {
+            Some(name) => TemplateParam { name, ty: None, optional: false, catch_all: true },
+            None => TemplateParam { name: inner, ty: None, optional: false, catch_all: false },
+        },
+        (None, None) => {
+            if segment.contains(['{', '}', '<', '>']) {
+                return Err(format!(
+                    "malformed path segment `{}` in route `{}`: braces must be balanced and a parameter must span the whole segment, e.g. `{{id}}`",
+                    segment, route
+                ));
+            }
+            return Ok(None);
+        }
+    };
+
+    let name = param.name;
+    let valid = name.chars().next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !valid {
+        return Err(format!("invalid path parameter name `{}` in route `{}`", segment, route));
+    }
+    Ok(Some(param))
+}
+
+/// 将路由模板中的参数与处理函数的提取器对应，返回参数名及其Schema表达式
+///
+/// 参数可以由 `Path<T>` 提取器提供（`T` 为元组时按位置对应），
+/// 也可以由同名的函数参数提供（Rocket 风格）。
+pub fn path_params(route: &str, sig: &Signature) -> Result<Vec<(String, TokenStream2)>, String> {
+    Ok(param_types(route, sig)?
+        .into_iter()
+        .map(|(name, ty)| {
+            // 结构体等类型按字段名提取，无法在宏中确定字段类型
+            let schema = ty.map_or_else(|| quote::quote!(uni_routing::schema::string()), schema_expr);
+            (name, schema)
+        })
+        .collect())
+}
+
+/// 路由模板中的参数及其在处理函数中的类型，由结构体提取器按字段名提取时类型为 `None`
+pub fn param_types<'a>(route: &str, sig: &'a Signature) -> Result<Vec<(String, Option<&'a Type>)>, String> {
+    let params = template_params(route);
+    if params.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let typed_args = || {
+        sig.inputs.iter().filter_map(|arg| match arg {
+            FnArg::Typed(pat_type) => Some(pat_type),
+            FnArg::Receiver(_) => None,
+        })
+    };
+    let extractor = typed_args().find_map(|arg| unwrap_extractor(&arg.ty, "Path"));
+
+    match extractor {
+        Some(Type::Tuple(tuple)) => {
+            if tuple.elems.len() != params.len() {
+                return Err(format!(
+                    "route `{}` has {} path parameter(s) but the Path extractor has {} element(s)",
+                    route,
+                    params.len(),
+                    tuple.elems.len()
+                ));
+            }
+            Ok(params.into_iter().zip(tuple.elems.iter().map(Some)).collect())
+        }
+        // 单个可选参数写作 `Path<Option<T>>`
+        Some(ty) if is_scalar(unwrap_extractor(ty, "Option").unwrap_or(ty)) => {
+            if params.len() != 1 {
+                return Err(format!(
+                    "route `{}` has {} path parameters but the Path extractor has a single value; use a tuple",
+                    route,
+                    params.len()
+                ));
+            }
+            Ok(params.into_iter().map(|name| (name, Some(ty))).collect())
+        }
+        Some(_) => Ok(params.into_iter().map(|name| (name, None)).collect()),
+        None => params
+            .into_iter()
+            .map(|name| {
+                let arg = typed_args().find(|arg| matches!(&*arg.pat, Pat::Ident(ident) if ident.ident == name));
+                match arg {
+                    Some(arg) => Ok((name, Some(&*arg.ty))),
+                    None => Err(format!(
+                        "route parameter `{}` has no matching handler argument or Path extractor",
+                        name
+                    )),
+                }
+            })
+            .collect(),
+    }
+}
+
+/// 路由模板中可以省略的参数名（`{page?}`）
+pub fn optional_params(route: &str) -> Vec<String> {
+    route
+        .split('/')
+        .filter_map(|segment| segment_param(route, segment).ok().flatten())
+        .filter(|param| param.optional)
+        .map(|param| param.name.to_string())
+        .collect()
+}
@@ -0,0 +1,189 @@
+//! 从处理函数签名推断请求体与响应体的Schema
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::visit::Visit;
+use syn::{Block, Expr, ExprTuple, FnArg, GenericArgument, Lit, PathArguments, ReturnType, Signature, Type};
+
+/// 返回类型路径的最后一段及其第一个泛型参数
+pub fn last_segment(ty: &Type) -> Option<(String, Option<&Type>)> {
+    let Type::Path(type_path) = ty else {
+        return None;
+    };
+    let segment = type_path.path.segments.last()?;
+    let inner = match &segment.arguments {
+        PathArguments::AngleBracketed(args) => args.args.iter().find_map(|arg| match arg {
+            GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        }),
+        _ => None,
+    };
+    Some((segment.ident.to_string(), inner))
+}
+
+/// 若类型为 `Wrapper<T>`（如 `Json<T>`、`web::Path<T>`）则返回 `T`
+pub fn unwrap_extractor<'a>(ty: &'a Type, wrapper: &str) -> Option<&'a Type> {
+    match last_segment(ty)? {
+        (name, inner) if name == wrapper => inner,
+        _ => None,
+    }
+}
+
+/// 判断类型是否为整数、浮点数、布尔或字符串等标量类型
+pub fn is_scalar(ty: &Type) -> bool {
+    let ty = match ty {
+        Type::Reference(reference) => &*reference.elem,
+        _ => ty,
+    };
+    matches!(
+        last_segment(ty),
+        Some((name, None)) if matches!(
+            name.as_str(),
+            "u8" | "u16" | "u32" | "u64" | "u128" | "usize"
+                | "i8" | "i16" | "i32" | "i64" | "i128" | "isize"
+                | "f32" | "f64" | "bool" | "String" | "str" | "char"
+        )
+    )
+}
+
+/// 生成描述类型 `ty` 的 `TypeSchema` 表达式
+pub fn schema_expr(ty: &Type) -> TokenStream2 {
+    match ty {
+        Type::Reference(reference) => return schema_expr(&reference.elem),
+        Type::Slice(slice) => {
+            let items = schema_expr(&slice.elem);
+            return quote!(uni_routing::schema::array(#items));
+        }
+        Type::Array(array) => {
+            let items = schema_expr(&array.elem);
+            return quote!(uni_routing::schema::array(#items));
+        }
+        _ => {}
+    }
+
+    let Some((name, inner)) = last_segment(ty) else {
+        return quote!(uni_routing::schema::any());
+    };
+
+    match (name.as_str(), inner) {
+        ("u8" | "u16" | "u32", _) => quote!(uni_routing::schema::integer("int32", true)),
+        ("u64" | "u128" | "usize", _) => quote!(uni_routing::schema::integer("int64", true)),
+        ("i8" | "i16" | "i32", _) => quote!(uni_routing::schema::integer("int32", false)),
+        ("i64" | "i128" | "isize", _) => quote!(uni_routing::schema::integer("int64", false)),
+        ("f32", _) => quote!(uni_routing::schema::number("float")),
+        ("f64", _) => quote!(uni_routing::schema::number("double")),
+        ("bool", _) => quote!(uni_routing::schema::boolean()),
+        ("String" | "str" | "char", _) => quote!(uni_routing::schema::string()),
+        ("Value", None) => quote!(uni_routing::schema::any()),
+        ("Vec" | "VecDeque" | "HashSet" | "BTreeSet", Some(item)) => {
+            let items = schema_expr(item);
+            quote!(uni_routing::schema::array(#items))
+        }
+        ("Option" | "Box" | "Arc" | "Rc", Some(inner)) => schema_expr(inner),
+        _ => quote!(uni_routing::schema::component::<#ty>()),
+    }
+}
+
+/// 从处理函数参数中找到 `Json<T>` 请求体
+pub fn request_body(sig: &Signature) -> Option<&Type> {
+    sig.inputs.iter().find_map(|arg| match arg {
+        FnArg::Typed(pat_type) => unwrap_extractor(&pat_type.ty, "Json"),
+        FnArg::Receiver(_) => None,
+    })
+}
+
+/// 从返回类型中找到 `Json<T>` 响应体，依次展开 `Result`、元组等包装
+pub fn response_body(sig: &Signature) -> Option<&Type> {
+    match &sig.output {
+        ReturnType::Default => None,
+        ReturnType::Type(_, ty) => find_json(ty),
+    }
+}
+
+fn find_json(ty: &Type) -> Option<&Type> {
+    match ty {
+        Type::Tuple(tuple) => tuple.elems.iter().find_map(find_json),
+        Type::Paren(paren) => find_json(&paren.elem),
+        _ => match last_segment(ty)? {
+            (name, inner) if name == "Json" => inner,
+            (name, Some(inner)) if name.ends_with("Result") => find_json(inner),
+            _ => None,
+        },
+    }
+}
+
+/// 推断成功响应的状态码
+///
+/// 返回类型为 `(状态码, Json<T>)` 元组时，从函数体中的元组表达式读取状态码（整数字面量或
+/// `StatusCode::CREATED` 等常量）；函数体中的状态码不唯一或无法识别时使用200。
+pub fn response_status(sig: &Signature, block: &Block) -> u16 {
+    let returns_status_tuple = match &sig.output {
+        ReturnType::Type(_, ty) => has_status_tuple(ty),
+        ReturnType::Default => false,
+    };
+    if !returns_status_tuple {
+        return 200;
+    }
+
+    let mut visitor = StatusVisitor::default();
+    visitor.visit_block(block);
+    visitor.statuses.dedup();
+    match visitor.statuses.as_slice() {
+        [status] => *status,
+        _ => 200,
+    }
+}
+
+fn has_status_tuple(ty: &Type) -> bool {
+    match ty {
+        Type::Tuple(tuple) => tuple.elems.len() >= 2 && tuple.elems.iter().skip(1).any(|elem| find_json(elem).is_some()),
+        Type::Paren(paren) => has_status_tuple(&paren.elem),
+        _ => matches!(last_segment(ty), Some((name, Some(inner))) if name.ends_with("Result") && has_status_tuple(inner)),
+    }
+}
+
+/// 收集函数体中首个元素为状态码的元组表达式
+#[derive(Default)]
+struct StatusVisitor {
+    statuses: Vec<u16>,
+}
+
+impl<'ast> Visit<'ast> for StatusVisitor {
+    fn visit_expr_tuple(&mut self, tuple: &'ast ExprTuple) {
+        if tuple.elems.len() >= 2 {
+            if let Some(status) = tuple.elems.first().and_then(status_code) {
+                if !self.statuses.contains(&status) {
+                    self.statuses.push(status);
+                }
+            }
+        }
+        syn::visit::visit_expr_tuple(self, tuple);
+    }
+
+    // 闭包和嵌套函数中的元组不是处理函数的返回值
+    fn visit_expr_closure(&mut self, _: &'ast syn::ExprClosure) {}
+
+    fn visit_item(&mut self, _: &'ast syn::Item) {}
+}
+
+/// 识别整数字面量与常见的状态码常量（`StatusCode::CREATED`、`Status::Created`）
+fn status_code(expr: &Expr) -> Option<u16> {
+    match expr {
+        Expr::Lit(lit) => match &lit.lit {
+            Lit::Int(int) => int.base10_parse::<u16>().ok().filter(|status| (100..600).contains(status)),
+            _ => None,
+        },
+        Expr::Path(path) => {
+            let name = path.path.segments.last()?.ident.to_string().replace('_', "").to_ascii_lowercase();
+            let status = match name.as_str() {
+                "ok" => 200,
+                "created" => 201,
+                "accepted" => 202,
+                "nocontent" => 204,
+                _ => return None,
+            };
+            Some(status)
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,98 @@
+//! 生成类型化的URL构建函数（反向路由）
+//!
+//! 每个处理函数生成 `<name>_url(...)`，参数按路由模板中的顺序排列。函数名只占用值命名空间，
+//! 不与同名的模块或框架路由属性（如 Rocket 的 `#[get]`）生成的类型冲突。
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{Ident, Signature, Type, Visibility};
+
+use crate::path;
+use crate::schema::{is_scalar, last_segment, unwrap_extractor};
+
+/// 生成URL构建函数，`template` 为求值为完整路由模板（包含版本前缀）的表达式
+///
+/// 运行时优先使用全局注册表中 `operation_id` 对应路由的模板，以包含分组前缀与配置重映射。
+pub fn url_fn(
+    fn_name: &Ident,
+    fn_vis: &Visibility,
+    operation_id: &str,
+    route: &str,
+    template: TokenStream2,
+    sig: &Signature,
+) -> Result<TokenStream2, String> {
+    let optional = path::optional_params(route);
+    let params: Vec<(Ident, String, TokenStream2, bool)> = path::param_types(route, sig)?
+        .into_iter()
+        .map(|(name, ty)| {
+            let is_optional = optional.contains(&name);
+            let arg = param_ident(&name)?;
+            let ty = arg_type(ty, is_optional);
+            Ok((arg, name, ty, is_optional))
+        })
+        .collect::<Result<_, String>>()?;
+
+    let args: Vec<TokenStream2> = params.iter().map(|(arg, _, ty, _)| quote!(#arg: #ty)).collect();
+    let required = params
+        .iter()
+        .filter(|(.., is_optional)| !is_optional)
+        .map(|(arg, name, ..)| quote!((#name, #arg.to_string())));
+    let optional = params.iter().filter(|(.., is_optional)| *is_optional).map(|(arg, name, ..)| {
+        quote! {
+            if let Some(#arg) = #arg {
+                __params.push((#name, #arg.to_string()));
+            }
+        }
+    });
+
+    let url_fn_name = Ident::new(&format!("{}_url", fn_name), fn_name.span());
+    let doc = format!(" 生成 `{}` 的请求路径，参数值进行百分号编码", route);
+    Ok(quote! {
+        #[doc = #doc]
+        #[allow(dead_code)]
+        #fn_vis fn #url_fn_name(#(#args),*) -> String {
+            #[allow(unused_mut)]
+            let mut __params: Vec<(&str, String)> = vec![#(#required),*];
+            #(#optional)*
+            uni_routing::url::build_registered(#operation_id, #template, &__params)
+        }
+    })
+}
+
+/// 参数名作为函数参数，关键字使用原始标识符
+///
+/// `self`、`super`、`crate`、`Self` 和 `_` 不能作为原始标识符，返回错误。
+fn param_ident(name: &str) -> Result<Ident, String> {
+    if let Ok(ident) = syn::parse_str::<Ident>(name) {
+        return Ok(ident);
+    }
+    if matches!(name, "self" | "super" | "crate" | "Self" | "_") {
+        return Err(format!(
+            "path parameter `{}` cannot be used as an argument of the generated URL builder, rename it",
+            name
+        ));
+    }
+    Ok(Ident::new_raw(name, proc_macro2::Span::call_site()))
+}
+
+/// URL构建函数的参数类型：标量类型保持原类型，字符串接受 `&str`，其余类型接受任何实现 `Display` 的值
+fn arg_type(ty: Option<&Type>, optional: bool) -> TokenStream2 {
+    let ty = match ty {
+        Some(ty) if optional => unwrap_extractor(ty, "Option").or(Some(ty)),
+        ty => ty,
+    };
+    let ty = match ty {
+        Some(Type::Reference(reference)) => Some(&*reference.elem),
+        ty => ty,
+    };
+    let arg = match ty {
+        Some(ty) if matches!(last_segment(ty), Some((name, None)) if name == "String" || name == "str") => quote!(&str),
+        Some(ty) if is_scalar(ty) => quote!(#ty),
+        _ => quote!(impl ::std::fmt::Display),
+    };
+    if optional {
+        quote!(Option<#arg>)
+    } else {
+        arg
+    }
+}